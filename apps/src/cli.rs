@@ -1,5 +1,7 @@
 use clap::Parser;
-use concilium_cli::{get_new_node_wallet_handler, get_new_user_wallet_handler, get_transaction_info_handler, send_to_address_handler};
+use concilium_cli::{get_new_node_wallet_handler, get_new_user_wallet_handler, get_transaction_info_handler, get_transaction_status_handler, send_to_address_handler, stake_to_node_handler, unstake_handler};
+use concilium_core::jrpc::utxo::SendToAddressResponse;
+use concilium_error::Error;
 use concilium_core::cli::{Commands, Cli};
 
 #[tokio::main]
@@ -61,6 +63,16 @@ async fn main() {
                         
                         println!("\x1b[1;4m{}\x1b[0m", "VOUT:");
                         println!("{}", serde_json::to_string_pretty(&transaction.vout).expect("failed to read vout"));
+
+                        if let Some(node_public_key) = transaction.stake {
+                            println!("\n\x1b[1;4m{}\x1b[0m", "Staked To Node:");
+                            println!("  {}", node_public_key);
+                        }
+
+                        if let Some(unstake) = transaction.unstake {
+                            println!("\n\x1b[1;4m{}\x1b[0m", "Unstaked:");
+                            println!("{}", serde_json::to_string_pretty(&unstake).expect("failed to read unstake"));
+                        }
                         println!("==================================================\n");
                     }
                 },
@@ -68,51 +80,86 @@ async fn main() {
             }
         }
         Commands::CheckTransactionStatus { txid } => {
-            let response = get_transaction_info_handler(txid).await;
+            let response = get_transaction_status_handler(txid).await;
             
             match response {
                 Ok(data) => {
-                    if data.status == false {
-                        println!("\n==================================================");
-                        println!("\x1b[1;4;31m{}\x1b[0m", "Unsuccessful transaction");
-                        println!("==================================================\n");
-                    } else {
-                        println!("\n==================================================");
-                        println!("\x1b[1;4;32m{}\x1b[0m", "Successful transaction");
-                        println!("==================================================\n");
+                    match data.state.as_deref() {
+                        Some("committed") => {
+                            println!("\n==================================================");
+                            println!("\x1b[1;4;32m{}\x1b[0m", "Successful transaction");
+                            println!("==================================================\n");
+                        },
+                        Some("rejected") => {
+                            println!("\n==================================================");
+                            println!("\x1b[1;4;31m{}\x1b[0m\n", "Rejected transaction");
+                            println!("\x1b[1;4m{}\x1b[0m", "Reason:");
+                            println!("  {}", data.reason.unwrap_or_default());
+                            println!("==================================================\n");
+                        },
+                        Some(state) => {
+                            println!("\n==================================================");
+                            println!("\x1b[1;4;33m{}\x1b[0m\n", "Pending transaction");
+                            println!("\x1b[1;4m{}\x1b[0m", "State:");
+                            println!("  {}", state);
+                            println!("==================================================\n");
+                        },
+                        None => {
+                            println!("\n==================================================");
+                            println!("\x1b[1;4;31m{}\x1b[0m", "Transaction not found");
+                            println!("==================================================\n");
+                        }
                     }
                 },
-                Err(_) => {
-                    println!("\n==================================================");
-                    println!("\x1b[1;4;31m{}\x1b[0m", "Unsuccessful transaction");
-                    println!("==================================================\n");
-                }
-            }
-        }
-        Commands::SendToAddress {sender_private_key, receiver_public_key, amount} => {
-            match send_to_address_handler(sender_private_key, receiver_public_key, amount).await {
-                Ok(data) => {
-                    println!("\n==================================================");
-                    println!("\x1b[1;4;32m{}\x1b[0m\n", "Successful transaction");
-
-                    println!("\x1b[1;4m{}\x1b[0m", "TXID:");
-                    println!("  {}\n", data.txid);
-
-                    println!("\x1b[1;4m{}\x1b[0m", "Accreditation Council Aggregated Signature:");
-                    println!("  {}\n", data.accreditation_council_aggregated_signature);
-
-                    println!("\x1b[1;4m{}\x1b[0m", "Broadcast Aggregated Signature:");
-                    println!("  {}", data.broadcast_aggregated_signature);
-                    println!("==================================================\n");
-                },
                 Err(e) => {
                     println!("\n==================================================");
-                    println!("\x1b[1;4;31m{}\x1b[0m\n", "Unsuccessful transaction");
+                    println!("\x1b[1;4;31m{}\x1b[0m\n", "Node is unreachable");
                     println!("\x1b[1;4m{}\x1b[0m", "Message:");
                     println!("  {}", e.get_message());
                     println!("==================================================\n");
                 }
             }
         }
+        Commands::SendToAddress {sender_private_key, receiver_public_key, amount, fee} => {
+            print_send_response(send_to_address_handler(sender_private_key, receiver_public_key, amount, fee).await);
+        }
+        Commands::StakeToNode {sender_private_key, node_public_key, amount, fee} => {
+            print_send_response(stake_to_node_handler(sender_private_key, node_public_key, amount, fee).await);
+        }
+        Commands::Unstake {sender_private_key, txid, vout, fee} => {
+            print_send_response(unstake_handler(sender_private_key, txid, vout, fee).await);
+        }
     }
 }
+
+fn print_send_response(response: Result<SendToAddressResponse, Error>) {
+    match response {
+        Ok(data) => {
+            println!("\n==================================================");
+            println!("\x1b[1;4;32m{}\x1b[0m\n", "Successful transaction");
+
+            println!("\x1b[1;4m{}\x1b[0m", "TXID:");
+            println!("  {}\n", data.txid);
+
+            println!("\x1b[1;4m{}\x1b[0m", "Accreditation Council Aggregated Signature:");
+            println!("  {}\n", data.accreditation_council_aggregated_signature);
+
+            println!("\x1b[1;4m{}\x1b[0m", "Accreditation Council Signers:");
+            println!("  {:?}\n", data.accreditation_council_signers);
+
+            println!("\x1b[1;4m{}\x1b[0m", "Broadcast Aggregated Signature:");
+            println!("  {}\n", data.broadcast_aggregated_signature);
+
+            println!("\x1b[1;4m{}\x1b[0m", "Broadcast Signers:");
+            println!("  {:?}", data.broadcast_signers);
+            println!("==================================================\n");
+        },
+        Err(e) => {
+            println!("\n==================================================");
+            println!("\x1b[1;4;31m{}\x1b[0m\n", "Unsuccessful transaction");
+            println!("\x1b[1;4m{}\x1b[0m", "Message:");
+            println!("  {}", e.get_message());
+            println!("==================================================\n");
+        }
+    }
+}
\ No newline at end of file
@@ -1,13 +1,16 @@
 use ahash::AHashMap;
-use concilium_core_ext::{db::DBSupport, epoch::{EpochPoolSupport, EpochSupport}, jrpc::rpc_module_context::RpcModuleContextSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport, serializable_node::SerializableNodeSupport}, temporary_node_ids::TemporaryNodeIdsSupport};
+use concilium_core_ext::{db::DBSupport, epoch::{EpochPoolSupport, EpochSupport}, jrpc::rpc_module_context::RpcModuleContextSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, node_health::NodeHealthSupport, self_node::SelfNodeSupport, serializable_node::SerializableNodeSupport}, temporary_node_ids::TemporaryNodeIdsSupport};
 use concilium_jrpc::{
-    get_address_utxos::handler as get_address_utxos_handler, get_transaction_by_hash::handler as get_transaction_by_hash_handler, send_raw_transaction::handler as send_raw_transaction_handler, get_account_transactions::handler as get_account_transactions_handler
+    get_address_utxos::handler as get_address_utxos_handler, get_transaction_by_hash::handler as get_transaction_by_hash_handler, send_raw_transaction::handler as send_raw_transaction_handler, get_account_transactions::handler as get_account_transactions_handler, get_account_nonce::handler as get_account_nonce_handler, get_transaction_status::handler as get_transaction_status_handler, get_state_root::handler as get_state_root_handler, get_evidence::handler as get_evidence_handler, get_committee::handler as get_committee_handler
 };
 use concilium_proto_defs::{
     connection::connection_server::ConnectionServer as ConnectionService,
     epoch::epoch_server::EpochServer as EpochService, 
     identifier::identifier_server::IdentifierServer as IdentifierService,
-    transaction::transaction_server::TransactionServer as TransactionService
+    transaction::transaction_server::TransactionServer as TransactionService,
+    reconciliation::reconciliation_server::ReconciliationServer as ReconciliationService,
+    checkpoint::checkpoint_server::CheckpointServer as CheckpointService,
+    evidence::evidence_server::EvidenceServer as EvidenceService
 };
 use concilium_core::{
     db::DB, epoch::Epoch, jrpc::rpc_module_context::RpcModuleContext, mempool::Mempool, node::{
@@ -44,22 +47,43 @@ use concilium_rpc::{
         }
     },
     transaction::{
+        batch::batch_handler,
         client::ClientSupport as TransactionClientSupport,
         server::{
             ServerSupport as TransactionServerSupport,
             Server as TransactionServer
         }
     },
+    reconciliation::{
+        anti_entropy::reconciliation_handler,
+        ledger_sync::ledger_sync_handler,
+        state_commitment::{commit_state_root, compare_state_roots},
+        server::{
+            ServerSupport as ReconciliationServerSupport,
+            Server as ReconciliationServer
+        }
+    },
+    checkpoint::{
+        proposal::propose_checkpoint,
+        server::{
+            ServerSupport as CheckpointServerSupport,
+            Server as CheckpointServer
+        }
+    },
+    evidence::server::{
+        ServerSupport as EvidenceServerSupport,
+        Server as EvidenceServer
+    },
 };
 use concilium_shared::{
-    binary, chacha20::generate_random_number_by_seed, epoch::current_epoch_number, ip::ipv4_to_string, sha::sha256, BOOTSTRAP_NODES, DST
+    binary, chacha20::generate_random_number_by_seed, epoch::current_epoch_number, ip::ipv4_to_string, BOOTSTRAP_NODES, DST
 };
 use concilium_log as log;
-use concilium_genesis::{load_genesis_transactions, load_transactions};
+use concilium_genesis::{load_genesis_transactions, load_transactions, migration::{migrate_amounts, migrate_transaction_kinds}};
+use concilium_transaction::{evidence::prune_signed_spends, health::{decay_node_health, get_unhealthy_node_ids, select_excluded_node_ids}, randomness::{hash_randomness, sign_exclusion_votes, sign_randomness}};
 use jsonrpsee::server::{RpcModule, ServerBuilder as JsonrpseeServer, ServerConfigBuilder};
 use hyper::Method;
 use tower_http::cors::{Any, CorsLayer};
-use rand::{rng, Rng};
 use std::{env, net::SocketAddr, sync::Arc, u16};
 use blst::min_pk::{AggregatePublicKey, AggregateSignature, PublicKey, SecretKey, Signature};
 use blst::BLST_ERROR;
@@ -79,14 +103,22 @@ async fn main() {
     let mempool = Arc::new(Mempool::new().expect("Mempool Error"));
 
     if db.exist("included_genesis_transactions") {
-        load_transactions(Arc::clone(&mempool), Arc::clone(&db)).await;
+        migrate_amounts(&db).expect("Amount Migration Error");
+        migrate_transaction_kinds(&db).expect("Transaction Kind Migration Error");
+        load_transactions(Arc::clone(&mempool), Arc::clone(&db)).await.expect("Transaction Loading Error");
     } else {
-        load_genesis_transactions(Arc::clone(&mempool), Arc::clone(&db)).await;
+        load_genesis_transactions(Arc::clone(&mempool), Arc::clone(&db)).await.expect("Genesis Loading Error");
     }
 
     connect_to_network(Arc::clone(&mempool)).await.unwrap();
 
-    handling_epoch(Arc::clone(&mempool));    
+    handling_epoch(Arc::clone(&mempool), Arc::clone(&db));    
+
+    handling_transaction_batch(Arc::clone(&mempool), Arc::clone(&db));
+
+    handling_reconciliation(Arc::clone(&mempool), Arc::clone(&db));
+
+    handling_ledger_sync(Arc::clone(&mempool), Arc::clone(&db));
 
     let _ = tokio::join!(
         rpc_server_handler(Arc::clone(&mempool), Arc::clone(&db)),
@@ -161,6 +193,12 @@ async fn do_connect_to_network(mempool: Arc<Mempool>) -> Result<(), Error> {
                 *bootstrap_node_signature = agg_sig.to_bytes();
 
                 self_node.set_id(1);
+                {
+                    let node_registry_lock = mempool.get_node_registry();
+                    let mut node_registry = node_registry_lock.write().await;
+
+                    node_registry.insert(1, *self_node.get_public_key());
+                }
 
                 return Ok(());
             }  
@@ -234,6 +272,17 @@ async fn do_connect_to_network(mempool: Arc<Mempool>) -> Result<(), Error> {
                 self_node.set_id(id);
                 *bootstrap_node_signature = agg_sig_bytes;
 
+                // every confirmed node is a possible committee member, reachable or not
+                {
+                    let node_registry_lock = mempool.get_node_registry();
+                    let mut node_registry = node_registry_lock.write().await;
+
+                    node_registry.insert(id, *self_node.get_public_key());
+                    for node in nodes.iter() {
+                        node_registry.insert(node.id, node.public_key);
+                    }
+                }
+
                 let active_nodes_lock = mempool.get_active_nodes();
                 let mut active_nodes = active_nodes_lock.write().await;
                 
@@ -279,13 +328,13 @@ async fn create_initial_entropies(mempool: Arc<Mempool>, last_node_id: u32) {
     let lock = mempool.get_epoch_pool().get_write();
     let mut epoch_pool = lock.lock().await;
 
-    epoch_pool.insert(current_epoch_number, Arc::new(Epoch::new(current_epoch_number, last_node_id, [0; 32], Vec::new(), AHashMap::new())));
-    epoch_pool.insert(current_epoch_number + 1, Arc::new(Epoch::new(current_epoch_number + 1, last_node_id, [0; 32], Vec::new(), AHashMap::new())));
-    epoch_pool.insert(current_epoch_number + 2, Arc::new(Epoch::new(current_epoch_number + 2, last_node_id, [0; 32], Vec::new(), AHashMap::new())));
+    epoch_pool.insert(current_epoch_number, Arc::new(Epoch::new(current_epoch_number, last_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
+    epoch_pool.insert(current_epoch_number + 1, Arc::new(Epoch::new(current_epoch_number + 1, last_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
+    epoch_pool.insert(current_epoch_number + 2, Arc::new(Epoch::new(current_epoch_number + 2, last_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
     epoch_pool.publish();
 }
 
-fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
+fn handling_epoch(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
     tokio::spawn(async move {
         let nodes: Arc<Mutex<AHashMap<u32, Arc<ActiveNode>>>> = Arc::new(Mutex::new(AHashMap::new()));
 
@@ -336,6 +385,10 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
 
                     if stage == 0 && stage_0_started == false {
                         stage_0_started = true;
+                        tokio::spawn(commit_state_root(current_epoch_number, Arc::clone(&mempool), Arc::clone(&db)));
+
+                        let unhealthy_node_ids = get_unhealthy_node_ids(Arc::clone(&mempool)).await;
+
                         let nodes = Arc::clone(&nodes);
                         let epoch_pool_read = mempool.get_epoch_pool().get_read();
                         let epoch_pool_write = mempool.get_epoch_pool().get_write();
@@ -347,30 +400,52 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
                                 None => {
                                     let last_node_id = epoch_pool_read.get(&current_epoch_number).unwrap().get_last_node_id();
 
-                                    epoch_pool_write_guard.insert(current_epoch_number + 1, Arc::new(Epoch::new(current_epoch_number + 1, last_node_id, [0; 32], Vec::new(), AHashMap::new())));
+                                    epoch_pool_write_guard.insert(current_epoch_number + 1, Arc::new(Epoch::new(current_epoch_number + 1, last_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
                                     epoch_pool_write_guard.publish();
 
                                     last_node_id
                                 }
                             };
 
-                            epoch_pool_write_guard.insert(current_epoch_number + 2, Arc::new(Epoch::new(current_epoch_number + 2, last_node_id, [0; 32], Vec::new(), AHashMap::new())));
+                            epoch_pool_write_guard.insert(current_epoch_number + 2, Arc::new(Epoch::new(current_epoch_number + 2, last_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
                             epoch_pool_write_guard.publish();
                         }
                         
                         if let Some(epoch) = epoch_pool_read.get(&(current_epoch_number + 1)) {
-                            let random_number = rng().random::<u64>();
-                            let mut random_numbers = epoch.get_random_numbers().to_vec();
-                            random_numbers.push(random_number);
+                            let previous_final_hash = match epoch_pool_read.get(&current_epoch_number) {
+                                Some(data) => data.get_final_hash().clone(),
+                                None => [0; 32]
+                            };
+
+                            let (self_node_id, signature, votes_signature) = {
+                                let lock = mempool.get_self_node();
+                                let self_node = lock.read().await;
+
+                                (
+                                    self_node.get_id(),
+                                    sign_randomness(current_epoch_number + 1, &previous_final_hash, self_node.get_private_key()).unwrap(),
+                                    sign_exclusion_votes(current_epoch_number + 1, &previous_final_hash, &unhealthy_node_ids, self_node.get_private_key()).unwrap()
+                                )
+                            };
+
+                            let mut randomness = epoch.get_randomness().clone();
+                            randomness.insert(self_node_id, signature);
 
-                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), random_numbers, epoch.get_hashes().clone())));
+                            let mut exclusion_votes = epoch.get_exclusion_votes().clone();
+                            for node_id in unhealthy_node_ids.iter() {
+                                *exclusion_votes.entry(*node_id).or_insert(0) += 1;
+                            }
+
+                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), randomness, epoch.get_hashes().clone(), exclusion_votes, epoch.get_exclusions().clone())));
                             epoch_pool_write_guard.publish();
 
                             tokio::spawn(async move {
                                 let nodes = nodes.lock().await;
 
                                 for (_, node) in nodes.iter() {
-                                    node.epoch_client.initial_request(current_epoch_number + 1, random_number).await.ok();
+                                    if node.epoch_client.initial_request(current_epoch_number + 1, self_node_id, &signature, &unhealthy_node_ids, &votes_signature).await.is_err() {
+                                        node.get_health().record_failure();
+                                    }
                                 }
                             });
                         };
@@ -378,35 +453,45 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
                     
                     if stage == 1 && stage_1_started == false {
                         stage_1_started = true;
+                        tokio::spawn(compare_state_roots(current_epoch_number, Arc::clone(&mempool), Arc::clone(&db)));
+
+                        let node_count = {
+                            let lock = mempool.get_active_nodes();
+                            let active_nodes = lock.read().await;
+                            active_nodes.get_nodes_by_id().len() + 1
+                        };
+
                         let nodes = Arc::clone(&nodes);
                         let epoch_pool_read = mempool.get_epoch_pool().get_read();
                         let epoch_pool_write = mempool.get_epoch_pool().get_write();
                         let mut epoch_pool_write_guard = epoch_pool_write.lock().await;
                         
                         if let Some(epoch) = epoch_pool_read.get(&(current_epoch_number + 1)) {
-                            let mut random_numbers = epoch.get_random_numbers().to_vec();
                             let mut hashes = epoch.get_hashes().clone();
-                            
-                            random_numbers.sort();
-                            
-                            let binary = binary::encode(&epoch.get_random_numbers().to_vec()).unwrap();
+                            let mut exclusions = epoch.get_exclusions().clone();
 
-                            let hash = sha256(&binary);
+                            // every node that contributed randomness had a vote on the exclusions
+                            let excluded_node_ids = select_excluded_node_ids(epoch.get_exclusion_votes(), epoch.get_randomness().len(), node_count);
+                            
+                            let hash = hash_randomness(epoch.get_randomness(), &excluded_node_ids).unwrap();
 
                             if let Some(h) = hashes.get_mut(&hash) {
                                 *h += 1;
                             } else {
                                 hashes.insert(hash, 1);
                             }
+                            *exclusions.entry((hash, excluded_node_ids.clone())).or_insert(0) += 1;
 
-                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), random_numbers, hashes)));
+                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), epoch.get_randomness().clone(), hashes, epoch.get_exclusion_votes().clone(), exclusions)));
                             epoch_pool_write_guard.publish();
 
                             tokio::spawn(async move {
                                 let nodes = nodes.lock().await;
 
                                 for (_, node) in nodes.iter() {
-                                    node.epoch_client.sync_request(current_epoch_number + 1, &hash).await.ok();
+                                    if node.epoch_client.sync_request(current_epoch_number + 1, &hash, &excluded_node_ids).await.is_err() {
+                                        node.get_health().record_failure();
+                                    }
                                 }
                             });
                         };
@@ -415,6 +500,10 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
                     if stage == 2 && stage_2_started == false {
 
                         stage_2_started = true;
+                        tokio::spawn(propose_checkpoint(current_epoch_number, Arc::clone(&mempool), Arc::clone(&db)));
+                        tokio::spawn(prune_signed_spends(Arc::clone(&mempool)));
+                        tokio::spawn(decay_node_health(Arc::clone(&mempool)));
+
                         let epoch_pool_read = mempool.get_epoch_pool().get_read();
                         let epoch_pool_write = mempool.get_epoch_pool().get_write();
                         let mut epoch_pool_write_guard = epoch_pool_write.lock().await;
@@ -429,7 +518,7 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
                                 None => [0; 32]
                             };
 
-                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), final_hash, epoch.get_random_numbers().to_vec(), epoch.get_hashes().clone())));
+                            epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), final_hash, epoch.get_randomness().clone(), epoch.get_hashes().clone(), epoch.get_exclusion_votes().clone(), epoch.get_exclusions().clone())));
                             epoch_pool_write_guard.publish();
 
                             let node_ids = generate_random_number_by_seed(final_hash, epoch.get_last_node_id(), epoch.get_last_node_id());
@@ -487,6 +576,18 @@ fn handling_epoch(mempool: Arc<Mempool>) -> JoinHandle<()> {
     })
 }
 
+fn handling_transaction_batch(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
+    tokio::spawn(batch_handler(mempool, db))
+}
+
+fn handling_reconciliation(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
+    tokio::spawn(reconciliation_handler(mempool, db))
+}
+
+fn handling_ledger_sync(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
+    tokio::spawn(ledger_sync_handler(mempool, db))
+}
+
 fn rpc_server_handler(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
     tokio::spawn(async move {
         println!("RPC server is running on[http://127.0.0.1:{}]", env::var("NODE_RPC_PORT").unwrap());
@@ -498,6 +599,9 @@ fn rpc_server_handler(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()> {
         .add_service(EpochService::new(EpochServer::new(Arc::clone(&mempool))))
         .add_service(ConnectionService::new(ConnectionServer::new(Arc::clone(&mempool))))
         .add_service(TransactionService::new(TransactionServer::new(Arc::clone(&mempool), Arc::clone(&db))))
+        .add_service(ReconciliationService::new(ReconciliationServer::new(Arc::clone(&mempool), Arc::clone(&db))))
+        .add_service(CheckpointService::new(CheckpointServer::new(Arc::clone(&mempool), Arc::clone(&db))))
+        .add_service(EvidenceService::new(EvidenceServer::new(Arc::clone(&mempool), Arc::clone(&db))))
         .serve(format!("127.0.0.1:{}", env::var("NODE_RPC_PORT").unwrap()).parse().unwrap())
         .await
         .unwrap();  
@@ -536,6 +640,26 @@ fn json_rpc_server_handler(mempool: Arc<Mempool>, db: Arc<DB>) -> JoinHandle<()>
         module.register_async_method("get_address_utxos", |params, ctx, _| async move {
             get_address_utxos_handler(params, ctx.get_mempool()).await
         }).unwrap();
+        
+        module.register_async_method("get_account_nonce", |params, ctx, _| async move {
+            get_account_nonce_handler(params, ctx.get_mempool()).await
+        }).unwrap();
+        
+        module.register_async_method("get_transaction_status", |params, ctx, _| async move {
+            get_transaction_status_handler(params, ctx.get_db()).await
+        }).unwrap();
+
+        module.register_async_method("get_state_root", |params, ctx, _| async move {
+            get_state_root_handler(params, ctx.get_db()).await
+        }).unwrap();
+
+        module.register_async_method("get_evidence", |params, ctx, _| async move {
+            get_evidence_handler(params, ctx.get_db()).await
+        }).unwrap();
+
+        module.register_async_method("get_committee", |params, ctx, _| async move {
+            get_committee_handler(params, ctx.get_mempool(), ctx.get_db()).await
+        }).unwrap();
 
         let server = JsonrpseeServer::default()
         .set_config(config)
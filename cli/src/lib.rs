@@ -1,8 +1,10 @@
 use std::env;
 use blst::min_pk::SecretKey;
 use chrono::Utc;
+use concilium_core::amount::Amount;
 use concilium_core::jrpc::utxo::{GetAddressUtxosResponse, SendToAddressResponse};
-use concilium_core::transaction::{TXInput, TXOutput, Transaction};
+use concilium_core::transaction::{TXInput, TXOutput, Transaction, TransactionKind};
+use concilium_core_ext::amount::AmountSupport;
 use concilium_core_ext::jrpc::utxo::SendToAddressResponseSupport;
 use concilium_core_ext::transaction::send_raw_transaction_request::SendRawTransactionRequestSupport;
 use concilium_core_ext::transaction::send_raw_transaction_request_txinput::SendRawTransactionRequestTXInputSupport;
@@ -16,7 +18,7 @@ use concilium_shared::sha::sha256;
 use ed25519_dalek::ed25519::signature::SignerMut;
 use ed25519_dalek::{Signature, SigningKey, PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH};
 use rand::{rngs::OsRng, RngCore};
-use concilium_core::jrpc::transaction::{GetTransactionByHashResponse, SendRawTransactionRequest, SendRawTransactionRequestTXInput, SendRawTransactionRequestTXOutput, SendRawTransactionResponse};
+use concilium_core::jrpc::transaction::{GetAccountNonceResponse, GetTransactionByHashResponse, GetTransactionStatusResponse, SendRawTransactionRequest, SendRawTransactionRequestTXInput, SendRawTransactionRequestTXOutput, SendRawTransactionResponse};
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::core::params::ObjectParams;
 use jsonrpsee::http_client::HttpClient;
@@ -61,9 +63,63 @@ pub async fn get_transaction_info_handler(txid: String) -> Result<GetTransaction
     Ok(client.request::<GetTransactionByHashResponse, _>("get_transaction_by_hash", obj_params).await?)
 }
 
-pub async fn send_to_address_handler(sender_private_key: String, receiver_public_key: String, amount: f32) -> Result<SendToAddressResponse, Error> {
+pub async fn get_transaction_status_handler(txid: String) -> Result<GetTransactionStatusResponse, Error> {
+    let dst = format!("http://{}:{}", env::var("NODE_IP_ADDRESS")?, env::var("NODE_JSON_RPC_PORT")?);
+    let client = HttpClient::builder().request_timeout(Duration::from_secs(10)).build(dst)?;
+    let raw_params = json!({
+        "txid": txid, 
+    });
+    
+    let mut obj_params = ObjectParams::new();
+    if let serde_json::Value::Object(map) = raw_params {
+        for (key, value) in map {
+            obj_params.insert(key.as_str(), value)?;
+        }
+    }
+
+    Ok(client.request::<GetTransactionStatusResponse, _>("get_transaction_status", obj_params).await?)
+}
+
+pub async fn send_to_address_handler(sender_private_key: String, receiver_public_key: String, amount: String, fee: String) -> Result<SendToAddressResponse, Error> {
+    let amount = Amount::from_decimal_str(&amount)?;
+    let fee = Amount::from_decimal_str(&fee)?;
+    let private_key: [u8; 32] = hex::decode(sender_private_key)?.try_into()?;
+    let receiver_public_key: [u8; 32] = hex::decode(receiver_public_key)?.try_into()?;
+
+    send_transaction(SigningKey::from_bytes(&private_key), Some((amount, receiver_public_key)), fee, TransactionKind::Transfer).await
+}
+
+// the bonded amount is paid back to the sender as the first output
+pub async fn stake_to_node_handler(sender_private_key: String, node_public_key: String, amount: String, fee: String) -> Result<SendToAddressResponse, Error> {
+    let amount = Amount::from_decimal_str(&amount)?;
+    let fee = Amount::from_decimal_str(&fee)?;
     let private_key: [u8; 32] = hex::decode(sender_private_key)?.try_into()?;
-    let mut signing_key: SigningKey = SigningKey::from_bytes(&private_key);
+    let node_public_key: [u8; 48] = hex::decode(node_public_key)?.try_into()?;
+    let signing_key = SigningKey::from_bytes(&private_key);
+    let sender_public_key = signing_key.verifying_key().to_bytes();
+
+    send_transaction(signing_key, Some((amount, sender_public_key)), fee, TransactionKind::Stake { node_public_key }).await
+}
+
+// only the fee is paid, the stake itself stays bonded until its unbonding delay passed
+pub async fn unstake_handler(sender_private_key: String, txid: String, vout: usize, fee: String) -> Result<SendToAddressResponse, Error> {
+    let fee = Amount::from_decimal_str(&fee)?;
+    let private_key: [u8; 32] = hex::decode(sender_private_key)?.try_into()?;
+    let txid: [u8; 32] = hex::decode(txid)?.try_into()?;
+
+    send_transaction(SigningKey::from_bytes(&private_key), None, fee, TransactionKind::Unstake { txid, vout }).await
+}
+
+/*
+    Picks the inputs, signs and sends a transaction paying the amount to the receiver, the change goes back to the sender.
+    Without an amount the transaction only pays its fee, so it needs some change to have an output at all.
+*/
+async fn send_transaction(mut signing_key: SigningKey, amount: Option<(Amount, [u8; 32])>, fee: Amount, kind: TransactionKind) -> Result<SendToAddressResponse, Error> {
+    // the fee is whatever the inputs carry on top of the outputs
+    let total_amount = match amount {
+        Some((value, _)) => value.checked_add(fee).ok_or(Error::new("Amount overflow"))?,
+        None => fee
+    };
     let sender_public_key = signing_key.verifying_key();
     let sender_public_key = sender_public_key.to_bytes();
 
@@ -80,25 +136,31 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
         }
     }
 
-    let utxos = client.request::<GetAddressUtxosResponse, _>("get_address_utxos", obj_params).await?;
+    let utxos = client.request::<GetAddressUtxosResponse, _>("get_address_utxos", obj_params.clone()).await?;
 
     if utxos.status == false {
         return Err(Error::new("Internal Error"))
     }
 
+    let account_nonce = client.request::<GetAccountNonceResponse, _>("get_account_nonce", obj_params).await?;
+
+    if account_nonce.status == false {
+        return Err(Error::new("Internal Error"))
+    }
+
     let mut needed_utxos = Vec::new();
-    let mut needed_amount: f32 = 0.0;
+    let mut needed_amount = Amount::zero();
 
     for item in utxos.vout {
-        if needed_amount >= amount {
+        if needed_amount >= total_amount && (amount.is_some() || needed_amount > total_amount) {
             break;
         } else {
-            needed_amount += item.value;
+            needed_amount = needed_amount.checked_add(Amount::from_decimal_str(&item.value)?).ok_or(Error::new("Amount overflow"))?;
             needed_utxos.push(item);
         }
     }
 
-    if needed_amount < amount {
+    if needed_amount < total_amount || (amount.is_none() && needed_amount == total_amount) {
         return Err(Error::new("Your balance is not sufficient"))
     }
 
@@ -110,17 +172,15 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
     
     let mut vout = Vec::new();
 
-    let receiver_public_key: [u8; 32] = hex::decode(receiver_public_key)?.try_into()?;
-    if needed_amount == amount {
-        vout.push(TXOutput::new(amount, receiver_public_key));
-    } else {
-        vout.push(TXOutput::new(amount, receiver_public_key));
-        vout.push(TXOutput::new(needed_amount - amount, sender_public_key));
-
+    if let Some((value, receiver_public_key)) = amount {
+        vout.push(TXOutput::new(value, receiver_public_key));
+    }
+    if needed_amount > total_amount {
+        vout.push(TXOutput::new(needed_amount.checked_sub(total_amount).unwrap_or_default(), sender_public_key));
     }
 
     let created_at = Utc::now().timestamp();
-    let nonce = rand::random::<u64>();
+    let nonce = account_nonce.next_nonce;
 
     let preimage = Transaction::new(
         [0; 32], 
@@ -129,7 +189,8 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
         nonce, 
         created_at, 
         vin.clone(),
-        vout.clone()
+        vout.clone(),
+        kind
     );
 
     let binary_preimage = binary::encode(&preimage).unwrap();
@@ -147,18 +208,26 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
     
     for item in vout {
         transaction_vout.push(SendRawTransactionRequestTXOutput::new(
-            item.value,
+            item.value.to_decimal_string(),
             hex::encode(item.public_key)
         ));
     }
 
+    let (stake, unstake) = match kind {
+        TransactionKind::Transfer => (None, None),
+        TransactionKind::Stake { node_public_key } => (Some(hex::encode(node_public_key)), None),
+        TransactionKind::Unstake { txid, vout } => (None, Some(SendRawTransactionRequestTXInput::new(hex::encode(txid), vout)))
+    };
+
     let transaction = SendRawTransactionRequest::new(
         hex::encode(sender_public_key), 
         hex::encode(signature.to_bytes()), 
         nonce, 
         created_at, 
         transaction_vin, 
-        transaction_vout
+        transaction_vout,
+        stake,
+        unstake
     );
 
     let raw_params = json!({
@@ -168,6 +237,8 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
         "created_at": transaction.created_at,
         "vin": transaction.vin,
         "vout": transaction.vout,
+        "stake": transaction.stake,
+        "unstake": transaction.unstake,
     });
 
     let mut obj_params = ObjectParams::new();
@@ -185,7 +256,9 @@ pub async fn send_to_address_handler(sender_private_key: String, receiver_public
             String::from("Successful transaction"), 
             response.txid, 
             response.accreditation_council_aggregated_signature,
-            response.broadcast_aggregated_signature 
+            response.broadcast_aggregated_signature,
+            response.accreditation_council_signers,
+            response.broadcast_signers
         ))
     } else {
         Err(Error::new("Unsuccessful transaction"))
@@ -0,0 +1,85 @@
+use concilium_core::amount::Amount;
+use concilium_error::Error;
+use concilium_shared::transaction::{AMOUNT_DECIMALS, BASE_UNITS_PER_COIN};
+
+pub trait AmountSupport {
+    fn new(base_units: u64) -> Amount;
+    fn zero() -> Amount;
+    fn get_base_units(&self) -> u64;
+    fn checked_add(&self, other: Amount) -> Option<Amount>;
+    fn checked_sub(&self, other: Amount) -> Option<Amount>;
+    fn checked_sum<'a, I: IntoIterator<Item = &'a Amount>>(amounts: I) -> Option<Amount>;
+    fn from_decimal_str(value: &str) -> Result<Amount, Error>;
+    fn to_decimal_string(&self) -> String;
+}
+
+impl AmountSupport for Amount {
+    fn new(base_units: u64) -> Amount {
+        Self {
+            base_units
+        }
+    }
+
+    fn zero() -> Amount {
+        Self {
+            base_units: 0
+        }
+    }
+
+    fn get_base_units(&self) -> u64 {
+        self.base_units
+    }
+
+    fn checked_add(&self, other: Amount) -> Option<Amount> {
+        self.base_units.checked_add(other.base_units).map(Amount::new)
+    }
+
+    fn checked_sub(&self, other: Amount) -> Option<Amount> {
+        self.base_units.checked_sub(other.base_units).map(Amount::new)
+    }
+
+    fn checked_sum<'a, I: IntoIterator<Item = &'a Amount>>(amounts: I) -> Option<Amount> {
+        amounts.into_iter().try_fold(Amount::zero(), |sum, amount| sum.checked_add(*amount))
+    }
+
+    // "12", "12.5" or "0.00000001", at most AMOUNT_DECIMALS fractional digits
+    fn from_decimal_str(value: &str) -> Result<Amount, Error> {
+        let value = value.trim();
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (value, "")
+        };
+
+        if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new("invalid amount"));
+        }
+
+        if fraction.len() > AMOUNT_DECIMALS as usize {
+            return Err(Error::new("amount has too many decimals"));
+        }
+
+        let integer: u64 = match integer.parse() {
+            Ok(data) => data,
+            Err(_) => return Err(Error::new("amount is too large"))
+        };
+        let fraction: u64 = format!("{:0<width$}", fraction, width = AMOUNT_DECIMALS as usize).parse().unwrap_or(0);
+
+        match integer.checked_mul(BASE_UNITS_PER_COIN).and_then(|units| units.checked_add(fraction)) {
+            Some(base_units) => Ok(Amount::new(base_units)),
+            None => Err(Error::new("amount is too large"))
+        }
+    }
+
+    fn to_decimal_string(&self) -> String {
+        let integer = self.base_units / BASE_UNITS_PER_COIN;
+        let fraction = self.base_units % BASE_UNITS_PER_COIN;
+
+        if fraction == 0 {
+            return integer.to_string();
+        }
+
+        let fraction = format!("{:0width$}", fraction, width = AMOUNT_DECIMALS as usize);
+
+        format!("{}.{}", integer, fraction.trim_end_matches('0'))
+    }
+}
@@ -1,12 +1,32 @@
 use ahash::{AHashMap, AHashSet};
-use concilium_core::chain_state::ChainState;
+use concilium_core::{amount::Amount, chain_state::{ChainState, StateCommitment}, stake::Stake};
 
 pub trait ChainStateSupport {
     fn new() -> ChainState;
-    fn get_balances(&self) -> &AHashMap<[u8; 32], f32>;
+    fn get_balances(&self) -> &AHashMap<[u8; 32], Amount>;
     fn get_transactions(&self) -> &AHashMap<[u8; 32], AHashSet<[u8; 32]>>;
-    fn get_mut_balances(&mut self) -> &mut AHashMap<[u8; 32], f32>;
+    fn get_mut_balances(&mut self) -> &mut AHashMap<[u8; 32], Amount>;
     fn get_mut_transactions(&mut self) -> &mut AHashMap<[u8; 32], AHashSet<[u8; 32]>>;
+    fn get_nonces(&self) -> &AHashMap<[u8; 32], u64>;
+    fn get_next_nonce(&self, public_key: &[u8; 32]) -> u64;
+    fn update_nonce(&mut self, public_key: [u8; 32], nonce: u64);
+    fn get_node_rewards(&self) -> &AHashMap<u32, Amount>;
+    fn add_node_reward(&mut self, node_id: u32, reward: Amount);
+    fn get_committed_txids(&self) -> &AHashSet<[u8; 32]>;
+    fn add_committed_txid(&mut self, txid: [u8; 32]);
+    fn get_uncheckpointed_txids(&self) -> &AHashMap<[u8; 32], u64>;
+    fn add_uncheckpointed_txid(&mut self, txid: [u8; 32], epoch_number: u64);
+    fn remove_uncheckpointed_txid(&mut self, txid: &[u8; 32]);
+    fn get_stakes(&self) -> &AHashMap<([u8; 32], usize), Stake>;
+    fn get_mut_stakes(&mut self) -> &mut AHashMap<([u8; 32], usize), Stake>;
+}
+
+pub trait StateCommitmentSupport {
+    fn new(epoch_number: u64, state_root: [u8; 32], diverged_node_ids: Vec<u32>) -> StateCommitment;
+    fn get_epoch_number(&self) -> u64;
+    fn get_state_root(&self) -> &[u8; 32];
+    fn get_diverged_node_ids(&self) -> &[u32];
+    fn set_diverged_node_ids(&mut self, diverged_node_ids: Vec<u32>);
 }
 
 impl ChainStateSupport for ChainState {
@@ -14,10 +34,15 @@ impl ChainStateSupport for ChainState {
         Self {
             balances: AHashMap::new(),
             transactions: AHashMap::new(),
+            nonces: AHashMap::new(),
+            node_rewards: AHashMap::new(),
+            committed_txids: AHashSet::new(),
+            uncheckpointed_txids: AHashMap::new(),
+            stakes: AHashMap::new(),
         }
     }
 
-    fn get_balances(&self) -> &AHashMap<[u8; 32], f32> {
+    fn get_balances(&self) -> &AHashMap<[u8; 32], Amount> {
         &self.balances
     }
 
@@ -25,11 +50,95 @@ impl ChainStateSupport for ChainState {
         &self.transactions
     }
     
-    fn get_mut_balances(&mut self) -> &mut AHashMap<[u8; 32], f32> {
+    fn get_mut_balances(&mut self) -> &mut AHashMap<[u8; 32], Amount> {
         &mut self.balances
     }
 
     fn get_mut_transactions(&mut self) -> &mut AHashMap<[u8; 32], AHashSet<[u8; 32]>> {
         &mut self.transactions
     }
+
+    fn get_nonces(&self) -> &AHashMap<[u8; 32], u64> {
+        &self.nonces
+    }
+
+    // nonces are strictly increasing per account
+    fn get_next_nonce(&self, public_key: &[u8; 32]) -> u64 {
+        match self.nonces.get(public_key) {
+            Some(nonce) => nonce.saturating_add(1),
+            None => 0
+        }
+    }
+
+    fn update_nonce(&mut self, public_key: [u8; 32], nonce: u64) {
+        let last_nonce = self.nonces.entry(public_key).or_insert(nonce);
+
+        if *last_nonce < nonce {
+            *last_nonce = nonce;
+        }
+    }
+
+    fn get_node_rewards(&self) -> &AHashMap<u32, Amount> {
+        &self.node_rewards
+    }
+
+    fn add_node_reward(&mut self, node_id: u32, reward: Amount) {
+        let node_reward = self.node_rewards.entry(node_id).or_default();
+
+        node_reward.base_units = node_reward.base_units.saturating_add(reward.base_units);
+    }
+
+    fn get_committed_txids(&self) -> &AHashSet<[u8; 32]> {
+        &self.committed_txids
+    }
+
+    fn add_committed_txid(&mut self, txid: [u8; 32]) {
+        self.committed_txids.insert(txid);
+    }
+
+    fn get_uncheckpointed_txids(&self) -> &AHashMap<[u8; 32], u64> {
+        &self.uncheckpointed_txids
+    }
+
+    fn add_uncheckpointed_txid(&mut self, txid: [u8; 32], epoch_number: u64) {
+        self.uncheckpointed_txids.insert(txid, epoch_number);
+    }
+
+    fn remove_uncheckpointed_txid(&mut self, txid: &[u8; 32]) {
+        self.uncheckpointed_txids.remove(txid);
+    }
+
+    fn get_stakes(&self) -> &AHashMap<([u8; 32], usize), Stake> {
+        &self.stakes
+    }
+
+    fn get_mut_stakes(&mut self) -> &mut AHashMap<([u8; 32], usize), Stake> {
+        &mut self.stakes
+    }
+}
+
+impl StateCommitmentSupport for StateCommitment {
+    fn new(epoch_number: u64, state_root: [u8; 32], diverged_node_ids: Vec<u32>) -> StateCommitment {
+        Self {
+            epoch_number,
+            state_root,
+            diverged_node_ids
+        }
+    }
+
+    fn get_epoch_number(&self) -> u64 {
+        self.epoch_number
+    }
+
+    fn get_state_root(&self) -> &[u8; 32] {
+        &self.state_root
+    }
+
+    fn get_diverged_node_ids(&self) -> &[u32] {
+        &self.diverged_node_ids
+    }
+
+    fn set_diverged_node_ids(&mut self, diverged_node_ids: Vec<u32>) {
+        self.diverged_node_ids = diverged_node_ids;
+    }
 }
\ No newline at end of file
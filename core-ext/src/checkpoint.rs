@@ -0,0 +1,84 @@
+use concilium_core::checkpoint::{Checkpoint, SignedCheckpoint};
+use concilium_error::Error;
+use concilium_shared::{binary, sha::sha256};
+
+pub trait CheckpointSupport {
+    fn new(epoch_number: u64, committee_epoch_number: u64, previous_hash: [u8; 32], first_sequence: u64, txids: Vec<[u8; 32]>) -> Checkpoint;
+    fn get_epoch_number(&self) -> u64;
+    fn get_committee_epoch_number(&self) -> u64;
+    fn get_previous_hash(&self) -> &[u8; 32];
+    fn get_first_sequence(&self) -> u64;
+    fn get_txids(&self) -> &[[u8; 32]];
+    fn get_next_sequence(&self) -> u64;
+    fn get_hash(&self) -> Result<[u8; 32], Error>;
+}
+
+pub trait SignedCheckpointSupport {
+    fn new(checkpoint: Checkpoint, signers: Vec<u32>, aggregated_signature: [u8; 96]) -> SignedCheckpoint;
+    fn get_checkpoint(&self) -> &Checkpoint;
+    fn get_signers(&self) -> &[u32];
+    fn get_aggregated_signature(&self) -> &[u8; 96];
+}
+
+impl CheckpointSupport for Checkpoint {
+    fn new(epoch_number: u64, committee_epoch_number: u64, previous_hash: [u8; 32], first_sequence: u64, txids: Vec<[u8; 32]>) -> Checkpoint {
+        Self {
+            epoch_number,
+            committee_epoch_number,
+            previous_hash,
+            first_sequence,
+            txids
+        }
+    }
+
+    fn get_epoch_number(&self) -> u64 {
+        self.epoch_number
+    }
+
+    fn get_committee_epoch_number(&self) -> u64 {
+        self.committee_epoch_number
+    }
+
+    fn get_previous_hash(&self) -> &[u8; 32] {
+        &self.previous_hash
+    }
+
+    fn get_first_sequence(&self) -> u64 {
+        self.first_sequence
+    }
+
+    fn get_txids(&self) -> &[[u8; 32]] {
+        &self.txids
+    }
+
+    fn get_next_sequence(&self) -> u64 {
+        self.first_sequence + self.txids.len() as u64
+    }
+
+    // the committee signs this hash and the next checkpoint links to it
+    fn get_hash(&self) -> Result<[u8; 32], Error> {
+        Ok(sha256(&binary::encode(self)?))
+    }
+}
+
+impl SignedCheckpointSupport for SignedCheckpoint {
+    fn new(checkpoint: Checkpoint, signers: Vec<u32>, aggregated_signature: [u8; 96]) -> SignedCheckpoint {
+        Self {
+            checkpoint,
+            signers,
+            aggregated_signature
+        }
+    }
+
+    fn get_checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    fn get_signers(&self) -> &[u32] {
+        &self.signers
+    }
+
+    fn get_aggregated_signature(&self) -> &[u8; 96] {
+        &self.aggregated_signature
+    }
+}
\ No newline at end of file
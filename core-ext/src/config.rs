@@ -0,0 +1,154 @@
+use std::{env, time::Duration};
+use concilium_core::{amount::Amount, config::Config};
+use concilium_error::Error;
+use crate::amount::AmountSupport;
+
+pub trait ConfigSupport {
+    fn new() -> Result<Config, Error>;
+    fn get_request_timeout(&self) -> Duration;
+    fn get_leader_request_timeout(&self) -> Duration;
+    fn get_save_request_retries(&self) -> u32;
+    fn get_min_transaction_fee(&self) -> Amount;
+    fn get_batch_window(&self) -> Duration;
+    fn get_max_batch_size(&self) -> usize;
+    fn is_batching_enabled(&self) -> bool;
+    fn get_reconciliation_interval(&self) -> Duration;
+    fn get_max_reconciliation_certificates(&self) -> usize;
+    fn is_reconciliation_enabled(&self) -> bool;
+    fn get_health_min_samples(&self) -> u64;
+    fn get_health_min_success_rate(&self) -> u32;
+    fn get_health_max_silence(&self) -> Duration;
+}
+
+impl ConfigSupport for Config {
+    // every value is optional and falls back to its default
+    fn new() -> Result<Config, Error> {
+        let config = Self {
+            request_timeout: match env::var("REQUEST_TIMEOUT") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 3000
+            },
+            leader_request_timeout: match env::var("LEADER_REQUEST_TIMEOUT") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 10000
+            },
+            save_request_retries: match env::var("SAVE_REQUEST_RETRIES") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 2
+            },
+            min_transaction_fee: match env::var("MIN_TRANSACTION_FEE") {
+                Ok(data) => Amount::from_decimal_str(&data)?,
+                Err(_) => Amount::from_decimal_str("0.0001")?
+            },
+            batch_window: match env::var("BATCH_WINDOW") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 100
+            },
+            max_batch_size: match env::var("MAX_BATCH_SIZE") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 512
+            },
+            reconciliation_interval: match env::var("RECONCILIATION_INTERVAL") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 30000
+            },
+            max_reconciliation_certificates: match env::var("MAX_RECONCILIATION_CERTIFICATES") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 256
+            },
+            health_min_samples: match env::var("HEALTH_MIN_SAMPLES") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 20
+            },
+            health_min_success_rate: match env::var("HEALTH_MIN_SUCCESS_RATE") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 50
+            },
+            health_max_silence: match env::var("HEALTH_MAX_SILENCE") {
+                Ok(data) => data.trim().parse()?,
+                Err(_) => 60
+            }
+        };
+
+        // the leader needs at least two stages of peer requests before it can answer
+        if config.request_timeout == 0 || config.leader_request_timeout < config.request_timeout * 2 {
+            return Err(Error::new("leader request timeout must be at least twice the request timeout"));
+        }
+
+        // a batched transaction waits for the window and can take a second council round
+        if config.batch_window > 0 && config.leader_request_timeout < config.batch_window + config.request_timeout * 3 {
+            return Err(Error::new("leader request timeout must cover the batch window and three request timeouts"));
+        }
+
+        if config.max_batch_size == 0 {
+            return Err(Error::new("max batch size must be at least 1"));
+        }
+
+        if config.max_reconciliation_certificates == 0 {
+            return Err(Error::new("max reconciliation certificates must be at least 1"));
+        }
+
+        if config.health_min_success_rate > 100 {
+            return Err(Error::new("health min success rate must be a percent between 0 and 100"));
+        }
+
+        // a peer is only heard from once per epoch stage, a shorter silence excludes healthy peers
+        if config.health_max_silence < 12 {
+            return Err(Error::new("health max silence must be at least one epoch"));
+        }
+
+        Ok(config)
+    }
+
+    fn get_request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout)
+    }
+
+    fn get_leader_request_timeout(&self) -> Duration {
+        Duration::from_millis(self.leader_request_timeout)
+    }
+
+    fn get_save_request_retries(&self) -> u32 {
+        self.save_request_retries
+    }
+
+    fn get_min_transaction_fee(&self) -> Amount {
+        self.min_transaction_fee
+    }
+
+    fn get_batch_window(&self) -> Duration {
+        Duration::from_millis(self.batch_window)
+    }
+
+    fn get_max_batch_size(&self) -> usize {
+        self.max_batch_size
+    }
+
+    fn is_batching_enabled(&self) -> bool {
+        self.batch_window > 0
+    }
+
+    fn get_reconciliation_interval(&self) -> Duration {
+        Duration::from_millis(self.reconciliation_interval)
+    }
+
+    fn get_max_reconciliation_certificates(&self) -> usize {
+        self.max_reconciliation_certificates
+    }
+
+    fn is_reconciliation_enabled(&self) -> bool {
+        self.reconciliation_interval > 0
+    }
+
+    fn get_health_min_samples(&self) -> u64 {
+        self.health_min_samples
+    }
+
+    fn get_health_min_success_rate(&self) -> u32 {
+        self.health_min_success_rate
+    }
+
+    fn get_health_max_silence(&self) -> Duration {
+        Duration::from_secs(self.health_max_silence)
+    }
+}
\ No newline at end of file
@@ -1,12 +1,14 @@
 use rocksdb::DBWithThreadMode;
 use rocksdb::SingleThreaded;
 use rocksdb::DB as RocksDB;
+use rocksdb::WriteBatch;
 use concilium_error::Error as Error;
 use concilium_core::db::DB;
 
 pub trait DBSupport {
     fn new() -> Result<DB, Error>;
     fn put<T>(&self, key: &str, value: &T) -> Result<(), Error> where T: AsRef<[u8]>;
+    fn put_all(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), Error>;
     fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error>;
     fn delete<T: AsRef<[u8]>>(&self, key: T) -> Result<(), Error>;
     fn exist<T: AsRef<[u8]>>(&self, key: T) -> bool;
@@ -29,6 +31,16 @@ impl DBSupport for DB {
         Ok(self.db.put(key, value)?)
     }
 
+    // every entry is written or none of them
+    fn put_all(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        for (key, value) in entries {
+            batch.put(key, value);
+        }
+
+        Ok(self.db.write(batch)?)
+    }
+
     fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
         if let Some(result) = self.db.get(key)? {
             Ok(Some(result))
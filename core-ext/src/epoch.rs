@@ -1,36 +1,39 @@
 use std::sync::Arc;
 use ahash::AHashMap;
-use concilium_core::epoch::{Epoch, EpochMap, EpochMapAddOp, EpochPool, EpochPoolRead, EpochPoolReadFactory, EpochPoolWrite};
+use concilium_core::epoch::{Epoch, EpochMap, EpochSnapshot, EpochMapAddOp, EpochPool, EpochPoolRead, EpochPoolReadFactory, EpochPoolWrite};
 use left_right::{ReadHandle, ReadHandleFactory, WriteHandle};
 use tokio::sync::Mutex;
 
 pub trait EpochSupport {
-    fn new(id: u64, last_node_id: u32, final_hash: [u8; 32], random_numbers: Vec<u64>, hashes: AHashMap<[u8; 32], u32>) -> Epoch;
+    fn new(id: u64, last_node_id: u32, final_hash: [u8; 32], randomness: AHashMap<u32, [u8; 96]>, hashes: AHashMap<[u8; 32], u32>, exclusion_votes: AHashMap<u32, u32>, exclusions: AHashMap<([u8; 32], Vec<u32>), u32>) -> Epoch;
     fn get_id(&self) -> u64;
     fn get_last_node_id(&self) -> u32;
     fn get_final_hash(&self) -> &[u8; 32];
-    fn get_random_numbers(&self) -> &[u64];
+    fn get_randomness(&self) -> &AHashMap<u32, [u8; 96]>;
     fn get_hashes(&self) -> &AHashMap<[u8; 32], u32>;
     fn get_mut_hashes(&mut self) -> &mut AHashMap<[u8; 32], u32>;
+    fn get_exclusion_votes(&self) -> &AHashMap<u32, u32>;
+    fn get_exclusions(&self) -> &AHashMap<([u8; 32], Vec<u32>), u32>;
     fn set_id(&mut self, id: u64);
     fn set_last_node_id(&mut self, last_node_id: u32);
     fn set_final_hash(&mut self, final_hash: [u8; 32]);
-    fn set_random_numbers(&mut self, random_numbers: Vec<u64>);
+    fn set_randomness(&mut self, randomness: AHashMap<u32, [u8; 96]>);
     fn set_hashes(&mut self, hashes: AHashMap<[u8; 32], u32>);
-    fn insert_random_number(&mut self, number: u64);
+    fn insert_randomness(&mut self, node_id: u32, signature: [u8; 96]);
     fn insert_hash(&mut self, hash: [u8; 32], count: u32);
     fn increase_hash(&mut self, hash: [u8; 32]);
-    fn sort_random_numbers(&mut self);
 }
 
 impl EpochSupport for Epoch {
-    fn new(id: u64, last_node_id: u32, final_hash: [u8; 32], random_numbers: Vec<u64>, hashes: AHashMap<[u8; 32], u32>) -> Epoch {
+    fn new(id: u64, last_node_id: u32, final_hash: [u8; 32], randomness: AHashMap<u32, [u8; 96]>, hashes: AHashMap<[u8; 32], u32>, exclusion_votes: AHashMap<u32, u32>, exclusions: AHashMap<([u8; 32], Vec<u32>), u32>) -> Epoch {
         Epoch { 
             id, 
             last_node_id, 
             final_hash, 
-            random_numbers, 
-            hashes 
+            randomness, 
+            hashes,
+            exclusion_votes,
+            exclusions
         }
     }
     
@@ -46,8 +49,8 @@ impl EpochSupport for Epoch {
         &self.final_hash
     }
 
-    fn get_random_numbers(&self) -> &[u64] {
-        &self.random_numbers
+    fn get_randomness(&self) -> &AHashMap<u32, [u8; 96]> {
+        &self.randomness
     }
 
     fn get_hashes(&self) -> &AHashMap<[u8; 32], u32> {
@@ -58,6 +61,14 @@ impl EpochSupport for Epoch {
         &mut self.hashes
     }
 
+    fn get_exclusion_votes(&self) -> &AHashMap<u32, u32> {
+        &self.exclusion_votes
+    }
+
+    fn get_exclusions(&self) -> &AHashMap<([u8; 32], Vec<u32>), u32> {
+        &self.exclusions
+    }
+
     fn set_id(&mut self, id: u64) {
         self.id = id;
     }
@@ -70,8 +81,8 @@ impl EpochSupport for Epoch {
         self.final_hash = final_hash;
     }
 
-    fn set_random_numbers(&mut self, random_numbers: Vec<u64>) {
-        self.random_numbers = random_numbers;
+    fn set_randomness(&mut self, randomness: AHashMap<u32, [u8; 96]>) {
+        self.randomness = randomness;
     }
 
     fn set_hashes(&mut self, hashes: AHashMap<[u8; 32], u32>) {
@@ -84,16 +95,65 @@ impl EpochSupport for Epoch {
         }
     }
 
-    fn insert_random_number(&mut self, number: u64) {
-        self.random_numbers.push(number);
+    fn insert_randomness(&mut self, node_id: u32, signature: [u8; 96]) {
+        self.randomness.insert(node_id, signature);
     }
 
     fn insert_hash(&mut self, hash: [u8; 32], count: u32) {
         self.hashes.insert(hash, count);
     }
+}
+
+pub trait EpochSnapshotSupport {
+    fn new(epoch_number: u64, last_node_id: u32, temporary_node_ids: Arc<AHashMap<u32, u32>>, before_temporary_node_ids: Arc<AHashMap<u32, u32>>, node_public_keys: AHashMap<u32, [u8; 48]>, node_stakes: AHashMap<u32, u64>, excluded_node_ids: Vec<u32>) -> EpochSnapshot;
+    fn get_epoch_number(&self) -> u64;
+    fn get_last_node_id(&self) -> u32;
+    fn get_temporary_node_ids(&self) -> &AHashMap<u32, u32>;
+    fn get_before_temporary_node_ids(&self) -> &AHashMap<u32, u32>;
+    fn get_node_public_keys(&self) -> &AHashMap<u32, [u8; 48]>;
+    fn get_node_stakes(&self) -> &AHashMap<u32, u64>;
+    fn get_excluded_node_ids(&self) -> &[u32];
+}
+
+impl EpochSnapshotSupport for EpochSnapshot {
+    fn new(epoch_number: u64, last_node_id: u32, temporary_node_ids: Arc<AHashMap<u32, u32>>, before_temporary_node_ids: Arc<AHashMap<u32, u32>>, node_public_keys: AHashMap<u32, [u8; 48]>, node_stakes: AHashMap<u32, u64>, excluded_node_ids: Vec<u32>) -> EpochSnapshot {
+        EpochSnapshot {
+            epoch_number,
+            last_node_id,
+            temporary_node_ids,
+            before_temporary_node_ids,
+            node_public_keys,
+            node_stakes,
+            excluded_node_ids
+        }
+    }
+
+    fn get_epoch_number(&self) -> u64 {
+        self.epoch_number
+    }
+
+    fn get_last_node_id(&self) -> u32 {
+        self.last_node_id
+    }
+
+    fn get_temporary_node_ids(&self) -> &AHashMap<u32, u32> {
+        &self.temporary_node_ids
+    }
+
+    fn get_before_temporary_node_ids(&self) -> &AHashMap<u32, u32> {
+        &self.before_temporary_node_ids
+    }
+
+    fn get_node_public_keys(&self) -> &AHashMap<u32, [u8; 48]> {
+        &self.node_public_keys
+    }
+
+    fn get_node_stakes(&self) -> &AHashMap<u32, u64> {
+        &self.node_stakes
+    }
 
-    fn sort_random_numbers(&mut self) {
-        self.random_numbers.sort();
+    fn get_excluded_node_ids(&self) -> &[u32] {
+        &self.excluded_node_ids
     }
 }
 
@@ -0,0 +1,101 @@
+use concilium_core::{evidence::{Evidence, SignedSpend}, transaction::Transaction};
+use concilium_error::Error;
+use concilium_shared::{binary, merkle::merkle_root_from_path, sha::sha256};
+use crate::transaction::transaction::TransactionSupport;
+
+pub trait SignedSpendSupport {
+    fn new(transaction: Transaction, merkle_path: Option<Vec<([u8; 32], bool)>>, signature: [u8; 96]) -> SignedSpend;
+    fn get_transaction(&self) -> &Transaction;
+    fn get_merkle_path(&self) -> Option<&[([u8; 32], bool)]>;
+    fn get_signature(&self) -> &[u8; 96];
+    fn get_message(&self) -> Result<Vec<u8>, Error>;
+}
+
+pub trait EvidenceSupport {
+    fn new(node_id: u32, public_key: [u8; 48], txid: [u8; 32], vout: usize, first: SignedSpend, second: SignedSpend, detected_at: i64) -> Evidence;
+    fn get_node_id(&self) -> u32;
+    fn get_public_key(&self) -> &[u8; 48];
+    fn get_txid(&self) -> &[u8; 32];
+    fn get_vout(&self) -> usize;
+    fn get_first(&self) -> &SignedSpend;
+    fn get_second(&self) -> &SignedSpend;
+    fn get_detected_at(&self) -> i64;
+    fn get_id(&self) -> Result<[u8; 32], Error>;
+}
+
+impl SignedSpendSupport for SignedSpend {
+    fn new(transaction: Transaction, merkle_path: Option<Vec<([u8; 32], bool)>>, signature: [u8; 96]) -> SignedSpend {
+        Self {
+            transaction,
+            merkle_path,
+            signature
+        }
+    }
+
+    fn get_transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    fn get_merkle_path(&self) -> Option<&[([u8; 32], bool)]> {
+        self.merkle_path.as_deref()
+    }
+
+    fn get_signature(&self) -> &[u8; 96] {
+        &self.signature
+    }
+
+    // what the node signed, the encoded transaction or the merkle root of its batch
+    fn get_message(&self) -> Result<Vec<u8>, Error> {
+        match &self.merkle_path {
+            Some(merkle_path) => Ok(merkle_root_from_path(self.transaction.get_txid(), merkle_path).to_vec()),
+            None => Ok(binary::encode(&self.transaction)?)
+        }
+    }
+}
+
+impl EvidenceSupport for Evidence {
+    fn new(node_id: u32, public_key: [u8; 48], txid: [u8; 32], vout: usize, first: SignedSpend, second: SignedSpend, detected_at: i64) -> Evidence {
+        Self {
+            node_id,
+            public_key,
+            txid,
+            vout,
+            first,
+            second,
+            detected_at
+        }
+    }
+
+    fn get_node_id(&self) -> u32 {
+        self.node_id
+    }
+
+    fn get_public_key(&self) -> &[u8; 48] {
+        &self.public_key
+    }
+
+    fn get_txid(&self) -> &[u8; 32] {
+        &self.txid
+    }
+
+    fn get_vout(&self) -> usize {
+        self.vout
+    }
+
+    fn get_first(&self) -> &SignedSpend {
+        &self.first
+    }
+
+    fn get_second(&self) -> &SignedSpend {
+        &self.second
+    }
+
+    fn get_detected_at(&self) -> i64 {
+        self.detected_at
+    }
+
+    // the same pair of transactions signed by the same node is one evidence, whoever detected it
+    fn get_id(&self) -> Result<[u8; 32], Error> {
+        Ok(sha256(&binary::encode(&(self.node_id, self.first.transaction.get_txid(), self.second.transaction.get_txid()))?))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use concilium_core::jrpc::chain_state::{GetStateRootRequest, GetStateRootResponse};
+
+pub trait GetStateRootRequestSupport {
+    fn new(epoch_number: u64) -> GetStateRootRequest;
+}
+
+pub trait GetStateRootResponseSupport {
+    fn new(status: bool, state_root: Option<String>, diverged_node_ids: Vec<u32>) -> GetStateRootResponse;
+}
+
+impl GetStateRootRequestSupport for GetStateRootRequest {
+    fn new(epoch_number: u64) -> GetStateRootRequest {
+        Self {
+            epoch_number
+        }
+    }
+}
+
+impl GetStateRootResponseSupport for GetStateRootResponse {
+    fn new(status: bool, state_root: Option<String>, diverged_node_ids: Vec<u32>) -> GetStateRootResponse {
+        Self {
+            status,
+            state_root,
+            diverged_node_ids
+        }
+    }
+}
\ No newline at end of file
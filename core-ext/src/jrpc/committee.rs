@@ -0,0 +1,51 @@
+use concilium_core::jrpc::{committee::{GetCommitteeNodeResponse, GetCommitteeRequest, GetCommitteeResponse}, transaction::SendRawTransactionRequest};
+
+pub trait GetCommitteeRequestSupport {
+    fn new(txid: Option<String>, transaction: Option<SendRawTransactionRequest>, epoch_number: Option<u64>) -> GetCommitteeRequest;
+}
+
+pub trait GetCommitteeNodeResponseSupport {
+    fn new(id: u32, public_key: Option<String>) -> GetCommitteeNodeResponse;
+}
+
+pub trait GetCommitteeResponseSupport {
+    fn new(status: bool, reason: Option<String>, txid: Option<String>, epoch_number: Option<u64>, leader: Option<GetCommitteeNodeResponse>, leader_candidates: Vec<GetCommitteeNodeResponse>, accreditation_council_size: Option<u32>, accreditation_council_before_cycle: Vec<GetCommitteeNodeResponse>, accreditation_council_current_cycle: Vec<GetCommitteeNodeResponse>, accreditation_council_transaction: Vec<GetCommitteeNodeResponse>, accreditation_council_signers: Option<Vec<GetCommitteeNodeResponse>>, broadcast: Option<Vec<GetCommitteeNodeResponse>>) -> GetCommitteeResponse;
+}
+
+impl GetCommitteeRequestSupport for GetCommitteeRequest {
+    fn new(txid: Option<String>, transaction: Option<SendRawTransactionRequest>, epoch_number: Option<u64>) -> GetCommitteeRequest {
+        Self {
+            txid,
+            transaction,
+            epoch_number
+        }
+    }
+}
+
+impl GetCommitteeNodeResponseSupport for GetCommitteeNodeResponse {
+    fn new(id: u32, public_key: Option<String>) -> GetCommitteeNodeResponse {
+        Self {
+            id,
+            public_key
+        }
+    }
+}
+
+impl GetCommitteeResponseSupport for GetCommitteeResponse {
+    fn new(status: bool, reason: Option<String>, txid: Option<String>, epoch_number: Option<u64>, leader: Option<GetCommitteeNodeResponse>, leader_candidates: Vec<GetCommitteeNodeResponse>, accreditation_council_size: Option<u32>, accreditation_council_before_cycle: Vec<GetCommitteeNodeResponse>, accreditation_council_current_cycle: Vec<GetCommitteeNodeResponse>, accreditation_council_transaction: Vec<GetCommitteeNodeResponse>, accreditation_council_signers: Option<Vec<GetCommitteeNodeResponse>>, broadcast: Option<Vec<GetCommitteeNodeResponse>>) -> GetCommitteeResponse {
+        Self {
+            status,
+            reason,
+            txid,
+            epoch_number,
+            leader,
+            leader_candidates,
+            accreditation_council_size,
+            accreditation_council_before_cycle,
+            accreditation_council_current_cycle,
+            accreditation_council_transaction,
+            accreditation_council_signers,
+            broadcast
+        }
+    }
+}
\ No newline at end of file
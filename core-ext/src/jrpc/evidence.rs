@@ -0,0 +1,45 @@
+use concilium_core::jrpc::evidence::{GetEvidenceRecordResponse, GetEvidenceRequest, GetEvidenceResponse};
+
+pub trait GetEvidenceRequestSupport {
+    fn new(node_id: u32) -> GetEvidenceRequest;
+}
+
+pub trait GetEvidenceRecordResponseSupport {
+    fn new(id: String, txid: String, vout: usize, first_txid: String, second_txid: String, detected_at: i64, evidence: String) -> GetEvidenceRecordResponse;
+}
+
+pub trait GetEvidenceResponseSupport {
+    fn new(status: bool, node_id: u32, evidences: Vec<GetEvidenceRecordResponse>) -> GetEvidenceResponse;
+}
+
+impl GetEvidenceRequestSupport for GetEvidenceRequest {
+    fn new(node_id: u32) -> GetEvidenceRequest {
+        Self {
+            node_id
+        }
+    }
+}
+
+impl GetEvidenceRecordResponseSupport for GetEvidenceRecordResponse {
+    fn new(id: String, txid: String, vout: usize, first_txid: String, second_txid: String, detected_at: i64, evidence: String) -> GetEvidenceRecordResponse {
+        Self {
+            id,
+            txid,
+            vout,
+            first_txid,
+            second_txid,
+            detected_at,
+            evidence
+        }
+    }
+}
+
+impl GetEvidenceResponseSupport for GetEvidenceResponse {
+    fn new(status: bool, node_id: u32, evidences: Vec<GetEvidenceRecordResponse>) -> GetEvidenceResponse {
+        Self {
+            status,
+            node_id,
+            evidences
+        }
+    }
+}
\ No newline at end of file
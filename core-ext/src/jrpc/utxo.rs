@@ -5,11 +5,11 @@ pub trait GetAddressUtxosResponseSupport {
 }
 
 pub trait GetAddressUtxosVoutResponseSupport {
-    fn new(txid: [u8; 32], vout: usize, value: f32) -> GetAddressUtxosVoutResponse;
+    fn new(txid: [u8; 32], vout: usize, value: String) -> GetAddressUtxosVoutResponse;
 }
 
 pub trait SendToAddressResponseSupport {
-    fn new(status: bool, message: String, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String) -> SendToAddressResponse;
+    fn new(status: bool, message: String, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String, accreditation_council_signers: Vec<u32>, broadcast_signers: Vec<u32>) -> SendToAddressResponse;
 }
 
 impl GetAddressUtxosResponseSupport for GetAddressUtxosResponse {
@@ -22,7 +22,7 @@ impl GetAddressUtxosResponseSupport for GetAddressUtxosResponse {
 }
 
 impl GetAddressUtxosVoutResponseSupport for GetAddressUtxosVoutResponse {
-    fn new(txid: [u8; 32], vout: usize, value: f32) -> GetAddressUtxosVoutResponse {
+    fn new(txid: [u8; 32], vout: usize, value: String) -> GetAddressUtxosVoutResponse {
         Self {
             txid,
             vout,
@@ -32,13 +32,15 @@ impl GetAddressUtxosVoutResponseSupport for GetAddressUtxosVoutResponse {
 }
 
 impl SendToAddressResponseSupport for SendToAddressResponse {
-    fn new(status: bool, message: String, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String) -> SendToAddressResponse {
+    fn new(status: bool, message: String, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String, accreditation_council_signers: Vec<u32>, broadcast_signers: Vec<u32>) -> SendToAddressResponse {
         Self {
             status,
             message,
             txid,
             accreditation_council_aggregated_signature,
-            broadcast_aggregated_signature
+            broadcast_aggregated_signature,
+            accreditation_council_signers,
+            broadcast_signers
         }
     }
 }
@@ -5,4 +5,9 @@ pub mod epoch;
 pub mod db;
 pub mod jrpc;
 pub mod chain_state;
-pub mod temporary_node_ids;
\ No newline at end of file
+pub mod temporary_node_ids;
+pub mod config;
+pub mod amount;
+pub mod checkpoint;
+pub mod evidence;
+pub mod stake;
\ No newline at end of file
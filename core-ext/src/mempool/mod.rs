@@ -1,16 +1,16 @@
-use std::{env, sync::Arc};
+use std::{env, sync::{atomic::{AtomicBool, Ordering}, Arc}};
 use active_nodes::ActiveNodesSupport;
 use ahash::AHashMap;
 use chrono::Utc;
 use concilium_core::{
-    active_nodes::ActiveNodes, chain_state::ChainState, epoch::EpochPool, mempool::Mempool, node::SelfNode, nodes_awaiting_confirmation::NodesAwaitingConfirmation, temporary_node_ids::TemporaryNodeIds, transaction::TXOutput
+    active_nodes::ActiveNodes, chain_state::ChainState, config::Config, epoch::EpochPool, evidence::SignedSpend, mempool::Mempool, node::SelfNode, nodes_awaiting_confirmation::NodesAwaitingConfirmation, temporary_node_ids::TemporaryNodeIds, transaction::{CommitCertificate, TXOutput, Transaction}
 };
 use concilium_error::Error;
 use concilium_shared::ip::ipv4_to_array;
 use nodes_awaiting_confirmation::NodesAwaitingConfirmationSupport;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{oneshot, Mutex, RwLock};
 use crate::{
-    chain_state::ChainStateSupport, epoch::EpochPoolSupport, node::self_node::SelfNodeSupport, temporary_node_ids::TemporaryNodeIdsSupport
+    chain_state::ChainStateSupport, config::ConfigSupport, epoch::EpochPoolSupport, node::self_node::SelfNodeSupport, temporary_node_ids::TemporaryNodeIdsSupport
 };
 
 pub mod active_nodes;
@@ -21,11 +21,18 @@ pub trait MempoolSupport {
     fn get_self_node(&self) -> Arc<RwLock<SelfNode>>;
     fn get_active_nodes(&self) -> Arc<RwLock<ActiveNodes>>;
     fn get_nodes_awaiting_confirmation(&self) -> Arc<RwLock<NodesAwaitingConfirmation>>;
+    fn get_node_registry(&self) -> Arc<RwLock<AHashMap<u32, [u8; 48]>>>;
     fn get_epoch_pool(&self) -> Arc<EpochPool>;
     fn get_temporary_node_ids(&self) -> Arc<TemporaryNodeIds>;
     fn get_utxos(&self) -> Arc<RwLock<AHashMap<([u8; 32], usize), TXOutput>>>;
+    fn get_pending_spends(&self) -> Arc<RwLock<AHashMap<([u8; 32], usize), ([u8; 32], i64)>>>;
     fn get_chain_state(&self) -> Arc<RwLock<ChainState>>;
     fn get_bootstrap_node_signature(&self) -> Arc<Mutex<[u8; 96]>>;
+    fn get_batch_queue(&self) -> Arc<Mutex<Vec<(Transaction, oneshot::Sender<Option<CommitCertificate>>)>>>;
+    fn get_signed_spends(&self) -> Arc<RwLock<AHashMap<(([u8; 32], usize), u32), (i64, Arc<SignedSpend>)>>>;
+    fn get_config(&self) -> Arc<Config>;
+    fn is_synced(&self) -> bool;
+    fn set_synced(&self, synced: bool);
 }
 
 impl MempoolSupport for Mempool {
@@ -46,11 +53,17 @@ impl MempoolSupport for Mempool {
                     )),
                 active_nodes: Arc::new(RwLock::new(ActiveNodes::new())),
                 nodes_awaiting_confirmation: Arc::new(RwLock::new(NodesAwaitingConfirmation::new())),
+                node_registry: Arc::new(RwLock::new(AHashMap::new())),
                 epoch_pool: Arc::new(EpochPool::new()),
                 temporary_node_ids: Arc::new(TemporaryNodeIds::new()),
                 utxos: Arc::new(RwLock::new(AHashMap::new())),
+                pending_spends: Arc::new(RwLock::new(AHashMap::new())),
                 chain_state: Arc::new(RwLock::new(ChainState::new())),
-                bootstrap_node_signature: Arc::new(Mutex::new([0; 96]))
+                bootstrap_node_signature: Arc::new(Mutex::new([0; 96])),
+                batch_queue: Arc::new(Mutex::new(Vec::new())),
+                signed_spends: Arc::new(RwLock::new(AHashMap::new())),
+                synced: Arc::new(AtomicBool::new(false)),
+                config: Arc::new(Config::new()?)
             }
         )
     }
@@ -66,6 +79,10 @@ impl MempoolSupport for Mempool {
     fn get_nodes_awaiting_confirmation(&self) -> Arc<RwLock<NodesAwaitingConfirmation>> {
         Arc::clone(&self.nodes_awaiting_confirmation)
     }
+
+    fn get_node_registry(&self) -> Arc<RwLock<AHashMap<u32, [u8; 48]>>> {
+        Arc::clone(&self.node_registry)
+    }
     
     fn get_epoch_pool(&self) -> Arc<EpochPool> {
         Arc::clone(&self.epoch_pool)
@@ -79,6 +96,10 @@ impl MempoolSupport for Mempool {
         Arc::clone(&self.utxos)
     }
 
+    fn get_pending_spends(&self) -> Arc<RwLock<AHashMap<([u8; 32], usize), ([u8; 32], i64)>>> {
+        Arc::clone(&self.pending_spends)
+    }
+
     fn get_chain_state(&self) -> Arc<RwLock<ChainState>> {
         Arc::clone(&self.chain_state)
     }
@@ -86,4 +107,24 @@ impl MempoolSupport for Mempool {
     fn get_bootstrap_node_signature(&self) -> Arc<Mutex<[u8; 96]>> {
         Arc::clone(&self.bootstrap_node_signature)
     }
+
+    fn get_batch_queue(&self) -> Arc<Mutex<Vec<(Transaction, oneshot::Sender<Option<CommitCertificate>>)>>> {
+        Arc::clone(&self.batch_queue)
+    }
+
+    fn get_signed_spends(&self) -> Arc<RwLock<AHashMap<(([u8; 32], usize), u32), (i64, Arc<SignedSpend>)>>> {
+        Arc::clone(&self.signed_spends)
+    }
+
+    fn get_config(&self) -> Arc<Config> {
+        Arc::clone(&self.config)
+    }
+
+    fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::SeqCst)
+    }
+
+    fn set_synced(&self, synced: bool) {
+        self.synced.store(synced, Ordering::SeqCst);
+    }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
+use std::sync::Arc;
 use concilium_core::{
-    node::ActiveNode,
+    node::{ActiveNode, NodeHealth},
     rpc::epoch::Client as EpochClient,
     rpc::transaction::Client as TransactionClient
 };
@@ -15,6 +16,7 @@ pub trait ActiveNodeSupport {
     fn get_created_at(&self) -> i64;
     fn get_epoch_client(&self) -> &EpochClient;
     fn get_transaction_client(&self) -> &TransactionClient;
+    fn get_health(&self) -> &NodeHealth;
     fn get_self(&self) -> &Self;
     fn set_id(&mut self, id: u32);
     fn set_name(&mut self, name: Vec<u8>);
@@ -37,6 +39,7 @@ impl ActiveNodeSupport for ActiveNode {
             port,
             version,
             created_at,
+            health: Arc::clone(&epoch_client.health),
             epoch_client,
             transaction_client
         }
@@ -77,6 +80,10 @@ impl ActiveNodeSupport for ActiveNode {
     fn get_transaction_client(&self) -> &TransactionClient {
         &self.transaction_client
     }
+
+    fn get_health(&self) -> &NodeHealth {
+        &self.health
+    }
     
     fn get_self(&self) -> &Self{
         &self
@@ -1,4 +1,5 @@
 pub mod active_node;
 pub mod awaiting_confirmation_node;
 pub mod self_node;
-pub mod serializable_node;
\ No newline at end of file
+pub mod serializable_node;
+pub mod node_health;
\ No newline at end of file
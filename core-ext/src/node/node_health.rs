@@ -0,0 +1,61 @@
+use std::{sync::atomic::Ordering, time::Duration};
+use concilium_core::node::NodeHealth;
+
+pub trait NodeHealthSupport {
+    fn new(last_response_at: i64) -> NodeHealth;
+    fn get_success_count(&self) -> u64;
+    fn get_failure_count(&self) -> u64;
+    fn get_latency(&self) -> Duration;
+    fn get_last_response_at(&self) -> i64;
+    fn record_success(&self, latency: Option<Duration>, now: i64);
+    fn record_failure(&self);
+    fn decay(&self);
+}
+
+impl NodeHealthSupport for NodeHealth {
+    // a new peer counts as having just answered, the connection itself was an answer
+    fn new(last_response_at: i64) -> NodeHealth {
+        let node_health = NodeHealth::default();
+        node_health.last_response_at.store(last_response_at, Ordering::Relaxed);
+
+        node_health
+    }
+
+    fn get_success_count(&self) -> u64 {
+        self.success_count.load(Ordering::Relaxed)
+    }
+
+    fn get_failure_count(&self) -> u64 {
+        self.failure_count.load(Ordering::Relaxed)
+    }
+
+    fn get_latency(&self) -> Duration {
+        Duration::from_millis(self.latency.load(Ordering::Relaxed))
+    }
+
+    fn get_last_response_at(&self) -> i64 {
+        self.last_response_at.load(Ordering::Relaxed)
+    }
+
+    // a stream response has no request to time, so only timed requests move the latency
+    fn record_success(&self, latency: Option<Duration>, now: i64) {
+        self.success_count.fetch_add(1, Ordering::Relaxed);
+        self.last_response_at.fetch_max(now, Ordering::Relaxed);
+
+        if let Some(latency) = latency {
+            let latency = latency.as_millis() as u64;
+            self.latency.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                Some(if average == 0 { latency } else { (average * 7 + latency) / 8 })
+            }).ok();
+        }
+    }
+
+    fn record_failure(&self) {
+        self.failure_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn decay(&self) {
+        self.success_count.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| Some(count - count / 4)).ok();
+        self.failure_count.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| Some(count - count / 4)).ok();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use concilium_core::{amount::Amount, stake::Stake};
+use concilium_shared::transaction::{STAKE_ACTIVATION_EPOCHS, UNBONDING_EPOCHS};
+
+pub trait StakeSupport {
+    fn new(node_public_key: [u8; 48], owner: [u8; 32], value: Amount, bonded_epoch: u64, unbonding_epoch: Option<u64>) -> Stake;
+    fn get_node_public_key(&self) -> &[u8; 48];
+    fn get_owner(&self) -> &[u8; 32];
+    fn get_value(&self) -> Amount;
+    fn get_bonded_epoch(&self) -> u64;
+    fn get_unbonding_epoch(&self) -> Option<u64>;
+    fn set_unbonding_epoch(&mut self, unbonding_epoch: u64);
+    fn is_active_at(&self, epoch_number: u64) -> bool;
+    fn is_spendable_at(&self, epoch_number: u64) -> bool;
+}
+
+impl StakeSupport for Stake {
+    fn new(node_public_key: [u8; 48], owner: [u8; 32], value: Amount, bonded_epoch: u64, unbonding_epoch: Option<u64>) -> Stake {
+        Self {
+            node_public_key,
+            owner,
+            value,
+            bonded_epoch,
+            unbonding_epoch
+        }
+    }
+
+    fn get_node_public_key(&self) -> &[u8; 48] {
+        &self.node_public_key
+    }
+
+    fn get_owner(&self) -> &[u8; 32] {
+        &self.owner
+    }
+
+    fn get_value(&self) -> Amount {
+        self.value
+    }
+
+    fn get_bonded_epoch(&self) -> u64 {
+        self.bonded_epoch
+    }
+
+    fn get_unbonding_epoch(&self) -> Option<u64> {
+        self.unbonding_epoch
+    }
+
+    fn set_unbonding_epoch(&mut self, unbonding_epoch: u64) {
+        self.unbonding_epoch = Some(unbonding_epoch);
+    }
+
+    // bonds and unbonds take effect a few epochs late, so nodes that commit them at different times agree
+    fn is_active_at(&self, epoch_number: u64) -> bool {
+        let bonded = epoch_number >= self.bonded_epoch + STAKE_ACTIVATION_EPOCHS;
+        let unbonded = match self.unbonding_epoch {
+            Some(unbonding_epoch) => epoch_number >= unbonding_epoch + STAKE_ACTIVATION_EPOCHS,
+            None => false
+        };
+
+        bonded && !unbonded
+    }
+
+    fn is_spendable_at(&self, epoch_number: u64) -> bool {
+        match self.unbonding_epoch {
+            Some(unbonding_epoch) => epoch_number >= unbonding_epoch + UNBONDING_EPOCHS,
+            None => false
+        }
+    }
+}
\ No newline at end of file
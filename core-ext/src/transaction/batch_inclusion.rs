@@ -0,0 +1,24 @@
+use concilium_core::transaction::{BatchInclusion, BatchTemp};
+
+pub trait BatchInclusionSupport {
+    fn new(batch_temp: BatchTemp, merkle_path: Vec<([u8; 32], bool)>) -> BatchInclusion;
+    fn get_batch_temp(&self) -> &BatchTemp;
+    fn get_merkle_path(&self) -> &Vec<([u8; 32], bool)>;
+}
+
+impl BatchInclusionSupport for BatchInclusion {
+    fn new(batch_temp: BatchTemp, merkle_path: Vec<([u8; 32], bool)>) -> BatchInclusion {
+        Self {
+            batch_temp,
+            merkle_path
+        }
+    }
+
+    fn get_batch_temp(&self) -> &BatchTemp {
+        &self.batch_temp
+    }
+
+    fn get_merkle_path(&self) -> &Vec<([u8; 32], bool)> {
+        &self.merkle_path
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use concilium_core::transaction::BatchTemp;
+
+pub trait BatchTempSupport {
+    fn new(epoch_number: u64, merkle_root: [u8; 32], signature: [u8; 96], accreditation_council_signers: Vec<u32>, leader_id: u32, broadcast_node_count: u32) -> BatchTemp;
+    fn get_epoch_number(&self) -> u64;
+    fn get_merkle_root(&self) -> &[u8; 32];
+    fn get_signature(&self) -> &[u8; 96];
+    fn get_accreditation_council_signers(&self) -> &Vec<u32>;
+    fn get_leader_id(&self) -> u32;
+    fn get_broadcast_node_count(&self) -> u32;
+}
+
+impl BatchTempSupport for BatchTemp {
+    fn new(epoch_number: u64, merkle_root: [u8; 32], signature: [u8; 96], accreditation_council_signers: Vec<u32>, leader_id: u32, broadcast_node_count: u32) -> BatchTemp {
+        Self {
+            epoch_number,
+            merkle_root,
+            signature,
+            accreditation_council_signers,
+            leader_id,
+            broadcast_node_count
+        }
+    }
+
+    fn get_epoch_number(&self) -> u64 {
+        self.epoch_number
+    }
+
+    fn get_merkle_root(&self) -> &[u8; 32] {
+        &self.merkle_root
+    }
+
+    fn get_signature(&self) -> &[u8; 96] {
+        &self.signature
+    }
+
+    fn get_accreditation_council_signers(&self) -> &Vec<u32> {
+        &self.accreditation_council_signers
+    }
+
+    fn get_leader_id(&self) -> u32 {
+        self.leader_id
+    }
+
+    fn get_broadcast_node_count(&self) -> u32 {
+        self.broadcast_node_count
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use concilium_core::transaction::{BatchTemp, BroadcastBatchTemp, Transaction};
+
+pub trait BroadcastBatchTempSupport {
+    fn new(batch_temp: BatchTemp, transactions: Vec<Transaction>) -> BroadcastBatchTemp;
+    fn get_batch_temp(&self) -> &BatchTemp;
+    fn get_transactions(&self) -> &Vec<Transaction>;
+}
+
+impl BroadcastBatchTempSupport for BroadcastBatchTemp {
+    fn new(batch_temp: BatchTemp, transactions: Vec<Transaction>) -> BroadcastBatchTemp {
+        Self {
+            batch_temp,
+            transactions
+        }
+    }
+
+    fn get_batch_temp(&self) -> &BatchTemp {
+        &self.batch_temp
+    }
+
+    fn get_transactions(&self) -> &Vec<Transaction> {
+        &self.transactions
+    }
+}
\ No newline at end of file
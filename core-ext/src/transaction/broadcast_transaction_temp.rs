@@ -1,16 +1,20 @@
 use concilium_core::transaction::{BroadcastTransactionTemp, Transaction};
 
 pub trait BroadcastTransactionTempSupport {
-    fn new(transaction: Transaction, signature: [u8; 96]) -> BroadcastTransactionTemp;
+    fn new(transaction: Transaction, signature: [u8; 96], accreditation_council_signers: Vec<u32>, leader_id: u32) -> BroadcastTransactionTemp;
     fn get_transaction(&self) -> &Transaction;
     fn get_signature(&self) -> &[u8; 96];
+    fn get_accreditation_council_signers(&self) -> &Vec<u32>;
+    fn get_leader_id(&self) -> u32;
 }
 
 impl BroadcastTransactionTempSupport for BroadcastTransactionTemp {
-    fn new(transaction: Transaction, signature: [u8; 96]) -> BroadcastTransactionTemp {
+    fn new(transaction: Transaction, signature: [u8; 96], accreditation_council_signers: Vec<u32>, leader_id: u32) -> BroadcastTransactionTemp {
         Self {
             transaction,
-            signature
+            signature,
+            accreditation_council_signers,
+            leader_id
         }
     }
 
@@ -21,4 +25,12 @@ impl BroadcastTransactionTempSupport for BroadcastTransactionTemp {
     fn get_signature(&self) -> &[u8; 96] {
         &self.signature
     }
+
+    fn get_accreditation_council_signers(&self) -> &Vec<u32> {
+        &self.accreditation_council_signers
+    }
+
+    fn get_leader_id(&self) -> u32 {
+        self.leader_id
+    }
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use concilium_core::transaction::{BatchInclusion, BroadcastTransactionTemp, CommitCertificate};
+
+pub trait CommitCertificateSupport {
+    fn new(broadcast_transaction_temp: BroadcastTransactionTemp, broadcast_aggregated_signature: [u8; 96], broadcast_signers: Vec<u32>, batch_inclusion: Option<BatchInclusion>) -> CommitCertificate;
+    fn get_broadcast_transaction_temp(&self) -> &BroadcastTransactionTemp;
+    fn get_broadcast_aggregated_signature(&self) -> &[u8; 96];
+    fn get_broadcast_signers(&self) -> &Vec<u32>;
+    fn get_batch_inclusion(&self) -> Option<&BatchInclusion>;
+}
+
+impl CommitCertificateSupport for CommitCertificate {
+    fn new(broadcast_transaction_temp: BroadcastTransactionTemp, broadcast_aggregated_signature: [u8; 96], broadcast_signers: Vec<u32>, batch_inclusion: Option<BatchInclusion>) -> CommitCertificate {
+        Self {
+            broadcast_transaction_temp,
+            broadcast_aggregated_signature,
+            broadcast_signers,
+            batch_inclusion
+        }
+    }
+
+    fn get_broadcast_transaction_temp(&self) -> &BroadcastTransactionTemp {
+        &self.broadcast_transaction_temp
+    }
+
+    fn get_broadcast_aggregated_signature(&self) -> &[u8; 96] {
+        &self.broadcast_aggregated_signature
+    }
+
+    fn get_broadcast_signers(&self) -> &Vec<u32> {
+        &self.broadcast_signers
+    }
+
+    fn get_batch_inclusion(&self) -> Option<&BatchInclusion> {
+        self.batch_inclusion.as_ref()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use concilium_core::{amount::Amount, transaction::FeeDistribution};
+
+pub trait FeeDistributionSupport {
+    fn new(fee: Amount, shares: Vec<(u32, Amount)>) -> FeeDistribution;
+    fn get_fee(&self) -> Amount;
+    fn get_shares(&self) -> &Vec<(u32, Amount)>;
+}
+
+impl FeeDistributionSupport for FeeDistribution {
+    fn new(fee: Amount, shares: Vec<(u32, Amount)>) -> FeeDistribution {
+        Self {
+            fee,
+            shares
+        }
+    }
+
+    fn get_fee(&self) -> Amount {
+        self.fee
+    }
+
+    fn get_shares(&self) -> &Vec<(u32, Amount)> {
+        &self.shares
+    }
+}
@@ -0,0 +1,26 @@
+use concilium_core::jrpc::transaction::{GetAccountNonceRequest, GetAccountNonceResponse};
+
+pub trait GetAccountNonceRequestSupport {
+    fn new(public_key: String) -> GetAccountNonceRequest;
+}
+
+pub trait GetAccountNonceResponseSupport {
+    fn new(status: bool, next_nonce: u64) -> GetAccountNonceResponse;
+}
+
+impl GetAccountNonceRequestSupport for GetAccountNonceRequest {
+    fn new(public_key: String) -> GetAccountNonceRequest {
+        Self {
+            public_key
+        }
+    }
+}
+
+impl GetAccountNonceResponseSupport for GetAccountNonceResponse {
+    fn new(status: bool, next_nonce: u64) -> GetAccountNonceResponse {
+        Self {
+            status,
+            next_nonce
+        }
+    }
+}
\ No newline at end of file
@@ -1,11 +1,11 @@
 use concilium_core::jrpc::transaction::{GetTransactionByHash, GetTransactionByHashTXInput, GetTransactionByHashTXOutput};
 
 pub trait GetTransactionByHashSupport {
-    fn new(txid: String, from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<GetTransactionByHashTXInput>, vout: Vec<GetTransactionByHashTXOutput>) -> GetTransactionByHash;
+    fn new(txid: String, from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<GetTransactionByHashTXInput>, vout: Vec<GetTransactionByHashTXOutput>, stake: Option<String>, unstake: Option<GetTransactionByHashTXInput>) -> GetTransactionByHash;
 }
 
 impl GetTransactionByHashSupport for GetTransactionByHash {
-    fn new(txid: String, from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<GetTransactionByHashTXInput>, vout: Vec<GetTransactionByHashTXOutput>) -> GetTransactionByHash {
+    fn new(txid: String, from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<GetTransactionByHashTXInput>, vout: Vec<GetTransactionByHashTXOutput>, stake: Option<String>, unstake: Option<GetTransactionByHashTXInput>) -> GetTransactionByHash {
         Self {
             txid,
             from,
@@ -13,7 +13,9 @@ impl GetTransactionByHashSupport for GetTransactionByHash {
             nonce,
             created_at,
             vin,
-            vout
+            vout,
+            stake,
+            unstake
         }
     }
 }
@@ -0,0 +1,28 @@
+use concilium_core::jrpc::transaction::{GetTransactionStatusRequest, GetTransactionStatusResponse};
+
+pub trait GetTransactionStatusRequestSupport {
+    fn new(txid: String) -> GetTransactionStatusRequest;
+}
+
+pub trait GetTransactionStatusResponseSupport {
+    fn new(status: bool, state: Option<String>, reason: Option<String>, updated_at: Option<i64>) -> GetTransactionStatusResponse;
+}
+
+impl GetTransactionStatusRequestSupport for GetTransactionStatusRequest {
+    fn new(txid: String) -> GetTransactionStatusRequest {
+        Self {
+            txid
+        }
+    }
+}
+
+impl GetTransactionStatusResponseSupport for GetTransactionStatusResponse {
+    fn new(status: bool, state: Option<String>, reason: Option<String>, updated_at: Option<i64>) -> GetTransactionStatusResponse {
+        Self {
+            status,
+            state,
+            reason,
+            updated_at
+        }
+    }
+}
\ No newline at end of file
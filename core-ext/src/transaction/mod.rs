@@ -8,4 +8,13 @@ pub mod send_raw_transaction_request_txoutput;
 pub mod send_raw_transaction_response;
 pub mod broadcast_transaction_temp;
 pub mod get_transaction_by_hash;
-pub mod get_account_transactions;
\ No newline at end of file
+pub mod get_account_transactions;
+pub mod commit_certificate;
+pub mod get_account_nonce;
+pub mod fee_distribution;
+pub mod transaction_batch;
+pub mod batch_temp;
+pub mod broadcast_batch_temp;
+pub mod batch_inclusion;
+pub mod transaction_status;
+pub mod get_transaction_status;
\ No newline at end of file
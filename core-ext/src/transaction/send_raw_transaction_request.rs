@@ -1,26 +1,30 @@
 use concilium_core::jrpc::transaction::{SendRawTransactionRequest, SendRawTransactionRequestTXInput, SendRawTransactionRequestTXOutput};
 
 pub trait SendRawTransactionRequestSupport {
-    fn new(from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<SendRawTransactionRequestTXInput>, vout: Vec<SendRawTransactionRequestTXOutput>) -> SendRawTransactionRequest;
+    fn new(from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<SendRawTransactionRequestTXInput>, vout: Vec<SendRawTransactionRequestTXOutput>, stake: Option<String>, unstake: Option<SendRawTransactionRequestTXInput>) -> SendRawTransactionRequest;
     fn get_from(&self) -> &String;
     fn get_signature(&self) -> &String;
     fn get_nonce(&self) -> u64;
     fn get_created_at(&self) -> i64;
     fn get_vin(&self) -> &Vec<SendRawTransactionRequestTXInput>;
     fn get_vout(&self) -> &Vec<SendRawTransactionRequestTXOutput>;
+    fn get_stake(&self) -> Option<&String>;
+    fn get_unstake(&self) -> Option<&SendRawTransactionRequestTXInput>;
     fn set_vin(&mut self, vin: Vec<SendRawTransactionRequestTXInput>);
     fn set_vout(&mut self, vout: Vec<SendRawTransactionRequestTXOutput>);
 }
 
 impl SendRawTransactionRequestSupport for SendRawTransactionRequest {
-    fn new(from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<SendRawTransactionRequestTXInput>, vout: Vec<SendRawTransactionRequestTXOutput>) -> SendRawTransactionRequest {
+    fn new(from: String, signature: String, nonce: u64, created_at: i64, vin: Vec<SendRawTransactionRequestTXInput>, vout: Vec<SendRawTransactionRequestTXOutput>, stake: Option<String>, unstake: Option<SendRawTransactionRequestTXInput>) -> SendRawTransactionRequest {
         Self {
             from,
             signature,
             nonce,
             created_at,
             vin,
-            vout
+            vout,
+            stake,
+            unstake
         }
     }
 
@@ -48,6 +52,14 @@ impl SendRawTransactionRequestSupport for SendRawTransactionRequest {
         &self.vout
     }
 
+    fn get_stake(&self) -> Option<&String> {
+        self.stake.as_ref()
+    }
+
+    fn get_unstake(&self) -> Option<&SendRawTransactionRequestTXInput> {
+        self.unstake.as_ref()
+    }
+
     fn set_vin(&mut self, vin: Vec<SendRawTransactionRequestTXInput>) {
         self.vin = vin
     }
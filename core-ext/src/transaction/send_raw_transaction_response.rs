@@ -1,16 +1,18 @@
 use concilium_core::jrpc::transaction::SendRawTransactionResponse;
 
 pub trait SendRawTransactionResponseSupport {
-    fn new(status: bool, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String) -> SendRawTransactionResponse;
+    fn new(status: bool, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String, accreditation_council_signers: Vec<u32>, broadcast_signers: Vec<u32>) -> SendRawTransactionResponse;
 }
 
 impl SendRawTransactionResponseSupport for SendRawTransactionResponse {
-    fn new(status: bool, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String) -> SendRawTransactionResponse {
+    fn new(status: bool, txid: String, accreditation_council_aggregated_signature: String, broadcast_aggregated_signature: String, accreditation_council_signers: Vec<u32>, broadcast_signers: Vec<u32>) -> SendRawTransactionResponse {
         Self {
             status,
             txid,
             accreditation_council_aggregated_signature,
             broadcast_aggregated_signature,
+            accreditation_council_signers,
+            broadcast_signers,
         }
     }
 }
@@ -1,7 +1,7 @@
-use concilium_core::transaction::{TXInput, TXOutput, Transaction};
+use concilium_core::transaction::{TXInput, TXOutput, Transaction, TransactionKind};
 
 pub trait TransactionSupport {
-    fn new(txid: [u8; 32], from: [u8; 32], signature: [u8; 64], nonce: u64, created_at: i64, vin: Vec<TXInput>, vout: Vec<TXOutput>) -> Transaction;
+    fn new(txid: [u8; 32], from: [u8; 32], signature: [u8; 64], nonce: u64, created_at: i64, vin: Vec<TXInput>, vout: Vec<TXOutput>, kind: TransactionKind) -> Transaction;
     fn get_txid(&self) -> &[u8; 32];
     fn get_from(&self) -> &[u8; 32];
     fn get_signature(&self) -> &[u8; 64];
@@ -9,6 +9,7 @@ pub trait TransactionSupport {
     fn get_created_at(&self) -> i64;
     fn get_vin(&self) -> &Vec<TXInput>;
     fn get_vout(&self) -> &Vec<TXOutput>;
+    fn get_kind(&self) -> &TransactionKind;
     fn set_txid(&mut self, txid: [u8; 32]);
     fn set_from(&mut self, from: [u8; 32]);
     fn set_signature(&mut self, signature: [u8; 64]);
@@ -19,10 +20,11 @@ pub trait TransactionSupport {
     fn vout_push(&mut self, tx_output: TXOutput);
     fn vout_pop(&mut self) -> Option<TXOutput>;
     fn set_created_at(&mut self, created_at: i64);
+    fn set_kind(&mut self, kind: TransactionKind);
 }
 
 impl TransactionSupport for Transaction {
-    fn new(txid: [u8; 32], from: [u8; 32], signature: [u8; 64], nonce: u64, created_at: i64, vin: Vec<TXInput>, vout: Vec<TXOutput>) -> Transaction {
+    fn new(txid: [u8; 32], from: [u8; 32], signature: [u8; 64], nonce: u64, created_at: i64, vin: Vec<TXInput>, vout: Vec<TXOutput>, kind: TransactionKind) -> Transaction {
         Transaction {
             txid,
             from,
@@ -31,6 +33,7 @@ impl TransactionSupport for Transaction {
             created_at,
             vin,
             vout,
+            kind,
         }
     }
 
@@ -62,6 +65,10 @@ impl TransactionSupport for Transaction {
         &self.vout
     }
 
+    fn get_kind(&self) -> &TransactionKind {
+        &self.kind
+    }
+
     fn set_txid(&mut self, txid: [u8; 32]) {
         self.txid = txid;
     }
@@ -101,4 +108,8 @@ impl TransactionSupport for Transaction {
     fn set_created_at(&mut self, created_at: i64) {
         self.created_at = created_at;
     }
+
+    fn set_kind(&mut self, kind: TransactionKind) {
+        self.kind = kind;
+    }
 }
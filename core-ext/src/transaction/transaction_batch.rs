@@ -0,0 +1,30 @@
+use concilium_core::transaction::{Transaction, TransactionBatch};
+
+pub trait TransactionBatchSupport {
+    fn new(leader_id: u32, transactions: Vec<Transaction>) -> TransactionBatch;
+    fn get_leader_id(&self) -> u32;
+    fn get_transactions(&self) -> &Vec<Transaction>;
+    fn get_txids(&self) -> Vec<[u8; 32]>;
+}
+
+impl TransactionBatchSupport for TransactionBatch {
+    fn new(leader_id: u32, transactions: Vec<Transaction>) -> TransactionBatch {
+        Self {
+            leader_id,
+            transactions
+        }
+    }
+
+    fn get_leader_id(&self) -> u32 {
+        self.leader_id
+    }
+
+    fn get_transactions(&self) -> &Vec<Transaction> {
+        &self.transactions
+    }
+
+    // the leaves of the batch merkle tree, in batch order
+    fn get_txids(&self) -> Vec<[u8; 32]> {
+        self.transactions.iter().map(|transaction| transaction.txid).collect()
+    }
+}
\ No newline at end of file
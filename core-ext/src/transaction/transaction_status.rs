@@ -0,0 +1,64 @@
+use concilium_core::transaction::{TransactionState, TransactionStatus};
+
+pub trait TransactionStateSupport {
+    fn get_name(&self) -> &'static str;
+    fn is_final(&self) -> bool;
+    fn get_rank(&self) -> u8;
+}
+
+pub trait TransactionStatusSupport {
+    fn new(state: TransactionState, reason: Option<String>, updated_at: i64) -> TransactionStatus;
+    fn get_state(&self) -> TransactionState;
+    fn get_reason(&self) -> Option<&String>;
+    fn get_updated_at(&self) -> i64;
+}
+
+impl TransactionStateSupport for TransactionState {
+    fn get_name(&self) -> &'static str {
+        match self {
+            TransactionState::Received => "received",
+            TransactionState::Accredited => "accredited",
+            TransactionState::Broadcast => "broadcast",
+            TransactionState::Committed => "committed",
+            TransactionState::Rejected => "rejected",
+        }
+    }
+
+    // a committed transaction never changes its state again
+    fn is_final(&self) -> bool {
+        *self == TransactionState::Committed
+    }
+
+    // the order in which a transaction moves forward, a rejection can be retried from scratch
+    fn get_rank(&self) -> u8 {
+        match self {
+            TransactionState::Rejected => 0,
+            TransactionState::Received => 1,
+            TransactionState::Accredited => 2,
+            TransactionState::Broadcast => 3,
+            TransactionState::Committed => 4,
+        }
+    }
+}
+
+impl TransactionStatusSupport for TransactionStatus {
+    fn new(state: TransactionState, reason: Option<String>, updated_at: i64) -> TransactionStatus {
+        Self {
+            state,
+            reason,
+            updated_at
+        }
+    }
+
+    fn get_state(&self) -> TransactionState {
+        self.state
+    }
+
+    fn get_reason(&self) -> Option<&String> {
+        self.reason.as_ref()
+    }
+
+    fn get_updated_at(&self) -> i64 {
+        self.updated_at
+    }
+}
\ No newline at end of file
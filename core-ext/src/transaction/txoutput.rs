@@ -1,22 +1,22 @@
-use concilium_core::transaction::TXOutput;
+use concilium_core::{amount::Amount, transaction::TXOutput};
 
 pub trait TXOutputSupport {
-    fn new(value: f32, public_key: [u8; 32]) -> TXOutput;
-    fn get_value(&self) -> f32;
+    fn new(value: Amount, public_key: [u8; 32]) -> TXOutput;
+    fn get_value(&self) -> Amount;
     fn get_public_key(&self) -> &[u8; 32];
-    fn set_value(&mut self, value: f32);
+    fn set_value(&mut self, value: Amount);
     fn set_public_key(&mut self, public_key: [u8; 32]);
 }
 
 impl TXOutputSupport for TXOutput {
-    fn new(value: f32, public_key: [u8; 32]) -> TXOutput {
+    fn new(value: Amount, public_key: [u8; 32]) -> TXOutput {
         TXOutput { 
             value, 
             public_key
         }
     }
 
-    fn get_value(&self) -> f32 {
+    fn get_value(&self) -> Amount {
         self.value
     }
 
@@ -24,7 +24,7 @@ impl TXOutputSupport for TXOutput {
         &self.public_key
     }
 
-    fn set_value(&mut self, value: f32) {
+    fn set_value(&mut self, value: Amount) {
         self.value = value;
     }
 
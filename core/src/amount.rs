@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+// fixed-point amount in base units, see AMOUNT_DECIMALS
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Amount {
+    pub base_units: u64,
+}
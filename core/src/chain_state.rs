@@ -1,6 +1,20 @@
 use ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
+use crate::{amount::Amount, stake::Stake};
 
 pub struct ChainState {
-    pub balances: AHashMap<[u8; 32], f32>, // account public key, balance
+    pub balances: AHashMap<[u8; 32], Amount>, // account public key, balance
     pub transactions: AHashMap<[u8; 32], AHashSet<[u8; 32]>>, // account public key, txid
+    pub nonces: AHashMap<[u8; 32], u64>, // account public key, last used nonce
+    pub node_rewards: AHashMap<u32, Amount>, // node id, collected fees
+    pub committed_txids: AHashSet<[u8; 32]>, // every transaction this node committed, genesis included
+    pub uncheckpointed_txids: AHashMap<[u8; 32], u64>, // committed txid without a sequence yet, epoch of its created_at
+    pub stakes: AHashMap<([u8; 32], usize), Stake>, // outpoint of a bonded output, its stake
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StateCommitment {
+    pub epoch_number: u64,
+    pub state_root: [u8; 32], // merkle root over the utxos, the balances and the stakes after the transactions of the checkpointed epoch
+    pub diverged_node_ids: Vec<u32>, // peers that reported another root for the epoch
 }
\ No newline at end of file
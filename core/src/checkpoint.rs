@@ -0,0 +1,23 @@
+use serde_big_array::BigArray;
+use serde::{Deserialize, Serialize};
+
+/*
+    The transactions of the network in their canonical order, one checkpoint per closed epoch.
+    Sequences continue from the previous checkpoint and every checkpoint names the hash of the one before.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub epoch_number: u64, // the last epoch whose transactions are included
+    pub committee_epoch_number: u64, // the epoch the signing committee is selected from
+    pub previous_hash: [u8; 32],
+    pub first_sequence: u64, // sequence of the first txid
+    pub txids: Vec<[u8; 32]>, // sorted
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignedCheckpoint {
+    pub checkpoint: Checkpoint,
+    pub signers: Vec<u32>, // node ids, empty for the genesis checkpoint
+    #[serde(with = "BigArray")]
+    pub aggregated_signature: [u8; 96],
+}
\ No newline at end of file
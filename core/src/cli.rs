@@ -31,6 +31,30 @@ pub enum Commands {
         #[arg(short, long)]
         receiver_public_key: String,
         #[arg(short, long)]
-        amount: f32,
+        amount: String, // decimal amount
+        #[arg(short, long, default_value = "0.0001")]
+        fee: String, // decimal amount
+    },
+    #[command(about = "Bond funds to a node, they count for its membership a few epochs later")]
+    StakeToNode {
+        #[arg(short, long)]
+        sender_private_key: String,
+        #[arg(short, long)]
+        node_public_key: String, // BLS public key
+        #[arg(short, long)]
+        amount: String, // decimal amount
+        #[arg(short, long, default_value = "0.0001")]
+        fee: String, // decimal amount
+    },
+    #[command(about = "Start unbonding a stake, its funds can be spent once the unbonding delay passed")]
+    Unstake {
+        #[arg(short, long)]
+        sender_private_key: String,
+        #[arg(short, long)]
+        txid: String, // staking transaction
+        #[arg(short, long, default_value = "0")]
+        vout: usize,
+        #[arg(short, long, default_value = "0.0001")]
+        fee: String, // decimal amount
     },
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use crate::amount::Amount;
+
+pub struct Config {
+    pub request_timeout: u64, // milliseconds a node waits for a peer to answer one stage of a transaction
+    pub leader_request_timeout: u64, // milliseconds the JSON-RPC waits for a remote leader to commit a transaction
+    pub save_request_retries: u32, // times a save request is sent again to a peer that did not confirm it
+    pub min_transaction_fee: Amount, // smallest fee this node leads or accredits a transaction for
+    pub batch_window: u64, // milliseconds a leader collects transactions into one batch, 0 leads every transaction on its own
+    pub max_batch_size: usize, // transactions a leader puts into one batch at most
+    pub reconciliation_interval: u64, // milliseconds between two reconciliations with a random peer, 0 turns them off
+    pub max_reconciliation_certificates: usize, // commit certificates a node asks a peer for in one request at most
+    pub health_min_samples: u64, // responses counted from a peer before its success rate can make it unhealthy
+    pub health_min_success_rate: u32, // percent of requests a peer has to answer to stay in committees
+    pub health_max_silence: u64, // seconds a peer may go without answering before it is unhealthy
+}
@@ -8,8 +8,25 @@ pub struct Epoch {
     pub id: u64,
     pub last_node_id: u32,
     pub final_hash: [u8; 32],
-    pub random_numbers: Vec<u64>,
+    pub randomness: AHashMap<u32, [u8; 96]>, // node id, its signature over the final hash of the epoch before
     pub hashes: AHashMap<[u8; 32], u32>, // hash, count
+    pub exclusion_votes: AHashMap<u32, u32>, // node id, count of nodes reporting it unhealthy
+    pub exclusions: AHashMap<([u8; 32], Vec<u32>), u32>, // hash and the node ids excluded along with it, count
+}
+
+/*
+    Everything committee selection depends on for a single epoch,
+    so transaction certificates can be checked without a running node.
+*/
+#[derive(Clone, Debug)]
+pub struct EpochSnapshot {
+    pub epoch_number: u64,
+    pub last_node_id: u32,
+    pub temporary_node_ids: Arc<AHashMap<u32, u32>>, // temporary id, node id
+    pub before_temporary_node_ids: Arc<AHashMap<u32, u32>>, // temporary id, node id
+    pub node_public_keys: AHashMap<u32, [u8; 48]>, // node id, public key
+    pub node_stakes: AHashMap<u32, u64>, // node id, weight in the selection
+    pub excluded_node_ids: Vec<u32>,
 }
 
 pub enum EpochMapAddOp {
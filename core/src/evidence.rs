@@ -0,0 +1,31 @@
+use serde_big_array::BigArray;
+use serde::{Deserialize, Serialize};
+use crate::transaction::Transaction;
+
+/*
+    The BLS signature of a council member over a transaction. In a batch the member signs
+    the merkle root of the batch, so the path from the txid up to the root comes with it.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignedSpend {
+    pub transaction: Transaction,
+    pub merkle_path: Option<Vec<([u8; 32], bool)>>, // none when the encoded transaction itself is signed
+    #[serde(with = "BigArray")]
+    pub signature: [u8; 96],
+}
+
+/*
+    Proof that a node signed two different transactions spending the same output within one reservation.
+    It can be verified with the public key of the node and the transaction validity window.
+*/
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Evidence {
+    pub node_id: u32,
+    #[serde(with = "BigArray")]
+    pub public_key: [u8; 48],
+    pub txid: [u8; 32], // the output both transactions spend
+    pub vout: usize,
+    pub first: SignedSpend, // the lower txid of the two
+    pub second: SignedSpend,
+    pub detected_at: i64,
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct GetStateRootRequest {
+    pub epoch_number: u64
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetStateRootResponse {
+    pub status: bool,
+    pub state_root: Option<String>, // hex
+    pub diverged_node_ids: Vec<u32>, // peers that reported another root for the epoch
+}
\ No newline at end of file
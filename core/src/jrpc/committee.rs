@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use crate::jrpc::transaction::SendRawTransactionRequest;
+
+#[derive(Deserialize)]
+pub struct GetCommitteeRequest {
+    #[serde(default)]
+    pub txid: Option<String>, // hex, a transaction this node stored
+    #[serde(default)]
+    pub transaction: Option<SendRawTransactionRequest>, // a transaction that was not sent yet
+    #[serde(default)]
+    pub epoch_number: Option<u64>, // epoch to select the committee of the raw transaction in, the one it was created in by default
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetCommitteeNodeResponse {
+    pub id: u32,
+    pub public_key: Option<String>, // hex of the BLS public key, none for a node this node was never told about
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetCommitteeResponse {
+    pub status: bool,
+    pub reason: Option<String>, // set when the committee could not be selected
+    pub txid: Option<String>, // hex
+    pub epoch_number: Option<u64>,
+    pub leader: Option<GetCommitteeNodeResponse>, // the node that led a committed transaction, the selected leader otherwise
+    pub leader_candidates: Vec<GetCommitteeNodeResponse>, // the leader followed by its backups, empty once the epoch is no longer kept
+    pub accreditation_council_size: Option<u32>,
+    pub accreditation_council_before_cycle: Vec<GetCommitteeNodeResponse>,
+    pub accreditation_council_current_cycle: Vec<GetCommitteeNodeResponse>,
+    pub accreditation_council_transaction: Vec<GetCommitteeNodeResponse>,
+    pub accreditation_council_signers: Option<Vec<GetCommitteeNodeResponse>>, // recorded in the certificate of a committed transaction
+    pub broadcast: Option<Vec<GetCommitteeNodeResponse>>, // the broadcast signers recorded in the certificate of a committed transaction
+}
\ No newline at end of file
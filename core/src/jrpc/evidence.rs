@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct GetEvidenceRequest {
+    pub node_id: u32
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetEvidenceRecordResponse {
+    pub id: String, // hex
+    pub txid: String, // hex, the output both transactions spend
+    pub vout: usize,
+    pub first_txid: String, // hex
+    pub second_txid: String, // hex
+    pub detected_at: i64,
+    pub evidence: String, // hex of the encoded evidence, it can be verified on its own
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetEvidenceResponse {
+    pub status: bool,
+    pub node_id: u32,
+    pub evidences: Vec<GetEvidenceRecordResponse>
+}
\ No newline at end of file
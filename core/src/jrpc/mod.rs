@@ -1,3 +1,6 @@
 pub mod transaction;
 pub mod rpc_module_context;
-pub mod utxo;
\ No newline at end of file
+pub mod utxo;
+pub mod chain_state;
+pub mod evidence;
+pub mod committee;
\ No newline at end of file
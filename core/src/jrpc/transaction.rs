@@ -9,7 +9,7 @@ pub struct SendRawTransactionRequestTXInput {
 
 #[derive(Serialize, Deserialize)]
 pub struct SendRawTransactionRequestTXOutput {
-    pub value: f32,
+    pub value: String, // decimal amount
     pub public_key: String
 }
 
@@ -21,6 +21,10 @@ pub struct SendRawTransactionRequest {
     pub created_at: i64,
     pub vin: Vec<SendRawTransactionRequestTXInput>,
     pub vout: Vec<SendRawTransactionRequestTXOutput>,
+    #[serde(default)]
+    pub stake: Option<String>, // BLS public key of the node the first output is bonded to
+    #[serde(default)]
+    pub unstake: Option<SendRawTransactionRequestTXInput>, // stake output to start unbonding
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +33,8 @@ pub struct SendRawTransactionResponse {
     pub txid: String,
     pub accreditation_council_aggregated_signature: String,
     pub broadcast_aggregated_signature: String,
+    pub accreditation_council_signers: Vec<u32>, // node ids
+    pub broadcast_signers: Vec<u32>, // node ids
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,7 +45,7 @@ pub struct GetTransactionByHashTXInput {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetTransactionByHashTXOutput {
-    pub value: f32,
+    pub value: String, // decimal amount
     pub public_key: String
 }
 
@@ -52,6 +58,8 @@ pub struct GetTransactionByHash {
     pub created_at: i64,
     pub vin: Vec<GetTransactionByHashTXInput>,
     pub vout: Vec<GetTransactionByHashTXOutput>,
+    pub stake: Option<String>, // BLS public key of the node the first output is bonded to
+    pub unstake: Option<GetTransactionByHashTXInput>, // stake output the transaction started unbonding
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,4 +82,28 @@ pub struct GetAccountTransactionsRequest {
 pub struct GetAccountTransactionsResponse {
     pub status: bool,
     pub transactions: Vec<Transaction>
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetAccountNonceRequest {
+    pub public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetAccountNonceResponse {
+    pub status: bool,
+    pub next_nonce: u64
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetTransactionStatusRequest {
+    pub txid: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GetTransactionStatusResponse {
+    pub status: bool,
+    pub state: Option<String>, // received, accredited, broadcast, committed or rejected
+    pub reason: Option<String>,
+    pub updated_at: Option<i64>,
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ pub struct GetAddressUtxosRequest {
 pub struct GetAddressUtxosVoutResponse {
     pub txid: [u8; 32],
     pub vout: usize,
-    pub value: f32,
+    pub value: String, // decimal amount
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -25,4 +25,6 @@ pub struct SendToAddressResponse {
     pub txid: String,
     pub accreditation_council_aggregated_signature: String,
     pub broadcast_aggregated_signature: String,
+    pub accreditation_council_signers: Vec<u32>, // node ids
+    pub broadcast_signers: Vec<u32>, // node ids
 }
@@ -10,3 +10,9 @@ pub mod transaction;
 pub mod cli;
 pub mod chain_state;
 pub mod temporary_node_ids;
+pub mod config;
+pub mod amount;
+
+pub mod checkpoint;
+pub mod evidence;
+pub mod stake;
\ No newline at end of file
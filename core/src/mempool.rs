@@ -1,15 +1,21 @@
-use std::sync::Arc;
+use std::sync::{atomic::AtomicBool, Arc};
 use ahash::AHashMap;
-use tokio::sync::{Mutex, RwLock};
-use crate::{active_nodes::ActiveNodes, chain_state::ChainState, epoch::EpochPool, node::SelfNode, nodes_awaiting_confirmation::NodesAwaitingConfirmation, temporary_node_ids::TemporaryNodeIds, transaction::TXOutput};
+use tokio::sync::{oneshot, Mutex, RwLock};
+use crate::{active_nodes::ActiveNodes, chain_state::ChainState, config::Config, epoch::EpochPool, evidence::SignedSpend, node::SelfNode, nodes_awaiting_confirmation::NodesAwaitingConfirmation, temporary_node_ids::TemporaryNodeIds, transaction::{CommitCertificate, TXOutput, Transaction}};
 
 pub struct Mempool {
     pub self_node: Arc<RwLock<SelfNode>>,
     pub active_nodes: Arc<RwLock<ActiveNodes>>,
     pub nodes_awaiting_confirmation: Arc<RwLock<NodesAwaitingConfirmation>>,
+    pub node_registry: Arc<RwLock<AHashMap<u32, [u8; 48]>>>, // node id, BLS public key of every node confirmed to the network, kept once it goes offline
     pub epoch_pool: Arc<EpochPool>,
     pub temporary_node_ids: Arc<TemporaryNodeIds>, 
     pub utxos: Arc<RwLock<AHashMap<([u8; 32], usize), TXOutput>>>, // txid, vout index, TXOutput,
+    pub pending_spends: Arc<RwLock<AHashMap<([u8; 32], usize), ([u8; 32], i64)>>>, // txid, vout index, (spending txid, reserved at)
     pub chain_state: Arc<RwLock<ChainState>>,
-    pub bootstrap_node_signature: Arc<Mutex<[u8; 96]>>
+    pub bootstrap_node_signature: Arc<Mutex<[u8; 96]>>,
+    pub batch_queue: Arc<Mutex<Vec<(Transaction, oneshot::Sender<Option<CommitCertificate>>)>>>, // transactions waiting for the next batch, answered with their certificate
+    pub signed_spends: Arc<RwLock<AHashMap<(([u8; 32], usize), u32), (i64, Arc<SignedSpend>)>>>, // (txid, vout index, node id), (signed at, the transaction the node signed spending it)
+    pub synced: Arc<AtomicBool>, // the committed ledger has caught up with the network, so the node can be a leader or a council member
+    pub config: Arc<Config>
 }
\ No newline at end of file
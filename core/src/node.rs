@@ -1,3 +1,4 @@
+use std::sync::{atomic::{AtomicI64, AtomicU64}, Arc};
 use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
 use crate::rpc::epoch::Client as EpochClient;
@@ -38,7 +39,20 @@ pub struct ActiveNode {
     pub version: Vec<u8>,
     pub created_at: i64,
     pub epoch_client: EpochClient,
-    pub transaction_client: TransactionClient
+    pub transaction_client: TransactionClient,
+    pub health: Arc<NodeHealth>, // shared with the epoch client, which sees the epoch responses of the node
+}
+
+/*
+    How a peer has been answering this node lately. The counts lose a quarter every epoch,
+    so a node that recovers is not held to its old failures for long.
+*/
+#[derive(Debug, Default)]
+pub struct NodeHealth {
+    pub success_count: AtomicU64,
+    pub failure_count: AtomicU64,
+    pub latency: AtomicU64, // milliseconds, moving average of the timed responses
+    pub last_response_at: AtomicI64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
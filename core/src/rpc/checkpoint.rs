@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use tonic::transport::Channel;
+use concilium_proto_defs::checkpoint::checkpoint_client::CheckpointClient;
+use crate::{db::DB, mempool::Mempool};
+
+pub struct Client {
+    pub client: CheckpointClient<Channel>
+}
+
+pub struct Server {
+    pub mempool: Arc<Mempool>,
+    pub db: Arc<DB>,
+}
\ No newline at end of file
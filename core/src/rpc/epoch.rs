@@ -6,7 +6,7 @@ use concilium_proto_defs::epoch::{
     InitialRequest, 
     SyncRequest
 };
-use crate::mempool::Mempool;
+use crate::{mempool::Mempool, node::NodeHealth};
 
 #[derive(Debug)]
 pub struct Client {
@@ -14,7 +14,8 @@ pub struct Client {
     pub initial_tx: Arc<Sender<InitialRequest>>,
     pub sync_tx: Arc<Sender<SyncRequest>>,
     pub initial_thread_handler: JoinHandle<()>,
-    pub sync_thread_handler: JoinHandle<()>
+    pub sync_thread_handler: JoinHandle<()>,
+    pub health: Arc<NodeHealth>
 }
 
 pub struct Server {
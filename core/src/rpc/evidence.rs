@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use tonic::transport::Channel;
+use concilium_proto_defs::evidence::evidence_client::EvidenceClient;
+use crate::{db::DB, mempool::Mempool};
+
+pub struct Client {
+    pub client: EvidenceClient<Channel>
+}
+
+pub struct Server {
+    pub mempool: Arc<Mempool>,
+    pub db: Arc<DB>,
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use tonic::transport::Channel;
+use concilium_proto_defs::reconciliation::reconciliation_client::ReconciliationClient;
+use crate::{db::DB, mempool::Mempool};
+
+pub struct Client {
+    pub client: ReconciliationClient<Channel>
+}
+
+pub struct Server {
+    pub mempool: Arc<Mempool>,
+    pub db: Arc<DB>,
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use crate::amount::Amount;
+
+// an output bonded to a node by a staking transaction, it stays owned by the sender
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Stake {
+    #[serde(with = "BigArray")]
+    pub node_public_key: [u8; 48],
+    pub owner: [u8; 32], // public key of the sender
+    pub value: Amount,
+    pub bonded_epoch: u64, // epoch of the staking transaction
+    pub unbonding_epoch: Option<u64>, // epoch of the unstaking transaction
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use serde_big_array::BigArray;
 use serde::{Deserialize, Serialize};
+use crate::amount::Amount;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Transaction {
@@ -11,6 +12,20 @@ pub struct Transaction {
     pub created_at: i64,
     pub vin: Vec<TXInput>,
     pub vout: Vec<TXOutput>,
+    pub kind: TransactionKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    Transfer,
+    Stake { // the first output, paid to the sender, is bonded to the node
+        #[serde(with = "BigArray")]
+        node_public_key: [u8; 48],
+    },
+    Unstake { // the sender starts unbonding a stake output it owns
+        txid: [u8; 32],
+        vout: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -21,7 +36,7 @@ pub struct TXInput {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 pub struct TXOutput {
-    pub value: f32,
+    pub value: Amount,
     pub public_key: [u8; 32]
 }
 
@@ -31,9 +46,71 @@ pub struct SenderTransactionTemp {
     pub epoch_number: i64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BroadcastTransactionTemp {
     pub transaction: Transaction,
     #[serde(with = "BigArray")]
     pub signature: [u8; 96],
+    pub accreditation_council_signers: Vec<u32>, // node ids
+    pub leader_id: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommitCertificate {
+    pub broadcast_transaction_temp: BroadcastTransactionTemp,
+    #[serde(with = "BigArray")]
+    pub broadcast_aggregated_signature: [u8; 96],
+    pub broadcast_signers: Vec<u32>, // node ids
+    pub batch_inclusion: Option<BatchInclusion>, // set when the transaction was committed as part of a batch
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeeDistribution {
+    pub fee: Amount,
+    pub shares: Vec<(u32, Amount)>, // node id, share of the fee
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionBatch {
+    pub leader_id: u32,
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchTemp {
+    pub epoch_number: u64,
+    pub merkle_root: [u8; 32], // over the txids of the batch
+    #[serde(with = "BigArray")]
+    pub signature: [u8; 96],
+    pub accreditation_council_signers: Vec<u32>, // node ids
+    pub leader_id: u32,
+    pub broadcast_node_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BroadcastBatchTemp {
+    pub batch_temp: BatchTemp,
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchInclusion {
+    pub batch_temp: BatchTemp,
+    pub merkle_path: Vec<([u8; 32], bool)>, // sibling hash, sibling is on the left
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    Received,
+    Accredited,
+    Broadcast,
+    Committed,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionStatus {
+    pub state: TransactionState,
+    pub reason: Option<String>, // set when the transaction was rejected
+    pub updated_at: i64,
 }
\ No newline at end of file
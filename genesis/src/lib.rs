@@ -1,10 +1,15 @@
 use std::sync::Arc;
 use ahash::AHashSet;
-use concilium_core::{db::DB, mempool::Mempool, transaction::{TXOutput, Transaction}};
-use concilium_core_ext::{chain_state::ChainStateSupport, db::DBSupport, mempool::MempoolSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}};
-use concilium_shared::{binary, coventor::vec::unsigned_int::vec_to_unsigned_int};
+use concilium_core::{amount::Amount, db::DB, mempool::Mempool, transaction::{FeeDistribution, TXOutput, Transaction, TransactionKind}};
+use concilium_core_ext::{amount::AmountSupport, chain_state::ChainStateSupport, db::DBSupport, mempool::MempoolSupport, transaction::{fee_distribution::FeeDistributionSupport, transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}};
+use concilium_error::Error;
+use concilium_shared::{binary, coventor::vec::unsigned_int::vec_to_unsigned_int, epoch::timestamp_to_epoch_number};
+use concilium_transaction::{checkpoint::{get_transaction_sequence, put_genesis_checkpoint}, stake::apply_stake};
 use serde::{Deserialize, Serialize};
 use tokio::{fs::File, io::AsyncReadExt};
+use migration::{AMOUNT_FORMAT_BASE_UNITS, AMOUNT_FORMAT_KEY, TRANSACTION_FORMAT_KEY, TRANSACTION_FORMAT_KINDS};
+
+pub mod migration;
 
 #[derive(Serialize, Deserialize, Debug)]
 struct GenesisTransaction {
@@ -32,18 +37,18 @@ struct GTXInput {
 
 #[derive(Serialize, Deserialize, Debug)]
 struct GTXOutput {
-    value: f32,
+    value: serde_json::Number, // decimal amount, parsed into base units
     public_key: String
 }
 
-pub async fn load_genesis_transactions(mempool: Arc<Mempool>, db: Arc<DB>,) {
-    let mut transactions_file = File::open("genesis_transactions.json").await.unwrap();
+pub async fn load_genesis_transactions(mempool: Arc<Mempool>, db: Arc<DB>,) -> Result<(), Error> {
+    let mut transactions_file = File::open("genesis_transactions.json").await?;
 
     let mut buffer = String::new();
 
-    transactions_file.read_to_string(&mut buffer).await.unwrap();
+    transactions_file.read_to_string(&mut buffer).await?;
 
-    let genesis_transactions: Vec<GenesisTransaction> = serde_json::from_str(&buffer).unwrap();
+    let genesis_transactions: Vec<GenesisTransaction> = serde_json::from_str(&buffer)?;
 
     let utxos_lock = mempool.get_utxos();
     let mut utxos = utxos_lock.write().await;
@@ -51,18 +56,24 @@ pub async fn load_genesis_transactions(mempool: Arc<Mempool>, db: Arc<DB>,) {
     let chain_state_lock = mempool.get_chain_state();
     let mut chain_state = chain_state_lock.write().await;
 
+    let mut genesis_txids = Vec::with_capacity(genesis_transactions.len());
     for trx in genesis_transactions {
-        let last_transaction_id = db.get("last_transaction_id").unwrap();
-        let last_transaction_id : u64 = if let Some(data) = last_transaction_id {
-            vec_to_unsigned_int::<u64>(&data).unwrap()
-        } else {
-            0
+        let last_transaction_id : u64 = match db.get("last_transaction_id")? {
+            Some(data) => vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid last transaction id"))?,
+            None => 0
         };
 
-        let public_key: [u8; 32] = hex::decode(trx.public_key).unwrap().try_into().unwrap();
+        let public_key: [u8; 32] = hex::decode(trx.public_key)?.try_into()?;
+
+        // a value written in exponent form is refused, amounts are plain decimals
+        let value = match trx.transaction.vout.first() {
+            Some(output) => Amount::from_decimal_str(&output.value.to_string())
+            .map_err(|e| Error::new(format!("invalid genesis value of {}: [{}]", trx.transaction.txid, e).as_str()))?,
+            None => return Err(Error::new(format!("genesis transaction {} has no output", trx.transaction.txid).as_str()))
+        };
 
         let transaction = Transaction::new(
-            hex::decode(trx.transaction.txid.clone()).unwrap().try_into().unwrap(), 
+            hex::decode(trx.transaction.txid.clone())?.try_into()?, 
             [0; 32],
             [0; 64],
             trx.transaction.nonce,
@@ -70,32 +81,41 @@ pub async fn load_genesis_transactions(mempool: Arc<Mempool>, db: Arc<DB>,) {
             Vec::new(), 
             vec![
                 TXOutput::new(
-                    trx.transaction.vout[0].value, 
+                    value, 
                     public_key.clone()
                 )
             ],
+            TransactionKind::Transfer
         );
 
-        let binary_transaction = binary::encode(&transaction).unwrap();
-        db.put(format!("transaction.{}", trx.transaction.txid.clone()).as_str(),&binary_transaction).unwrap();
+        let binary_transaction = binary::encode(&transaction)?;
+        db.put(format!("transaction.{}", trx.transaction.txid.clone()).as_str(),&binary_transaction)?;
         
-        db.put(format!("transaction.id.{}", (last_transaction_id + 1)).as_str(),&trx.transaction.txid).unwrap();
-        db.put("last_transaction_id", &(last_transaction_id + 1).to_le_bytes()).unwrap();
+        db.put(format!("transaction.id.{}", (last_transaction_id + 1)).as_str(),&trx.transaction.txid)?;
+        db.put("last_transaction_id", &(last_transaction_id + 1).to_le_bytes())?;
 
         utxos.insert((transaction.txid.clone(), 0), transaction.vout[0].clone());
 
-        chain_state.get_mut_balances().insert(public_key.clone(), trx.transaction.vout[0].value);
+        chain_state.get_mut_balances().insert(public_key.clone(), value);
 
         let mut transaction_hash_set = AHashSet::new();
         transaction_hash_set.insert(transaction.txid);
         chain_state.get_mut_transactions().insert(public_key, transaction_hash_set);
+        chain_state.add_committed_txid(transaction.txid);
+        genesis_txids.push(transaction.txid);
     }
 
-    db.put("included_genesis_transactions", b"true").unwrap();  
+    put_genesis_checkpoint(genesis_txids, &db)?;
+    db.put("included_genesis_transactions", b"true")?;  
+    db.put(AMOUNT_FORMAT_KEY, &AMOUNT_FORMAT_BASE_UNITS)?;
+    db.put(TRANSACTION_FORMAT_KEY, &TRANSACTION_FORMAT_KINDS)
 }
 
-pub async fn load_transactions(mempool: Arc<Mempool>, db: Arc<DB>) {
-    let last_transaction_id = vec_to_unsigned_int::<u64>(&db.get("last_transaction_id").unwrap().unwrap()).unwrap();
+pub async fn load_transactions(mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let last_transaction_id = match db.get("last_transaction_id")? {
+        Some(data) => vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid last transaction id"))?,
+        None => return Err(Error::new("last transaction id not found"))
+    };
 
     let utxos_lock = mempool.get_utxos();
     let mut utxos = utxos_lock.write().await;
@@ -103,24 +123,36 @@ pub async fn load_transactions(mempool: Arc<Mempool>, db: Arc<DB>) {
     let chain_state_lock = mempool.get_chain_state();
     let mut chain_state = chain_state_lock.write().await;
 
+    // genesis transactions have no sender
+    let mut genesis_txids = Vec::new();
     for id in 1..=last_transaction_id {
-        let txid = db.get(format!("transaction.id.{}", id).as_str()).unwrap().unwrap();
-        let txid = String::from_utf8(txid).unwrap();
+        let txid = match db.get(format!("transaction.id.{}", id).as_str())? {
+            Some(data) => String::from_utf8(data)?,
+            None => return Err(Error::new("transaction id not found"))
+        };
+
+        let transaction = match db.get(format!("transaction.{}", txid).as_str())? {
+            Some(data) => binary::decode::<Transaction>(&data)?,
+            None => return Err(Error::new("transaction not found"))
+        };
 
-        let transaction = db.get(format!("transaction.{}", txid).as_str()).unwrap().unwrap();
-        let transaction = binary::decode::<Transaction>(&transaction).unwrap();
+        // transactions committed before fees existed have no distribution
+        let fee_distribution = match db.get(format!("fee_distribution.{}", txid).as_str())? {
+            Some(data) => Some(binary::decode::<FeeDistribution>(&data)?),
+            None => None
+        };
 
         for input in transaction.get_vin() {
             utxos.remove(&(*input.get_txid(), input.get_vout()));    
         }
 
-        let mut outputs_fee = 0.0;
+        let mut outputs_fee = Amount::zero();
         for (index, output ) in transaction.get_vout().iter().enumerate() {
-            outputs_fee += output.get_value();
+            outputs_fee = outputs_fee.checked_add(output.get_value()).ok_or(Error::new("amount overflow"))?;
 
             let account_balance = match chain_state.get_balances().get(output.get_public_key()) {
-                Some(data) => *data + output.value,
-                None => output.value
+                Some(data) => data.checked_add(output.get_value()).ok_or(Error::new("amount overflow"))?,
+                None => output.get_value()
             };
             chain_state.get_mut_balances().insert(output.get_public_key().clone(), account_balance);
 
@@ -137,11 +169,29 @@ pub async fn load_transactions(mempool: Arc<Mempool>, db: Arc<DB>) {
         }
 
 
+        if let Some(fee_distribution) = &fee_distribution {
+            outputs_fee = outputs_fee.checked_add(fee_distribution.get_fee()).ok_or(Error::new("amount overflow"))?;
+
+            for (node_id, share) in fee_distribution.get_shares() {
+                chain_state.add_node_reward(*node_id, *share);
+            }
+        }
+
+        // a sender short of what it paid means the stored ledger is inconsistent
         let account_balance = match chain_state.get_balances().get(transaction.get_from()) {
-            Some(data) => *data - outputs_fee,
-            None => -outputs_fee
+            Some(data) => data.checked_sub(outputs_fee).ok_or(Error::new(format!("balance of the sender of {} is short of its outputs and fee", txid).as_str()))?,
+            None => Amount::zero()
         };
         chain_state.get_mut_balances().insert(transaction.get_from().clone(), account_balance);
+        chain_state.update_nonce(transaction.get_from().clone(), transaction.get_nonce());
+        chain_state.add_committed_txid(transaction.get_txid().clone());
+        apply_stake(&transaction, &mut chain_state);
+
+        if *transaction.get_from() == [0; 32] {
+            genesis_txids.push(transaction.get_txid().clone());
+        } else if get_transaction_sequence(transaction.get_txid(), &db)?.is_none() {
+            chain_state.add_uncheckpointed_txid(transaction.get_txid().clone(), timestamp_to_epoch_number(transaction.get_created_at()) as u64);
+        }
 
         if let Some(data) = chain_state.get_mut_transactions().get_mut(transaction.get_from()) {
             data.insert(transaction.get_txid().clone());
@@ -152,4 +202,7 @@ pub async fn load_transactions(mempool: Arc<Mempool>, db: Arc<DB>) {
             chain_state.get_mut_transactions().insert(transaction.get_from().clone(), hash_set);
         }
     }
+
+    // databases from before the checkpoints get their genesis checkpoint here
+    put_genesis_checkpoint(genesis_txids, &db)
 }
\ No newline at end of file
@@ -0,0 +1,193 @@
+use concilium_core::{amount::Amount, db::DB, transaction::{BatchInclusion, BroadcastTransactionTemp, CommitCertificate, TXInput, TXOutput, Transaction, TransactionKind}};
+use concilium_core_ext::{amount::AmountSupport, db::DBSupport, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport, txoutput::TXOutputSupport}};
+use concilium_error::Error;
+use concilium_shared::{binary, coventor::vec::unsigned_int::vec_to_unsigned_int, transaction::BASE_UNITS_PER_COIN};
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+
+pub const AMOUNT_FORMAT_KEY: &str = "amount_format";
+pub const AMOUNT_FORMAT_BASE_UNITS: &[u8] = b"base_units";
+pub const TRANSACTION_FORMAT_KEY: &str = "transaction_format";
+pub const TRANSACTION_FORMAT_KINDS: &[u8] = b"kinds";
+
+// transaction layout of databases written before amounts were stored in base units
+#[derive(Serialize, Deserialize)]
+struct LegacyTransaction {
+    txid: [u8; 32],
+    from: [u8; 32],
+    #[serde(with = "BigArray")]
+    signature: [u8; 64],
+    nonce: u64,
+    created_at: i64,
+    vin: Vec<TXInput>,
+    vout: Vec<LegacyTXOutput>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyTXOutput {
+    value: f32,
+    public_key: [u8; 32]
+}
+
+// transaction layout of databases written before transactions had a kind
+#[derive(Serialize, Deserialize)]
+struct UnkindedTransaction {
+    txid: [u8; 32],
+    from: [u8; 32],
+    #[serde(with = "BigArray")]
+    signature: [u8; 64],
+    nonce: u64,
+    created_at: i64,
+    vin: Vec<TXInput>,
+    vout: Vec<TXOutput>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UnkindedBroadcastTransactionTemp {
+    transaction: UnkindedTransaction,
+    #[serde(with = "BigArray")]
+    signature: [u8; 96],
+    accreditation_council_signers: Vec<u32>,
+    leader_id: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UnkindedCommitCertificate {
+    broadcast_transaction_temp: UnkindedBroadcastTransactionTemp,
+    #[serde(with = "BigArray")]
+    broadcast_aggregated_signature: [u8; 96],
+    broadcast_signers: Vec<u32>,
+    batch_inclusion: Option<BatchInclusion>,
+}
+
+/*
+    Rewrites every stored transaction from f32 values to base units.
+    Values are rounded to two decimals as they were validated before, and txids
+    are kept as they are so the transaction index and account history still match.
+    The records and the format keys are written in one batch, so a crash can not leave
+    migrated records behind without the key that marks them.
+*/
+pub fn migrate_amounts(db: &DB) -> Result<(), Error> {
+    if db.get(AMOUNT_FORMAT_KEY)?.is_some() {
+        return Ok(());
+    }
+
+    let last_transaction_id = match db.get("last_transaction_id")? {
+        Some(data) => vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid last transaction id"))?,
+        None => 0
+    };
+
+    let mut entries = Vec::new();
+    for id in 1..=last_transaction_id {
+        let txid = match db.get(format!("transaction.id.{}", id).as_str())? {
+            Some(data) => String::from_utf8(data)?,
+            None => return Err(Error::new("transaction id not found"))
+        };
+
+        let key = format!("transaction.{}", txid);
+        let legacy_transaction = match db.get(key.as_str())? {
+            Some(data) => binary::decode::<LegacyTransaction>(&data)?,
+            None => return Err(Error::new("transaction not found"))
+        };
+
+        let vout = legacy_transaction.vout.iter()
+        .map(|output| TXOutput::new(legacy_value_to_amount(output.value), output.public_key))
+        .collect();
+
+        let transaction = Transaction::new(
+            legacy_transaction.txid,
+            legacy_transaction.from,
+            legacy_transaction.signature,
+            legacy_transaction.nonce,
+            legacy_transaction.created_at,
+            legacy_transaction.vin,
+            vout,
+            TransactionKind::Transfer
+        );
+
+        entries.push((key, binary::encode(&transaction)?));
+    }
+
+    // the transactions are written with their kind already
+    entries.push((TRANSACTION_FORMAT_KEY.to_string(), TRANSACTION_FORMAT_KINDS.to_vec()));
+    entries.push((AMOUNT_FORMAT_KEY.to_string(), AMOUNT_FORMAT_BASE_UNITS.to_vec()));
+
+    db.put_all(entries)
+}
+
+/*
+    Rewrites every stored transaction and commit certificate with a kind, everything stored before staking is a transfer.
+    Like the amount migration, txids are kept and everything is written in one batch with the format key.
+    Certificates older than the amount migration can not be read in either layout and are left as they are.
+*/
+pub fn migrate_transaction_kinds(db: &DB) -> Result<(), Error> {
+    if db.get(TRANSACTION_FORMAT_KEY)?.is_some() {
+        return Ok(());
+    }
+
+    let last_transaction_id = match db.get("last_transaction_id")? {
+        Some(data) => vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid last transaction id"))?,
+        None => 0
+    };
+
+    let mut entries = Vec::new();
+    for id in 1..=last_transaction_id {
+        let txid = match db.get(format!("transaction.id.{}", id).as_str())? {
+            Some(data) => String::from_utf8(data)?,
+            None => return Err(Error::new("transaction id not found"))
+        };
+
+        let key = format!("transaction.{}", txid);
+        let unkinded_transaction = match db.get(key.as_str())? {
+            Some(data) => binary::decode::<UnkindedTransaction>(&data)?,
+            None => return Err(Error::new("transaction not found"))
+        };
+
+        entries.push((key, binary::encode(&unkinded_to_transaction(unkinded_transaction))?));
+
+        let key = format!("commit_certificate.{}", txid);
+        if let Some(data) = db.get(key.as_str())? {
+            if let Ok(unkinded_commit_certificate) = binary::decode::<UnkindedCommitCertificate>(&data) {
+                let unkinded_broadcast_transaction_temp = unkinded_commit_certificate.broadcast_transaction_temp;
+                let broadcast_transaction_temp = BroadcastTransactionTemp::new(
+                    unkinded_to_transaction(unkinded_broadcast_transaction_temp.transaction),
+                    unkinded_broadcast_transaction_temp.signature,
+                    unkinded_broadcast_transaction_temp.accreditation_council_signers,
+                    unkinded_broadcast_transaction_temp.leader_id
+                );
+
+                let commit_certificate = CommitCertificate::new(
+                    broadcast_transaction_temp,
+                    unkinded_commit_certificate.broadcast_aggregated_signature,
+                    unkinded_commit_certificate.broadcast_signers,
+                    unkinded_commit_certificate.batch_inclusion
+                );
+
+                entries.push((key, binary::encode(&commit_certificate)?));
+            }
+        }
+    }
+
+    entries.push((TRANSACTION_FORMAT_KEY.to_string(), TRANSACTION_FORMAT_KINDS.to_vec()));
+
+    db.put_all(entries)
+}
+
+fn unkinded_to_transaction(unkinded_transaction: UnkindedTransaction) -> Transaction {
+    Transaction::new(
+        unkinded_transaction.txid,
+        unkinded_transaction.from,
+        unkinded_transaction.signature,
+        unkinded_transaction.nonce,
+        unkinded_transaction.created_at,
+        unkinded_transaction.vin,
+        unkinded_transaction.vout,
+        TransactionKind::Transfer
+    )
+}
+
+fn legacy_value_to_amount(value: f32) -> Amount {
+    let cents = (value as f64 * 100.0).round().max(0.0) as u64;
+
+    Amount::new(cents.saturating_mul(BASE_UNITS_PER_COIN / 100))
+}
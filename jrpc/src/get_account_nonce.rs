@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use concilium_core::{jrpc::transaction::{GetAccountNonceRequest, GetAccountNonceResponse}, mempool::Mempool};
+use concilium_core_ext::{chain_state::ChainStateSupport, mempool::MempoolSupport, transaction::get_account_nonce::GetAccountNonceResponseSupport};
+use jsonrpsee::types::{ErrorObject, Params};
+
+pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>) -> Result<GetAccountNonceResponse, ErrorObject<'static>> {
+    let request: GetAccountNonceRequest = params.parse()?;
+
+    let public_key: [u8; 32] = match hex::decode(request.public_key) {
+        Ok(data) => match data.try_into() {
+            Ok(converted) => converted,
+            Err(_) => return Ok(failed_response())
+        },
+        Err(_) => return Ok(failed_response())
+    };
+
+    let chain_state_lock = mempool.get_chain_state();
+    let chain_state = chain_state_lock.read().await;
+
+    Ok(GetAccountNonceResponse::new(true, chain_state.get_next_nonce(&public_key)))
+}
+
+fn failed_response() -> GetAccountNonceResponse {
+    GetAccountNonceResponse::new(false, 0)
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use concilium_core::{jrpc::utxo::{GetAddressUtxosRequest, GetAddressUtxosResponse, GetAddressUtxosVoutResponse}, mempool::Mempool};
-use concilium_core_ext::{jrpc::utxo::{GetAddressUtxosResponseSupport, GetAddressUtxosVoutResponseSupport}, mempool::MempoolSupport};
+use concilium_core_ext::{amount::AmountSupport, chain_state::ChainStateSupport, jrpc::utxo::{GetAddressUtxosResponseSupport, GetAddressUtxosVoutResponseSupport}, mempool::MempoolSupport, stake::StakeSupport};
+use concilium_shared::epoch::current_epoch_number;
 use jsonrpsee::types::{ErrorObject, Params};
 
 pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>) -> Result<GetAddressUtxosResponse, ErrorObject<'static>> {
@@ -17,11 +18,18 @@ pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>) -> Result<GetAdd
         Err(_) => return Ok(failed_response())
     };
 
+    // bonded outputs can not be spent until their unbonding delay passed
+    let current_epoch_number = current_epoch_number() as u64;
+    let lock = mempool.get_chain_state();
+    let chain_state = lock.read().await;
+
     let mut vout = Vec::new();
 
     for item in utxos.iter() {
-        if item.1.public_key == public_key {
-            vout.push(GetAddressUtxosVoutResponse::new(item.0.0, item.0.1, item.1.value));
+        let locked = chain_state.get_stakes().get(item.0).is_some_and(|stake| !stake.is_spendable_at(current_epoch_number));
+
+        if item.1.public_key == public_key && !locked {
+            vout.push(GetAddressUtxosVoutResponse::new(item.0.0, item.0.1, item.1.value.to_decimal_string()));
         }
     }
 
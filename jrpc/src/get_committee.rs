@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use ahash::AHashMap;
+use concilium_core::{db::DB, jrpc::committee::{GetCommitteeNodeResponse, GetCommitteeRequest, GetCommitteeResponse}, mempool::Mempool, transaction::{CommitCertificate, Transaction}};
+use concilium_core_ext::{db::DBSupport, jrpc::committee::{GetCommitteeNodeResponseSupport, GetCommitteeResponseSupport}, mempool::MempoolSupport, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport}};
+use concilium_shared::{binary, epoch::{epoch_number_to_timestamp, timestamp_to_epoch_number}};
+use concilium_transaction::{
+    get_epoch_snapshot, get_nmac, send_raw_transaction_request_to_transaction, selection::{select_accreditation_council_segments, select_leader_ids}
+};
+use jsonrpsee::types::{ErrorObject, Params};
+
+/*
+    The leader, Accreditation Council and broadcast nodes of a transaction, selected from the same epoch snapshot the pipeline uses.
+    A committed transaction also reports the leader and the signers its certificate recorded, even once its epoch is no longer kept.
+    A raw transaction is moved to the requested epoch to see who would be chosen there,
+    its broadcast nodes are unknown since they are seeded by the signature of the council.
+*/
+pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<GetCommitteeResponse, ErrorObject<'static>> {
+    let request: GetCommitteeRequest = match params.parse() {
+        Ok(data) => data,
+        Err(_) => {
+            return Ok(failed_response("invalid params"));
+        }
+    };
+
+    let (transaction, commit_certificate) = match (request.txid, request.transaction) {
+        (Some(txid), None) => match get_stored_transaction(&txid, &db) {
+            Some(data) => data,
+            None => return Ok(failed_response("transaction not found"))
+        },
+        (None, Some(mut trx_request)) => {
+            if let Some(epoch_number) = request.epoch_number {
+                if timestamp_to_epoch_number(trx_request.created_at) as u64 != epoch_number {
+                    trx_request.created_at = match i64::try_from(epoch_number).ok().and_then(epoch_number_to_timestamp) {
+                        Some(data) => data,
+                        None => return Ok(failed_response("invalid epoch number"))
+                    };
+                }
+            }
+
+            match send_raw_transaction_request_to_transaction(trx_request) {
+                Ok(data) => (data, None),
+                Err(e) => return Ok(failed_response(e.get_message()))
+            }
+        },
+        _ => return Ok(failed_response("either a txid or a transaction is needed"))
+    };
+
+    let node_registry = {
+        let lock = mempool.get_node_registry();
+        let node_registry = lock.read().await;
+
+        node_registry.clone()
+    };
+
+    let (leader_ids, accreditation_council_size, (before_cycle_node_ids, current_cycle_node_ids, transaction_node_ids)) = match get_epoch_snapshot(&transaction, Arc::clone(&mempool)).await {
+        Ok(epoch_snapshot) => {
+            let leader_ids = match select_leader_ids(&transaction, &epoch_snapshot) {
+                Ok(data) => data,
+                Err(e) => return Ok(failed_response(e.get_message()))
+            };
+
+            let (nnr, _, _, _) = match get_nmac(&transaction, Arc::clone(&mempool)).await {
+                Ok(data) => data,
+                Err(e) => return Ok(failed_response(e.get_message()))
+            };
+
+            match select_accreditation_council_segments(&transaction, &epoch_snapshot) {
+                Ok(data) => (leader_ids, Some(nnr), data),
+                Err(e) => return Ok(failed_response(&e.get_message()))
+            }
+        },
+        Err(_) if commit_certificate.is_some() => (Vec::new(), None, (Vec::new(), Vec::new(), Vec::new())),
+        Err(e) => return Ok(failed_response(e.get_message()))
+    };
+
+    // a committed transaction reports who actually signed, a backup leader or a partial council included
+    let (leader_id, accreditation_council_signers, broadcast) = match &commit_certificate {
+        Some(commit_certificate) => {
+            let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+
+            (
+                Some(broadcast_transaction_temp.get_leader_id()),
+                Some(to_node_responses(broadcast_transaction_temp.get_accreditation_council_signers(), &node_registry)),
+                Some(to_node_responses(commit_certificate.get_broadcast_signers(), &node_registry))
+            )
+        },
+        None => (leader_ids.first().copied(), None, None)
+    };
+
+    Ok(
+        GetCommitteeResponse::new(
+            true, 
+            None, 
+            Some(hex::encode(transaction.get_txid())), 
+            Some(timestamp_to_epoch_number(transaction.get_created_at()) as u64), 
+            leader_id.map(|id| to_node_response(id, &node_registry)), 
+            to_node_responses(&leader_ids, &node_registry), 
+            accreditation_council_size,
+            to_node_responses(&before_cycle_node_ids, &node_registry), 
+            to_node_responses(&current_cycle_node_ids, &node_registry), 
+            to_node_responses(&transaction_node_ids, &node_registry), 
+            accreditation_council_signers,
+            broadcast
+        )
+    )
+}
+
+// committed transactions carry their certificate, transactions of the genesis do not
+fn get_stored_transaction(txid: &str, db: &DB) -> Option<(Transaction, Option<CommitCertificate>)> {
+    let txid: [u8; 32] = hex::decode(txid).ok()?.try_into().ok()?;
+    let txid_hex = hex::encode(txid);
+
+    if let Ok(Some(binary_commit_certificate)) = db.get(format!("commit_certificate.{}", txid_hex).as_str()) {
+        if let Ok(commit_certificate) = binary::decode::<CommitCertificate>(&binary_commit_certificate) {
+            let transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction().clone();
+
+            return Some((transaction, Some(commit_certificate)));
+        }
+    }
+
+    let binary_transaction = db.get(format!("transaction.{}", txid_hex).as_str()).ok()??;
+
+    binary::decode::<Transaction>(&binary_transaction).ok().map(|transaction| (transaction, None))
+}
+
+fn to_node_responses(node_ids: &[u32], node_registry: &AHashMap<u32, [u8; 48]>) -> Vec<GetCommitteeNodeResponse> {
+    node_ids.iter()
+    .map(|id| to_node_response(*id, node_registry))
+    .collect()
+}
+
+fn to_node_response(node_id: u32, node_registry: &AHashMap<u32, [u8; 48]>) -> GetCommitteeNodeResponse {
+    GetCommitteeNodeResponse::new(node_id, node_registry.get(&node_id).map(hex::encode))
+}
+
+fn failed_response(reason: &str) -> GetCommitteeResponse {
+    GetCommitteeResponse::new(false, Some(reason.to_string()), None, None, None, Vec::new(), None, Vec::new(), Vec::new(), Vec::new(), None, None)
+}
\ No newline at end of file
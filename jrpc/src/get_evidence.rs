@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use concilium_core::{db::DB, jrpc::evidence::{GetEvidenceRecordResponse, GetEvidenceRequest, GetEvidenceResponse}};
+use concilium_core_ext::{evidence::{EvidenceSupport, SignedSpendSupport}, jrpc::evidence::{GetEvidenceRecordResponseSupport, GetEvidenceResponseSupport}, transaction::transaction::TransactionSupport};
+use concilium_shared::binary;
+use concilium_transaction::evidence::get_evidences_by_node_id;
+use jsonrpsee::types::{ErrorObject, Params};
+
+// the evidence this node holds against a node, detected here or received from a peer
+pub async fn handler(params: Params<'_>, db: Arc<DB>) -> Result<GetEvidenceResponse, ErrorObject<'static>> {
+    let request: GetEvidenceRequest = match params.parse() {
+        Ok(data) => data,
+        Err(_) => {
+            return Ok(failed_response(0));
+        }
+    };
+
+    let evidences = match get_evidences_by_node_id(request.node_id, &db) {
+        Ok(data) => data,
+        Err(_) => return Ok(failed_response(request.node_id))
+    };
+
+    let mut records = Vec::new();
+    for evidence in evidences {
+        let (Ok(id), Ok(binary_evidence)) = (evidence.get_id(), binary::encode(&evidence)) else {
+            return Ok(failed_response(request.node_id));
+        };
+
+        records.push(GetEvidenceRecordResponse::new(
+            hex::encode(id),
+            hex::encode(evidence.get_txid()),
+            evidence.get_vout(),
+            hex::encode(evidence.get_first().get_transaction().get_txid()),
+            hex::encode(evidence.get_second().get_transaction().get_txid()),
+            evidence.get_detected_at(),
+            hex::encode(binary_evidence)
+        ));
+    }
+
+    Ok(GetEvidenceResponse::new(true, request.node_id, records))
+}
+
+fn failed_response(node_id: u32) -> GetEvidenceResponse {
+    GetEvidenceResponse::new(false, node_id, Vec::new())
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+use concilium_core::{db::DB, jrpc::chain_state::{GetStateRootRequest, GetStateRootResponse}};
+use concilium_core_ext::{chain_state::StateCommitmentSupport, jrpc::chain_state::GetStateRootResponseSupport};
+use concilium_transaction::state::get_state_commitment;
+use jsonrpsee::types::{ErrorObject, Params};
+
+pub async fn handler(params: Params<'_>, db: Arc<DB>) -> Result<GetStateRootResponse, ErrorObject<'static>> {
+    let request: GetStateRootRequest = match params.parse() {
+        Ok(data) => data,
+        Err(_) => {
+            return Ok(failed_response());
+        }
+    };
+
+    if let Ok(Some(state_commitment)) = get_state_commitment(request.epoch_number, &db) {
+        return Ok(
+            GetStateRootResponse::new(
+                true, 
+                Some(hex::encode(state_commitment.get_state_root())), 
+                state_commitment.get_diverged_node_ids().to_vec()
+            )
+        );
+    }
+
+    return Ok(failed_response());
+}
+
+// the node was not running when the epoch began
+fn failed_response() -> GetStateRootResponse {
+    GetStateRootResponse::new(false, None, Vec::new())
+}
\ No newline at end of file
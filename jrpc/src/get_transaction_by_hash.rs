@@ -1,6 +1,6 @@
 use std::sync::Arc;
-use concilium_core::{db::DB, jrpc::transaction::{GetTransactionByHash, GetTransactionByHashRequest, GetTransactionByHashResponse, GetTransactionByHashTXInput, GetTransactionByHashTXOutput}, transaction::Transaction};
-use concilium_core_ext::{db::DBSupport, transaction::{get_transaction_by_hash::{response::GetTransactionByHashResponseSupport, transaction::GetTransactionByHashSupport, txinput::GetTransactionByHashTXInputSupport, txoutput::GetTransactionByHashTXOutputSupport}, transaction::TransactionSupport}};
+use concilium_core::{db::DB, jrpc::transaction::{GetTransactionByHash, GetTransactionByHashRequest, GetTransactionByHashResponse, GetTransactionByHashTXInput, GetTransactionByHashTXOutput}, transaction::{Transaction, TransactionKind}};
+use concilium_core_ext::{amount::AmountSupport, db::DBSupport, transaction::{get_transaction_by_hash::{response::GetTransactionByHashResponseSupport, transaction::GetTransactionByHashSupport, txinput::GetTransactionByHashTXInputSupport, txoutput::GetTransactionByHashTXOutputSupport}, transaction::TransactionSupport}};
 use concilium_shared::binary;
 use jsonrpsee::types::{ErrorObject, Params};
 
@@ -24,9 +24,15 @@ pub async fn handler(params: Params<'_>, db: Arc<DB>) -> Result<GetTransactionBy
                 }
                 
                 for item in transaction.get_vout() {
-                    vout.push(GetTransactionByHashTXOutput::new(item.value, hex::encode(item.public_key.clone())));
+                    vout.push(GetTransactionByHashTXOutput::new(item.value.to_decimal_string(), hex::encode(item.public_key.clone())));
                 }
 
+                let (stake, unstake) = match transaction.get_kind() {
+                    TransactionKind::Transfer => (None, None),
+                    TransactionKind::Stake { node_public_key } => (Some(hex::encode(node_public_key)), None),
+                    TransactionKind::Unstake { txid, vout } => (None, Some(GetTransactionByHashTXInput::new(hex::encode(txid), *vout)))
+                };
+
                 let transaction = GetTransactionByHash::new(
                     hex::encode(transaction.get_txid().clone()), 
                     hex::encode(transaction.get_from().clone()), 
@@ -34,7 +40,9 @@ pub async fn handler(params: Params<'_>, db: Arc<DB>) -> Result<GetTransactionBy
                     transaction.get_nonce(), 
                     transaction.get_created_at(), 
                     vin, 
-                    vout
+                    vout,
+                    stake,
+                    unstake
                 );
 
                 return Ok(GetTransactionByHashResponse::new(true, Some(transaction)));
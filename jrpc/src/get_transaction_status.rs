@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use concilium_core::{db::DB, jrpc::transaction::{GetTransactionStatusRequest, GetTransactionStatusResponse}};
+use concilium_core_ext::transaction::{get_transaction_status::GetTransactionStatusResponseSupport, transaction_status::{TransactionStateSupport, TransactionStatusSupport}};
+use concilium_transaction::status::get_transaction_status;
+use jsonrpsee::types::{ErrorObject, Params};
+
+pub async fn handler(params: Params<'_>, db: Arc<DB>) -> Result<GetTransactionStatusResponse, ErrorObject<'static>> {
+    let request: GetTransactionStatusRequest = match params.parse() {
+        Ok(data) => data,
+        Err(_) => {
+            return Ok(failed_response());
+        }
+    };
+
+    let txid: [u8; 32] = match hex::decode(request.txid).ok().and_then(|data| data.try_into().ok()) {
+        Some(data) => data,
+        None => return Ok(failed_response())
+    };
+
+    if let Ok(Some(transaction_status)) = get_transaction_status(&txid, &db) {
+        return Ok(
+            GetTransactionStatusResponse::new(
+                true, 
+                Some(transaction_status.get_state().get_name().to_string()), 
+                transaction_status.get_reason().cloned(), 
+                Some(transaction_status.get_updated_at())
+            )
+        );
+    }
+
+    return Ok(failed_response());
+}
+
+// the node never saw the transaction
+fn failed_response() -> GetTransactionStatusResponse {
+    GetTransactionStatusResponse::new(false, None, None, None)
+}
\ No newline at end of file
@@ -1,4 +1,9 @@
 pub mod send_raw_transaction;
 pub mod get_transaction_by_hash;
 pub mod get_address_utxos;
-pub mod get_account_transactions;
\ No newline at end of file
+pub mod get_account_transactions;
+pub mod get_account_nonce;
+pub mod get_transaction_status;
+pub mod get_state_root;
+pub mod get_evidence;
+pub mod get_committee;
\ No newline at end of file
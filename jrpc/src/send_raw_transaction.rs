@@ -1,14 +1,13 @@
 use std::sync::Arc;
-use blst::min_pk::{AggregateSignature, SecretKey, Signature};
-use concilium_core::{db::DB, jrpc::transaction::{SendRawTransactionRequest, SendRawTransactionResponse}, mempool::Mempool, transaction::BroadcastTransactionTemp};
-use concilium_core_ext::{mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, send_raw_transaction_response::SendRawTransactionResponseSupport, transaction::TransactionSupport}};
-use concilium_proto_defs::transaction::{AccreditationCouncilResponse, BroadcastResponse};
-use concilium_shared::{binary, DST};
-use concilium_transaction::{get_accreditation_council_node, get_broadcast_node, get_leader, put_success_transaction_on_db, send_raw_transaction_request_to_transaction, validation::{validate_signature_and_txid, validate_utxo_exist_and_values}};
+use concilium_core::{db::DB, jrpc::transaction::{SendRawTransactionRequest, SendRawTransactionResponse}, mempool::Mempool, transaction::{CommitCertificate, Transaction, TransactionState}};
+use concilium_core_ext::{config::ConfigSupport, mempool::MempoolSupport, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, send_raw_transaction_response::SendRawTransactionResponseSupport, transaction::TransactionSupport}};
+use concilium_shared::binary;
+use concilium_transaction::{
+    coordinator::{DbLedgerStore, LeadError, TransactionCoordinator}, get_active_nodes_by_ids, get_leader_ids, send_raw_transaction_request_to_transaction, status::put_transaction_status, validation::validate_transaction
+};
 use jsonrpsee::types::{ErrorObject, Params};
-use concilium_rpc::transaction::client::ClientSupport;
-use tokio::task::JoinSet;
-use rayon::prelude::*;
+use concilium_rpc::transaction::{batch::enqueue_transaction, client::ClientSupport, transport::ClientTransport};
+use tokio::time::timeout;
 
 pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<SendRawTransactionResponse, ErrorObject<'static>> {
     let trx_request: SendRawTransactionRequest = match params.parse() {
@@ -21,212 +20,114 @@ pub async fn handler(params: Params<'_>, mempool: Arc<Mempool>, db: Arc<DB>) ->
         Err(_) => return Err(error_response(-32602, "invalid params(convert to Transaction is failed)"))
     };
     
-    if validate_signature_and_txid(&transaction, false) == false {
-        return Err(error_response(-32602, "invalid signature"));
+    // the txid was computed from the request, so only the signature over it is checked
+    if let Err(e) = validate_transaction(&transaction, false, Arc::clone(&mempool)).await {
+        put_transaction_status(transaction.get_txid(), TransactionState::Rejected, Some(e.get_message()), &db).ok();
+        return Err(error_response(-32602, e.get_message()));
     }
     
-    if let Ok(result) = get_leader(&transaction, Arc::clone(&mempool)).await {
-        if let Some(node) = result {
-            let transaction_binary = match binary::encode(&transaction) {
-                Ok(data) => data,
-                Err(_) => return Err(error_response(-32602, "transaction to binary is failed - leader"))
-            };
-            
-            let response = match node.transaction_client.leader_request(
-                node.get_id(),
-                transaction.get_nonce(),
-                &transaction_binary
-            ).await {
-                Ok(data) => data,
-                Err(_) => return Err(error_response(-32602, "send to leader request is failed"))
-            };
-
-            match response.await {
-                Ok(data) => {
-                    if data.status == true {
-                        return Ok(
-                            successful_transaction(
-                                transaction.get_txid().clone(), 
-                                data.accreditation_council_aggregated_signature.try_into().unwrap_or([0; 96]), 
-                                data.broadcast_aggregated_signature.try_into().unwrap_or([0; 96])
-                            ).await
-                        );
-                    }
-                    
-                    return Err(error_response(-32602, "transaction is failed"))
-                },
-                Err(_) => {
-                    return Err(error_response(-32602, "transaction is failed(request error)"))
-                }
-            }
-        }
-    } else {
-        return Err(error_response(-32602, "find leader is failed"))
-    }
-    
-    let self_node_private_key = {
+    let leader_ids = match get_leader_ids(&transaction, Arc::clone(&mempool)).await {
+        Ok(data) => data,
+        Err(_) => return Err(error_response(-32602, "find leader is failed"))
+    };
+
+    let self_node_id = {
         let lock = mempool.get_self_node();
         let self_node = lock.read().await;
 
-        self_node.get_private_key().clone()
+        self_node.get_id()
     };
-    
-    if validate_utxo_exist_and_values(&transaction, Arc::clone(&mempool)).await == false {        
-        return Err(error_response(-32602, "utxo is invaild"))
-    }
-    
-    let transaction_nonce = transaction.get_nonce();
 
-    let binary_transaction = match binary::encode(&transaction) {
-        Ok(data) => Arc::new(data),
-        Err(_) => return Err(error_response(-32602, "transaction to binary is failed"))
-    };
-    let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
+    let transaction_binary = match binary::encode(&transaction) {
         Ok(data) => data,
-        Err(_) => return Err(error_response(-32602, "invalid private key"))
+        Err(_) => return Err(error_response(-32602, "transaction to binary is failed - leader"))
     };
-    let binary_transaction_clone = Arc::clone(&binary_transaction);
-    let sign_transaction = private_key.sign(&binary_transaction_clone, DST, &[]);
 
-    let accreditation_council_node = match get_accreditation_council_node(&transaction, Arc::clone(&mempool)).await {
-        Ok(data) => data,
-        Err(_) => return Err(error_response(-32602, "send to accreditation council request is failed"))
-    };
-    let mut set = JoinSet::new();
-    
-    for node in accreditation_council_node {
-        let binary = Arc::clone(&binary_transaction);
-        set.spawn(async move {
-            if let Ok(result) = node.get_transaction_client().accreditation_council_request(node.get_id(), transaction_nonce, &binary).await {
-                if let Ok(response) = result.await {
-                    response
-                } else {
-                    AccreditationCouncilResponse {
-                        request_id: Vec::new(),
-                        status: false,
-                        signature: Vec::new()
-                    }
-                }
-            } else {
-                AccreditationCouncilResponse {
-                    request_id: Vec::new(),
-                    status: false,
-                    signature: Vec::new()
-                }
-            }
-        });
+    // a transaction led by another node is committed here once its leader sends the save request
+    if !leader_ids.contains(&self_node_id) {
+        put_transaction_status(transaction.get_txid(), TransactionState::Received, None, &db).ok();
     }
 
-    let mut accreditation_council_signatures: Vec<Signature> = Vec::new();
-    while let Some(data) = set.join_next().await {
-        match data {
-            Ok(response ) => {
-                if response.status == true {
-                    if let Ok(signature) = Signature::from_bytes(&response.signature) {
-                        accreditation_council_signatures.push(signature);
-                    } else {
-                        return Err(error_response(-32602, "accreditation council node can not loaded signature"));
-                    }
-                } else {
-                    return Err(error_response(-32602, "accreditation council node not signed transaction"))
+    // the candidates take over in order, only an unreachable or silent leader is skipped
+    let mut last_error = error_response(-32602, "send to leader request is failed");
+    for leader_id in leader_ids {
+        if leader_id == self_node_id {
+            return lead_transaction_by_self(&transaction, mempool, db).await;
+        }
+
+        let node = match get_active_nodes_by_ids(&[leader_id], Arc::clone(&mempool)).await.pop() {
+            Some(data) => data,
+            None => continue
+        };
+
+        let response = match node.get_transaction_client().leader_request(
+            node.get_id(),
+            transaction.get_nonce(),
+            &transaction_binary
+        ).await {
+            Ok(data) => data,
+            Err(_) => continue
+        };
+
+        match timeout(mempool.get_config().get_leader_request_timeout(), response).await {
+            Ok(Ok(data)) => {
+                if data.status == true {
+                    return Ok(
+                        successful_transaction(
+                            transaction.get_txid().clone(), 
+                            data.accreditation_council_aggregated_signature.try_into().unwrap_or([0; 96]), 
+                            data.broadcast_aggregated_signature.try_into().unwrap_or([0; 96]),
+                            data.accreditation_council_signers,
+                            data.broadcast_signers
+                        ).await
+                    );
                 }
+                
+                put_transaction_status(transaction.get_txid(), TransactionState::Rejected, Some("rejected by the leader"), &db).ok();
+                return Err(error_response(-32602, "transaction is failed"))
+            },
+            Ok(Err(_)) => {
+                last_error = error_response(-32602, "transaction is failed(request error)");
             },
-            Err(_) => return Err(error_response(-32602, "send for accreditation council node to signed is failed"))
+            Err(_) => {
+                last_error = error_response(-32000, "leader request timed out");
+            }
         }
-        
     }
 
-    accreditation_council_signatures.push(sign_transaction);
+    Err(last_error)
+}
 
-    let accreditation_council_signatures = accreditation_council_signatures.par_iter().collect::<Vec<&Signature>>();
-    let accreditation_council_aggregated_signature = match AggregateSignature::aggregate(&accreditation_council_signatures, false) {
-        Ok(data) => data,
-        Err(_) => return Err(error_response(-32602, "accreditation council aggregate signature is failed"))
-    };
-    
-    let broadcast_transaction_temp = BroadcastTransactionTemp::new(transaction.clone(), accreditation_council_aggregated_signature.to_signature().to_bytes());
-    let binary_broadcast_transaction_temp= match binary::encode(&broadcast_transaction_temp) {
-        Ok(data) => Arc::new(data),
-        Err(_) => return Err(error_response(-32602, "broadcast transaction temp to binary is failed"))
-    };
+async fn lead_transaction_by_self(transaction: &Transaction, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<SendRawTransactionResponse, ErrorObject<'static>> {
+    let coordinator = TransactionCoordinator::new(Arc::new(ClientTransport::new(Arc::clone(&mempool))), Arc::new(DbLedgerStore::new(Arc::clone(&mempool), db)), Arc::clone(&mempool));
 
-    let broadcast_node = match get_broadcast_node(&broadcast_transaction_temp, Arc::clone(&mempool)).await {
-        Ok(data) => data,
-        Err(_) => return Err(error_response(-32602, "send to broadcast node request is failed"))
-    };
-    let mut set = JoinSet::new();
-    
-    for node in broadcast_node {
-        let binary = Arc::clone(&binary_broadcast_transaction_temp);
-        set.spawn(async move {
-            if let Ok(result) = node.get_transaction_client().broadcast_request(node.get_id(), transaction_nonce, &binary).await {
-                if let Ok(response) = result.await {
-                    response
-                } else {
-                    BroadcastResponse {
-                        request_id: Vec::new(),
-                        status: false,
-                        signature: Vec::new(),
-                    }
-                }
-            } else {
-                BroadcastResponse {
-                    request_id: Vec::new(),
-                    status: false,
-                    signature: Vec::new(),
-                }
-            }
-        });
+    if let Err(e) = coordinator.admit(transaction, false).await {
+        return Err(lead_error_response(e));
     }
 
-    let mut broadcast_signatures: Vec<Signature> = Vec::new();
-    while let Some(data) = set.join_next().await {
-        match data {
-            Ok(response ) => {
-                if response.status == true {
-                    if let Ok(signature) = Signature::from_bytes(&response.signature) {
-                        broadcast_signatures.push(signature);
-                    } else {
-                        return Err(error_response(-32602, "broadcast node can not loaded signature"));
-                    }
-                } else {
-                    return Err(error_response(-32602, "broadcast node not signed transaction "))
-                }
-            },
-            Err(_) => return Err(error_response(-32602, "send for broadcast node to signed is failed"))
-        }
+    // the batch releases the reservation itself when the transaction is dropped
+    if mempool.get_config().is_batching_enabled() {
+        return match enqueue_transaction(transaction.clone(), Arc::clone(&mempool)).await.await {
+            Ok(Some(commit_certificate)) => Ok(successful_commit(&commit_certificate).await),
+            _ => Err(error_response(-32602, "transaction is failed"))
+        };
     }
 
-    let broadcast_signatures = broadcast_signatures.par_iter().collect::<Vec<&Signature>>();
-    let broadcast_aggregated_signature = match AggregateSignature::aggregate(&broadcast_signatures, false) {
-        Ok(data) => data,
-        Err(_) => return Err(error_response(-32602, "broadcast aggregate signature is failed"))
-    };
-
-    if let Err(_) = put_success_transaction_on_db(&transaction, Arc::clone(&mempool), Arc::clone(&db)).await {        
-        return Err(error_response(-32602, "internal error(save on db)"));
+    match coordinator.commit(transaction).await {
+        Ok(commit_certificate) => Ok(successful_commit(&commit_certificate).await),
+        Err(e) => Err(lead_error_response(e))
     }
+}
 
-    let active_nodes = {
-        let active_nodes_lock = mempool.get_active_nodes();
-        let active_nodes = active_nodes_lock.read().await;
-        active_nodes.get_nodes_by_id().clone()
-    };
-    for (_, node) in active_nodes {
-        let binary = Arc::clone(&binary_transaction);
-        tokio::spawn(async move {
-            node.get_transaction_client().save_request(node.get_id(), transaction_nonce, &binary).await.ok();
-        });
+// refused transactions are invalid params, a failed round is a server error
+fn lead_error_response(error: LeadError) -> ErrorObject<'static> {
+    match &error {
+        LeadError::Rejected(_) | LeadError::NotLeaderCandidate | LeadError::InputsReserved => error_response(-32602, error.get_message().as_str()),
+        _ => error_response(-32000, error.get_message().as_str())
     }
-
-    return Ok(successful_transaction(
-        transaction.get_txid().clone(), 
-        accreditation_council_aggregated_signature.to_signature().to_bytes(), 
-        broadcast_aggregated_signature.to_signature().to_bytes()).await
-    );
 }
 
-fn error_response(code: i32, message: &str) -> ErrorObject<'_> {
+fn error_response(code: i32, message: &str) -> ErrorObject<'static> {
     ErrorObject::owned(
         code,
         message,
@@ -234,11 +135,25 @@ fn error_response(code: i32, message: &str) -> ErrorObject<'_> {
     )
 }
 
-async fn successful_transaction(txid: [u8; 32], accreditation_council_aggregated_signature: [u8; 96], broadcast_aggregated_signature: [u8; 96]) -> SendRawTransactionResponse {
+async fn successful_transaction(txid: [u8; 32], accreditation_council_aggregated_signature: [u8; 96], broadcast_aggregated_signature: [u8; 96], accreditation_council_signers: Vec<u32>, broadcast_signers: Vec<u32>) -> SendRawTransactionResponse {
     SendRawTransactionResponse::new(
         true, 
         hex::encode(txid), 
         hex::encode(accreditation_council_aggregated_signature), 
         hex::encode(broadcast_aggregated_signature), 
+        accreditation_council_signers,
+        broadcast_signers,
     )
+}
+
+async fn successful_commit(commit_certificate: &CommitCertificate) -> SendRawTransactionResponse {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+
+    successful_transaction(
+        broadcast_transaction_temp.get_transaction().get_txid().clone(),
+        broadcast_transaction_temp.get_signature().clone(),
+        commit_certificate.get_broadcast_aggregated_signature().clone(),
+        broadcast_transaction_temp.get_accreditation_council_signers().clone(),
+        commit_certificate.get_broadcast_signers().clone()
+    ).await
 }
\ No newline at end of file
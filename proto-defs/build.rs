@@ -7,6 +7,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "epoch.proto",
         "connection.proto",
         "transaction.proto",
+        "reconciliation.proto",
+        "checkpoint.proto",
+        "evidence.proto",
         ], 
         &[
             format!("{}/protos", env!("CARGO_MANIFEST_DIR"))
@@ -16,6 +16,10 @@ pub struct LeaderResponse {
     pub accreditation_council_aggregated_signature: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "4")]
     pub broadcast_aggregated_signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, repeated, tag = "5")]
+    pub accreditation_council_signers: ::prost::alloc::vec::Vec<u32>,
+    #[prost(uint32, repeated, tag = "6")]
+    pub broadcast_signers: ::prost::alloc::vec::Vec<u32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AccreditationCouncilRequest {
@@ -23,6 +27,8 @@ pub struct AccreditationCouncilRequest {
     pub request_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "2")]
     pub transaction: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub transaction_batch: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AccreditationCouncilResponse {
@@ -32,6 +38,8 @@ pub struct AccreditationCouncilResponse {
     pub status: bool,
     #[prost(bytes = "vec", tag = "3")]
     pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "4")]
+    pub rejected_txids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BroadcastRequest {
@@ -39,6 +47,8 @@ pub struct BroadcastRequest {
     pub request_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "2")]
     pub broadcast_transaction_temp: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub broadcast_batch_temp: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BroadcastResponse {
@@ -48,13 +58,15 @@ pub struct BroadcastResponse {
     pub status: bool,
     #[prost(bytes = "vec", tag = "3")]
     pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SaveRequest {
     #[prost(bytes = "vec", tag = "1")]
     pub request_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "2")]
-    pub transaction: ::prost::alloc::vec::Vec<u8>,
+    pub commit_certificate: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SaveResponse {
@@ -0,0 +1,58 @@
+use concilium_core::{checkpoint::{Checkpoint, SignedCheckpoint}, rpc::checkpoint::Client};
+use concilium_error::Error;
+use concilium_shared::binary;
+use tonic::Response;
+use concilium_proto_defs::checkpoint::{
+    GetCheckpointsRequest,
+    GetCheckpointsResponse,
+    PutCheckpointRequest,
+    PutCheckpointResponse,
+    SignCheckpointRequest,
+    SignCheckpointResponse,
+    checkpoint_client::CheckpointClient
+};
+
+#[tonic::async_trait]
+pub trait ClientSupport {
+    async fn connect(dst: &str) -> Result<Client, Error>;
+    async fn sign_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<Response<SignCheckpointResponse>, Error>;
+    async fn put_checkpoint(&mut self, signed_checkpoint: &SignedCheckpoint) -> Result<Response<PutCheckpointResponse>, Error>;
+    async fn get_checkpoints(&mut self, after_epoch_number: u64) -> Result<Response<GetCheckpointsResponse>, Error>;
+}
+
+#[tonic::async_trait]
+impl ClientSupport for Client {
+    async fn connect(dst: &str) -> Result<Client, Error> {
+        let client = CheckpointClient::connect(format!("http://{}", dst)).await?;
+
+        Ok(
+            Self {
+                client
+            }
+        )
+    }
+
+    async fn sign_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<Response<SignCheckpointResponse>, Error> {
+        Ok(
+            self.client.sign_checkpoint(SignCheckpointRequest {
+                checkpoint: binary::encode(checkpoint)?
+            }).await?
+        )
+    }
+
+    async fn put_checkpoint(&mut self, signed_checkpoint: &SignedCheckpoint) -> Result<Response<PutCheckpointResponse>, Error> {
+        Ok(
+            self.client.put_checkpoint(PutCheckpointRequest {
+                signed_checkpoint: binary::encode(signed_checkpoint)?
+            }).await?
+        )
+    }
+
+    async fn get_checkpoints(&mut self, after_epoch_number: u64) -> Result<Response<GetCheckpointsResponse>, Error> {
+        Ok(
+            self.client.get_checkpoints(GetCheckpointsRequest {
+                after_epoch_number
+            }).await?
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use blst::{min_pk::{AggregateSignature, PublicKey, SecretKey, Signature}, BLST_ERROR};
+use concilium_core::{checkpoint::{Checkpoint, SignedCheckpoint}, db::DB, mempool::Mempool, node::ActiveNode, rpc::checkpoint::Client};
+use concilium_core_ext::{
+    checkpoint::{CheckpointSupport, SignedCheckpointSupport}, config::ConfigSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, checkpoint::CHECKPOINT_COMMITTEE_SIZE, ip::ipv4_to_string, DST};
+use concilium_transaction::{
+    checkpoint::{build_checkpoint, get_last_checkpoint, put_checkpoint, verify_signed_checkpoint}, get_active_nodes_by_ids, get_epoch_snapshot_by_epoch_number, selection::{calculate_quorum, select_checkpoint_committee_node_ids, select_checkpoint_proposer_id}
+};
+use concilium_log as log;
+use tokio::{task::JoinSet, time::timeout};
+use super::client::ClientSupport;
+
+/*
+    Once per epoch the proposer of the checkpoint collects the signatures of its committee
+    and hands the signed checkpoint to every node. An epoch whose proposer is missing is not lost,
+    its transactions are part of the next checkpoint.
+*/
+pub async fn propose_checkpoint(committee_epoch_number: u64, mempool: Arc<Mempool>, db: Arc<DB>) {
+    if !mempool.is_synced() {
+        return;
+    }
+
+    let checkpoint = match build_checkpoint(committee_epoch_number, Arc::clone(&mempool), Arc::clone(&db)).await {
+        Ok(Some(data)) => data,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn(format!("checkpoint is not built: [{}]", e).as_str()).await.ok();
+            return;
+        }
+    };
+
+    let signed_checkpoint = match sign_checkpoint(&checkpoint, Arc::clone(&mempool)).await {
+        Ok(Some(data)) => data,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn(format!("checkpoint of epoch {} is not signed: [{}]", checkpoint.get_epoch_number(), e).as_str()).await.ok();
+            return;
+        }
+    };
+
+    if let Err(e) = put_checkpoint(&signed_checkpoint, Arc::clone(&mempool), Arc::clone(&db)).await {
+        log::warn(format!("checkpoint of epoch {} is not saved: [{}]", checkpoint.get_epoch_number(), e).as_str()).await.ok();
+        return;
+    }
+
+    let nodes: Vec<Arc<ActiveNode>> = {
+        let lock = mempool.get_active_nodes();
+        let active_nodes = lock.read().await;
+
+        active_nodes.get_nodes_by_id().values().map(Arc::clone).collect()
+    };
+
+    let signed_checkpoint = Arc::new(signed_checkpoint);
+    for node in nodes {
+        let signed_checkpoint = Arc::clone(&signed_checkpoint);
+        tokio::spawn(async move {
+            if let Ok(mut client) = Client::connect(&node_address(&node)).await {
+                client.put_checkpoint(&signed_checkpoint).await.ok();
+            }
+        });
+    }
+}
+
+// None when this node is not the proposer of the checkpoint
+async fn sign_checkpoint(checkpoint: &Checkpoint, mempool: Arc<Mempool>) -> Result<Option<SignedCheckpoint>, Error> {
+    let epoch_snapshot = get_epoch_snapshot_by_epoch_number(checkpoint.get_committee_epoch_number(), Arc::clone(&mempool)).await?;
+    let committee_node_ids = select_checkpoint_committee_node_ids(checkpoint, CHECKPOINT_COMMITTEE_SIZE, &epoch_snapshot)?;
+
+    let (self_node_id, self_node_private_key) = {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        (self_node.get_id(), self_node.get_private_key().clone())
+    };
+
+    if select_checkpoint_proposer_id(checkpoint, &committee_node_ids)? != self_node_id {
+        return Ok(None);
+    }
+
+    let private_key = SecretKey::from_bytes(self_node_private_key.as_slice())?;
+    let hash = Arc::new(checkpoint.get_hash()?);
+    let checkpoint = Arc::new(checkpoint.clone());
+
+    let other_node_ids: Vec<u32> = committee_node_ids.iter().copied().filter(|id| *id != self_node_id).collect();
+    let committee_nodes = get_active_nodes_by_ids(&other_node_ids, Arc::clone(&mempool)).await;
+
+    let request_timeout = mempool.get_config().get_request_timeout();
+    let mut set = JoinSet::new();
+
+    for node in committee_nodes {
+        let checkpoint = Arc::clone(&checkpoint);
+        let hash = Arc::clone(&hash);
+        set.spawn(async move {
+            let mut client = Client::connect(&node_address(&node)).await.ok()?;
+            let response = timeout(request_timeout, client.sign_checkpoint(&checkpoint)).await.ok()?.ok()?.into_inner();
+            if !response.status {
+                return None;
+            }
+
+            // a wrong signature would spoil the aggregate, so each one is checked on its own
+            let signature = Signature::from_bytes(&response.signature).ok()?;
+            let public_key = PublicKey::from_bytes(node.get_public_key()).ok()?;
+            if signature.verify(true, hash.as_slice(), DST, &[], &public_key, true) != BLST_ERROR::BLST_SUCCESS {
+                return None;
+            }
+
+            Some((node.get_id(), signature))
+        });
+    }
+
+    let mut signatures: Vec<Signature> = vec![private_key.sign(hash.as_slice(), DST, &[])];
+    let mut signers: Vec<u32> = vec![self_node_id];
+
+    while let Some(result) = set.join_next().await {
+        if let Ok(Some((node_id, signature))) = result {
+            signatures.push(signature);
+            signers.push(node_id);
+        }
+    }
+
+    let quorum = calculate_quorum(committee_node_ids.len());
+    if signers.len() < quorum {
+        return Err(Error::new(format!("checkpoint quorum is not reached: [{}/{}]", signers.len(), quorum).as_str()));
+    }
+    signers.sort();
+
+    let signatures = signatures.iter().collect::<Vec<&Signature>>();
+    let aggregated_signature = AggregateSignature::aggregate(&signatures, false)?.to_signature().to_bytes();
+
+    Ok(Some(SignedCheckpoint::new((*checkpoint).clone(), signers, aggregated_signature)))
+}
+
+/*
+    Pulls the checkpoints a peer stored after the last one here, e.g. while this node was offline.
+    They are verified and stored in order, the first one that does not fit stops the round.
+*/
+pub async fn sync_checkpoints(node: &ActiveNode, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let mut client = Client::connect(&node_address(node)).await?;
+
+    loop {
+        let last_checkpoint = get_last_checkpoint(&db)?.ok_or(Error::new("genesis checkpoint not found"))?;
+
+        let response = client.get_checkpoints(last_checkpoint.get_checkpoint().get_epoch_number()).await?.into_inner();
+        if !response.status || response.signed_checkpoints.is_empty() {
+            return Ok(());
+        }
+
+        for binary_signed_checkpoint in response.signed_checkpoints {
+            let signed_checkpoint = binary::decode::<SignedCheckpoint>(&binary_signed_checkpoint)?;
+
+            verify_signed_checkpoint(&signed_checkpoint, Arc::clone(&mempool)).await?;
+            put_checkpoint(&signed_checkpoint, Arc::clone(&mempool), Arc::clone(&db)).await?;
+        }
+    }
+}
+
+fn node_address(node: &ActiveNode) -> String {
+    format!("{}:{}", ipv4_to_string(node.get_ip_address()), node.get_port())
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::{ops::Deref, sync::Arc};
+use blst::min_pk::SecretKey;
+use concilium_core::{checkpoint::{Checkpoint, SignedCheckpoint}, db::DB, mempool::Mempool, rpc::checkpoint::Server as OriginServer};
+use concilium_core_ext::{checkpoint::CheckpointSupport, db::DBSupport, mempool::MempoolSupport, node::self_node::SelfNodeSupport};
+use concilium_shared::{binary, checkpoint::CHECKPOINT_COMMITTEE_SIZE, DST};
+use concilium_transaction::{
+    checkpoint::{get_checkpoint, get_next_checkpoint_epoch_number, put_checkpoint, validate_checkpoint_proposal, verify_signed_checkpoint}, get_epoch_snapshot_by_epoch_number, selection::select_checkpoint_committee_node_ids
+};
+use concilium_error::Error;
+use concilium_log as log;
+use tonic::{Request, Response, Status};
+use concilium_proto_defs::checkpoint::{
+    GetCheckpointsRequest,
+    GetCheckpointsResponse,
+    PutCheckpointRequest,
+    PutCheckpointResponse,
+    SignCheckpointRequest,
+    SignCheckpointResponse,
+    checkpoint_server::Checkpoint as CheckpointServerSupport
+};
+
+const MAX_CHECKPOINTS_PER_RESPONSE: usize = 64;
+
+pub struct Server(pub OriginServer);
+
+impl Deref for Server {
+    type Target = OriginServer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub trait ServerSupport {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self;
+}
+
+impl ServerSupport for Server {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self {
+        Self(OriginServer { mempool, db })
+    }
+}
+
+#[tonic::async_trait]
+impl CheckpointServerSupport for Server {
+    async fn sign_checkpoint(&self, request: Request<SignCheckpointRequest>) -> Result<Response<SignCheckpointResponse>, Status> {
+        // a node that is still catching up can not tell which transactions are committed
+        if !self.mempool.is_synced() {
+            return Ok(sign_checkpoint_failed_response());
+        }
+
+        let checkpoint = match binary::decode::<Checkpoint>(&request.into_inner().checkpoint) {
+            Ok(data) => data,
+            Err(_) => return Ok(sign_checkpoint_failed_response())
+        };
+
+        match sign(&checkpoint, Arc::clone(&self.mempool), Arc::clone(&self.db)).await {
+            Ok(signature) => Ok(
+                Response::new(
+                    SignCheckpointResponse {
+                        status: true,
+                        signature: signature.to_vec()
+                    }
+                )
+            ),
+            Err(e) => {
+                log::warn(format!("checkpoint of epoch {} is not signed: [{}]", checkpoint.get_epoch_number(), e).as_str()).await.ok();
+                Ok(sign_checkpoint_failed_response())
+            }
+        }
+    }
+
+    async fn put_checkpoint(&self, request: Request<PutCheckpointRequest>) -> Result<Response<PutCheckpointResponse>, Status> {
+        let signed_checkpoint = match binary::decode::<SignedCheckpoint>(&request.into_inner().signed_checkpoint) {
+            Ok(data) => data,
+            Err(_) => return Ok(put_checkpoint_response(false))
+        };
+
+        if let Err(e) = verify_signed_checkpoint(&signed_checkpoint, Arc::clone(&self.mempool)).await {
+            log::warn(format!("checkpoint is not verified: [{}]", e).as_str()).await.ok();
+            return Ok(put_checkpoint_response(false));
+        }
+
+        // a checkpoint that does not follow the last one here is pulled again with the missing ones
+        match put_checkpoint(&signed_checkpoint, Arc::clone(&self.mempool), Arc::clone(&self.db)).await {
+            Ok(_) => Ok(put_checkpoint_response(true)),
+            Err(_) => Ok(put_checkpoint_response(false))
+        }
+    }
+
+    // the checkpoints stored after the given one, oldest first
+    async fn get_checkpoints(&self, request: Request<GetCheckpointsRequest>) -> Result<Response<GetCheckpointsResponse>, Status> {
+        let mut epoch_number = request.into_inner().after_epoch_number;
+
+        match get_checkpoint(epoch_number, &self.db) {
+            Ok(Some(_)) => {},
+            _ => return Ok(get_checkpoints_failed_response())
+        }
+
+        let mut signed_checkpoints = Vec::new();
+        while signed_checkpoints.len() < MAX_CHECKPOINTS_PER_RESPONSE {
+            epoch_number = match get_next_checkpoint_epoch_number(epoch_number, &self.db) {
+                Ok(Some(data)) => data,
+                _ => break
+            };
+
+            match self.db.get(format!("checkpoint.{}", epoch_number).as_str()) {
+                Ok(Some(data)) => signed_checkpoints.push(data),
+                _ => break
+            }
+        }
+
+        Ok(
+            Response::new(
+                GetCheckpointsResponse {
+                    status: true,
+                    signed_checkpoints
+                }
+            )
+        )
+    }
+}
+
+// a committee member signs a checkpoint it can rebuild from its own commits
+async fn sign(checkpoint: &Checkpoint, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<[u8; 96], Error> {
+    validate_checkpoint_proposal(checkpoint, Arc::clone(&mempool), db).await?;
+
+    let epoch_snapshot = get_epoch_snapshot_by_epoch_number(checkpoint.get_committee_epoch_number(), Arc::clone(&mempool)).await?;
+    let committee_node_ids = select_checkpoint_committee_node_ids(checkpoint, CHECKPOINT_COMMITTEE_SIZE, &epoch_snapshot)?;
+
+    let (self_node_id, self_node_private_key) = {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        (self_node.get_id(), self_node.get_private_key().clone())
+    };
+
+    if committee_node_ids.binary_search(&self_node_id).is_err() {
+        return Err(Error::new("node is not a member of the checkpoint committee"));
+    }
+
+    let private_key = SecretKey::from_bytes(self_node_private_key.as_slice())?;
+
+    Ok(private_key.sign(&checkpoint.get_hash()?, DST, &[]).to_bytes())
+}
+
+fn sign_checkpoint_failed_response() -> Response<SignCheckpointResponse> {
+    Response::new(
+        SignCheckpointResponse {
+            status: false,
+            signature: Vec::new()
+        }
+    )
+}
+
+fn put_checkpoint_response(status: bool) -> Response<PutCheckpointResponse> {
+    Response::new(
+        PutCheckpointResponse {
+            status
+        }
+    )
+}
+
+fn get_checkpoints_failed_response() -> Response<GetCheckpointsResponse> {
+    Response::new(
+        GetCheckpointsResponse {
+            status: false,
+            signed_checkpoints: Vec::new()
+        }
+    )
+}
\ No newline at end of file
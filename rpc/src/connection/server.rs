@@ -136,6 +136,13 @@ impl ConnectionServerSupport for Server {
             };
 
             if agg_sig.verify(false, &binary_message, DST, &[], &agg_pub, true) == BLST_ERROR::BLST_SUCCESS {
+                {
+                    let node_registry_lock = self.mempool.get_node_registry();
+                    let mut node_registry = node_registry_lock.write().await;
+
+                    node_registry.insert(new_node_id, new_node_public_key);
+                }
+
                 let mempool_clone = Arc::clone(&self.mempool);
 
                 tokio::spawn(async move {
@@ -190,10 +197,10 @@ impl ConnectionServerSupport for Server {
 
                                 if let Some(epoch) = epoch_pool_read.get(&epoch_number) {
                                     if epoch.get_last_node_id() < new_node_id {
-                                        epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), new_node_id, epoch.get_final_hash().clone(), epoch.get_random_numbers().to_vec(), epoch.get_hashes().clone())));        
+                                        epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), new_node_id, epoch.get_final_hash().clone(), epoch.get_randomness().clone(), epoch.get_hashes().clone(), epoch.get_exclusion_votes().clone(), epoch.get_exclusions().clone())));        
                                     }
                                 } else {
-                                    epoch_pool_write_guard.insert(epoch_number, Arc::new(Epoch::new(epoch_number, new_node_id, [0; 32], Vec::new(), AHashMap::new())));        
+                                    epoch_pool_write_guard.insert(epoch_number, Arc::new(Epoch::new(epoch_number, new_node_id, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));        
                                 };   
                                 epoch_pool_write_guard.publish();
                             }
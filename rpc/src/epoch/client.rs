@@ -1,5 +1,7 @@
 use std::sync::Arc;
-use concilium_core::rpc::epoch::Client;
+use chrono::Utc;
+use concilium_core::{node::NodeHealth, rpc::epoch::Client};
+use concilium_core_ext::node::node_health::NodeHealthSupport;
 use tokio::{
     sync::mpsc,
     task::JoinHandle
@@ -16,8 +18,9 @@ pub trait ClientSupport {
     fn get_initial_thread_handler(self) -> JoinHandle<()>;
     fn get_sync_thread_handler(self) -> JoinHandle<()>;
     fn get_client(&self) -> &EpochClient<Channel>;
-    async fn initial_request(&self, id: u64, random_data: u64) -> Result<(), Error>;
-    async fn sync_request(&self, id: u64, hash: &[u8; 32]) -> Result<(), Error>;
+    fn get_health(&self) -> &NodeHealth;
+    async fn initial_request(&self, id: u64, node_id: u32, signature: &[u8; 96], unhealthy_node_ids: &[u32], votes_signature: &[u8; 96]) -> Result<(), Error>;
+    async fn sync_request(&self, id: u64, hash: &[u8; 32], excluded_node_ids: &[u32]) -> Result<(), Error>;
 }
 
 #[tonic::async_trait]
@@ -43,13 +46,18 @@ impl ClientSupport for Client {
         let mut initial_stream: Streaming<EpochResponse> = initial_response.into_inner();
         let mut sync_stream: Streaming<EpochResponse> = sync_response.into_inner();
 
+        let health = Arc::new(NodeHealth::new(Utc::now().timestamp()));
+        let initial_health = Arc::clone(&health);
+        let sync_health = Arc::clone(&health);
+
         let initial_thread_handler = tokio::spawn(async move {
             while let Some(response) = initial_stream.next().await {
                 match response {
                     Ok(_data) => {
-                        // write code
+                        initial_health.record_success(None, Utc::now().timestamp());
                     },
                     Err(e) => {
+                        initial_health.record_failure();
                         log::error(e.message().to_string().as_str()).await.ok();
                     }
                 }
@@ -60,9 +68,10 @@ impl ClientSupport for Client {
             while let Some(response) = sync_stream.next().await {
                 match response {
                     Ok(_data) => {
-                        // write code           
+                        sync_health.record_success(None, Utc::now().timestamp());
                     },
                     Err(e) => {
+                        sync_health.record_failure();
                         log::error(e.message().to_string().as_str()).await.ok();
                     }
                 }
@@ -75,7 +84,8 @@ impl ClientSupport for Client {
                 initial_tx: Arc::new(initial_tx),
                 sync_tx: Arc::new(sync_tx),
                 initial_thread_handler,
-                sync_thread_handler
+                sync_thread_handler,
+                health
             }
         )
     }
@@ -92,23 +102,31 @@ impl ClientSupport for Client {
         &self.client
     }
 
-    async fn initial_request(&self, id: u64, random_data: u64) -> Result<(), Error> {
+    fn get_health(&self) -> &NodeHealth {
+        &self.health
+    }
+
+    async fn initial_request(&self, id: u64, node_id: u32, signature: &[u8; 96], unhealthy_node_ids: &[u32], votes_signature: &[u8; 96]) -> Result<(), Error> {
         Ok(
             self.initial_tx.send(
                 InitialRequest { 
                     id, 
-                    random_data 
+                    unhealthy_node_ids: unhealthy_node_ids.to_vec(),
+                    node_id,
+                    signature: signature.to_vec(),
+                    votes_signature: votes_signature.to_vec()
                 }
             ).await?
         )
     }
     
-    async fn sync_request(&self, id: u64, hash: &[u8; 32]) -> Result<(), Error> {
+    async fn sync_request(&self, id: u64, hash: &[u8; 32], excluded_node_ids: &[u32]) -> Result<(), Error> {
         Ok(
             self.sync_tx.send(
                 SyncRequest { 
                     id, 
-                    hash: hash.to_vec()
+                    hash: hash.to_vec(),
+                    excluded_node_ids: excluded_node_ids.to_vec()
                 }
             ).await?
         )
@@ -1,6 +1,7 @@
 use concilium_core::{epoch::Epoch, mempool::Mempool, rpc::epoch::Server as OriginServer};
-use concilium_core_ext::{epoch::{EpochSupport, EpochPoolSupport}, mempool::MempoolSupport};
+use concilium_core_ext::{epoch::{EpochSupport, EpochPoolSupport}, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::active_node::ActiveNodeSupport};
 use concilium_shared::epoch::current_epoch_number;
+use concilium_transaction::randomness::{verify_exclusion_votes, verify_randomness};
 use tonic::{Request, Response, Streaming, Status};
 use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
 use std::{ops::Deref, pin::Pin, sync::Arc};
@@ -51,38 +52,19 @@ impl EpochServerSupport for Server {
                 match request {
                     Ok(data) => {
                         let current_epoch_number = u64::try_from(current_epoch_number()).unwrap();
-                        if data.id == (current_epoch_number + 1) {
-                            {
-                                let epoch_pool_read = mempool.get_epoch_pool().get_read();
-
-                                if let Some(epoch) = epoch_pool_read.get(&data.id) {
-                                    let mut random_numbers = epoch.get_random_numbers().to_vec();
-                                    random_numbers.push(data.random_data);
-
-                                    let epoch_pool_write = mempool.get_epoch_pool().get_write();
-                                    let mut epoch_pool_write_guard = epoch_pool_write.lock().await;
-
-                                    epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), random_numbers, epoch.get_hashes().clone())));
-                                    epoch_pool_write_guard.publish();
-                                }; 
-                            }
-
-                            tx.send(
-                                Ok(
-                                    EpochResponse {
-                                        status: true
-                                    }
-                                )
-                            ).await.unwrap();
+                        let status = if data.id == (current_epoch_number + 1) {
+                            add_contribution(data, Arc::clone(&mempool)).await
                         } else {
-                            tx.send(
-                                Ok(
-                                    EpochResponse {
-                                        status: false
-                                    }
-                                )
-                            ).await.unwrap();
-                        }
+                            false
+                        };
+
+                        tx.send(
+                            Ok(
+                                EpochResponse {
+                                    status
+                                }
+                            )
+                        ).await.unwrap();
                     },
                     Err(e) => {
                         log::error(format!("connect to network error: [{}]", e.message()).as_str()).await.ok();
@@ -122,10 +104,14 @@ impl EpochServerSupport for Server {
                                         hashes.insert(hash, 1);
                                     }
 
+                                    // the ids are only a claim of the sender, they count once a quorum of the senders of the hash agrees
+                                    let mut exclusions = epoch.get_exclusions().clone();
+                                    *exclusions.entry((hash, data.excluded_node_ids)).or_insert(0) += 1;
+
                                     let epoch_pool_write = mempool.get_epoch_pool().get_write();
                                     let mut epoch_pool_write_guard = epoch_pool_write.lock().await;
 
-                                    epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), epoch.get_random_numbers().to_vec(), hashes)));
+                                    epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), epoch.get_randomness().clone(), hashes, epoch.get_exclusion_votes().clone(), exclusions)));
                                     epoch_pool_write_guard.publish();
                                 };
                             }
@@ -161,4 +147,56 @@ impl EpochServerSupport for Server {
             Response::new(Box::pin(out) as Self::SyncStream)
         )
     }
+}
+
+/*
+    Adds the randomness of a peer to the epoch, the unhealthy node ids it sent are counted as its votes.
+    Both the randomness and the votes have to verify against the public key of the peer, every peer contributes once.
+*/
+async fn add_contribution(request: InitialRequest, mempool: Arc<Mempool>) -> bool {
+    let public_key = {
+        let lock = mempool.get_active_nodes();
+        let active_nodes = lock.read().await;
+
+        match active_nodes.get_by_id(request.node_id) {
+            Some(node) => node.get_public_key().clone(),
+            None => return false
+        }
+    };
+
+    let epoch_pool_read = mempool.get_epoch_pool().get_read();
+    let epoch_pool_write = mempool.get_epoch_pool().get_write();
+    let mut epoch_pool_write_guard = epoch_pool_write.lock().await;
+
+    let previous_epoch = request.id.checked_sub(1).and_then(|number| epoch_pool_read.get(&number));
+    let (epoch, previous_final_hash) = match (epoch_pool_read.get(&request.id), previous_epoch) {
+        (Some(epoch), Some(previous_epoch)) => (epoch, previous_epoch.get_final_hash().clone()),
+        _ => return false
+    };
+
+    if epoch.get_randomness().contains_key(&request.node_id) {
+        return false;
+    }
+
+    let signature = match verify_randomness(request.id, &previous_final_hash, &request.signature, &public_key) {
+        Ok(data) => data,
+        Err(_) => return false
+    };
+
+    if verify_exclusion_votes(request.id, &previous_final_hash, &request.unhealthy_node_ids, &request.votes_signature, &public_key).is_err() {
+        return false;
+    }
+
+    let mut randomness = epoch.get_randomness().clone();
+    randomness.insert(request.node_id, signature);
+
+    let mut exclusion_votes = epoch.get_exclusion_votes().clone();
+    for node_id in request.unhealthy_node_ids {
+        *exclusion_votes.entry(node_id).or_insert(0) += 1;
+    }
+
+    epoch_pool_write_guard.update(epoch.get_id().clone(), Arc::new(Epoch::new(epoch.get_id().clone(), epoch.get_last_node_id().clone(), epoch.get_final_hash().clone(), randomness, epoch.get_hashes().clone(), exclusion_votes, epoch.get_exclusions().clone())));
+    epoch_pool_write_guard.publish();
+
+    true
 }
\ No newline at end of file
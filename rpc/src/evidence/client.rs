@@ -0,0 +1,41 @@
+use concilium_core::{evidence::Evidence, rpc::evidence::Client};
+use concilium_error::Error;
+use concilium_shared::binary;
+use tonic::Response;
+use concilium_proto_defs::evidence::{
+    PutEvidenceRequest,
+    PutEvidenceResponse,
+    evidence_client::EvidenceClient
+};
+
+#[tonic::async_trait]
+pub trait ClientSupport {
+    async fn connect(dst: &str) -> Result<Client, Error>;
+    async fn put_evidence(&mut self, evidence: &Evidence) -> Result<Response<PutEvidenceResponse>, Error>;
+    async fn put_binary_evidence(&mut self, evidence: &[u8]) -> Result<Response<PutEvidenceResponse>, Error>;
+}
+
+#[tonic::async_trait]
+impl ClientSupport for Client {
+    async fn connect(dst: &str) -> Result<Client, Error> {
+        let client = EvidenceClient::connect(format!("http://{}", dst)).await?;
+
+        Ok(
+            Self {
+                client
+            }
+        )
+    }
+
+    async fn put_evidence(&mut self, evidence: &Evidence) -> Result<Response<PutEvidenceResponse>, Error> {
+        self.put_binary_evidence(&binary::encode(evidence)?).await
+    }
+
+    async fn put_binary_evidence(&mut self, evidence: &[u8]) -> Result<Response<PutEvidenceResponse>, Error> {
+        Ok(
+            self.client.put_evidence(PutEvidenceRequest {
+                evidence: evidence.to_vec()
+            }).await?
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+use concilium_core::node::ActiveNode;
+use concilium_core_ext::node::active_node::ActiveNodeSupport;
+use concilium_shared::ip::ipv4_to_string;
+
+pub(crate) fn node_address(node: &ActiveNode) -> String {
+    format!("{}:{}", ipv4_to_string(node.get_ip_address()), node.get_port())
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::{ops::Deref, sync::Arc};
+use concilium_core::{db::DB, evidence::Evidence, mempool::Mempool, rpc::evidence::Server as OriginServer};
+use concilium_core_ext::evidence::EvidenceSupport;
+use concilium_shared::binary;
+use concilium_transaction::evidence::{put_evidence, verify_evidence};
+use concilium_log as log;
+use tonic::{Request, Response, Status};
+use concilium_proto_defs::evidence::{
+    PutEvidenceRequest,
+    PutEvidenceResponse,
+    evidence_server::Evidence as EvidenceServerSupport
+};
+
+pub struct Server(pub OriginServer);
+
+impl Deref for Server {
+    type Target = OriginServer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub trait ServerSupport {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self;
+}
+
+impl ServerSupport for Server {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self {
+        Self(OriginServer { mempool, db })
+    }
+}
+
+#[tonic::async_trait]
+impl EvidenceServerSupport for Server {
+    // the node that detected the evidence hands it to every node, so it is not passed on from here
+    async fn put_evidence(&self, request: Request<PutEvidenceRequest>) -> Result<Response<PutEvidenceResponse>, Status> {
+        let evidence = match binary::decode::<Evidence>(&request.into_inner().evidence) {
+            Ok(data) => data,
+            Err(_) => return Ok(put_evidence_response(false))
+        };
+
+        if let Err(e) = verify_evidence(&evidence, Arc::clone(&self.mempool)).await {
+            log::warn(format!("evidence is not verified: [{}]", e).as_str()).await.ok();
+            return Ok(put_evidence_response(false));
+        }
+
+        match put_evidence(&evidence, Arc::clone(&self.mempool), &self.db).await {
+            Ok(true) => {
+                log::warn(format!("node {} signed conflicting transactions", evidence.get_node_id()).as_str()).await.ok();
+                Ok(put_evidence_response(true))
+            },
+            Ok(false) => Ok(put_evidence_response(true)),
+            Err(_) => Ok(put_evidence_response(false))
+        }
+    }
+}
+
+fn put_evidence_response(status: bool) -> Response<PutEvidenceResponse> {
+    Response::new(
+        PutEvidenceResponse {
+            status
+        }
+    )
+}
\ No newline at end of file
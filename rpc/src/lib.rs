@@ -1,4 +1,7 @@
 pub mod connection;
 pub mod identifier;
 pub mod epoch;
-pub mod transaction;
\ No newline at end of file
+pub mod transaction;
+pub mod reconciliation;
+pub mod checkpoint;
+pub mod evidence;
\ No newline at end of file
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use concilium_core::{db::DB, mempool::Mempool, node::ActiveNode, rpc::reconciliation::Client, transaction::CommitCertificate};
+use concilium_core_ext::{
+    chain_state::ChainStateSupport, config::ConfigSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::active_node::ActiveNodeSupport, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, ip::ipv4_to_string, reconciliation::{bucket_digests, get_bucket, RECONCILIATION_BUCKETS}};
+use concilium_transaction::apply_commit_certificate;
+use concilium_log as log;
+use rand::{rng, Rng};
+use tokio::time::interval;
+use crate::checkpoint::proposal::sync_checkpoints;
+use super::client::ClientSupport;
+
+/*
+    Save requests can get lost, so once per interval the committed transactions are compared
+    with a random peer. Only the buckets whose digests differ are listed, and the transactions
+    missing here are pulled with their certificates and applied like a save request.
+    The peer pulls what it misses from this node in its own rounds.
+*/
+pub async fn reconciliation_handler(mempool: Arc<Mempool>, db: Arc<DB>) {
+    let config = mempool.get_config();
+    if !config.is_reconciliation_enabled() {
+        return;
+    }
+
+    let mut ticker = interval(config.get_reconciliation_interval());
+    loop {
+        ticker.tick().await;
+
+        let node = {
+            let lock = mempool.get_active_nodes();
+            let active_nodes = lock.read().await;
+
+            let nodes: Vec<&Arc<ActiveNode>> = active_nodes.get_nodes_by_id().values().collect();
+            if nodes.is_empty() {
+                continue;
+            }
+
+            Arc::clone(nodes[rng().random_range(0..nodes.len())])
+        };
+
+        if let Err(e) = reconcile(&node, Arc::clone(&mempool), Arc::clone(&db)).await {
+            log::warn(format!("reconciliation with node {} is failed: [{}]", node.get_id(), e).as_str()).await.ok();
+        }
+
+        // checkpoints are only handed out once, a node that missed one pulls it here
+        if let Err(e) = sync_checkpoints(&node, Arc::clone(&mempool), Arc::clone(&db)).await {
+            log::warn(format!("checkpoint sync with node {} is failed: [{}]", node.get_id(), e).as_str()).await.ok();
+        }
+    }
+}
+
+async fn reconcile(node: &ActiveNode, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let node_address = format!("{}:{}", ipv4_to_string(node.get_ip_address()), node.get_port());
+    let mut client = Client::connect(&node_address).await?;
+
+    let remote_digests = client.get_digest().await?.into_inner();
+    if !remote_digests.status || remote_digests.digests.len() != RECONCILIATION_BUCKETS {
+        return Err(Error::new("invalid digests"));
+    }
+
+    let local_digests = {
+        let lock = mempool.get_chain_state();
+        let chain_state = lock.read().await;
+
+        bucket_digests(chain_state.get_committed_txids().iter())
+    };
+
+    let mut missing_txids = Vec::new();
+    for (bucket, (local_digest, remote_digest)) in local_digests.iter().zip(remote_digests.digests.iter()).enumerate() {
+        if local_digest.as_slice() == remote_digest.as_slice() {
+            continue;
+        }
+
+        let response = client.get_bucket_txids(bucket as u32).await?.into_inner();
+        if !response.status {
+            continue;
+        }
+
+        let lock = mempool.get_chain_state();
+        let chain_state = lock.read().await;
+
+        for txid in response.txids {
+            let Ok(txid) = <[u8; 32]>::try_from(txid) else {
+                continue;
+            };
+
+            if get_bucket(&txid) == bucket && !chain_state.get_committed_txids().contains(&txid) {
+                missing_txids.push(txid);
+            }
+        }
+    }
+
+    if missing_txids.is_empty() {
+        return Ok(());
+    }
+
+    let mut commit_certificates = Vec::new();
+    for txids in missing_txids.chunks(mempool.get_config().get_max_reconciliation_certificates()) {
+        let response = client.get_commit_certificates(txids).await?.into_inner();
+        if !response.status {
+            continue;
+        }
+
+        for binary_commit_certificate in response.commit_certificates {
+            let Ok(commit_certificate) = binary::decode::<CommitCertificate>(&binary_commit_certificate) else {
+                continue;
+            };
+
+            // only the certificates that were asked for
+            if txids.contains(commit_certificate.get_broadcast_transaction_temp().get_transaction().get_txid()) {
+                commit_certificates.push(commit_certificate);
+            }
+        }
+    }
+
+    apply_commit_certificates(commit_certificates, mempool, db).await;
+
+    Ok(())
+}
+
+/*
+    A missing transaction can spend the outputs of another missing one, so the certificates are
+    applied in passes until one makes no progress. The rest is tried again in a later round.
+*/
+async fn apply_commit_certificates(mut commit_certificates: Vec<CommitCertificate>, mempool: Arc<Mempool>, db: Arc<DB>) {
+    let mut errors = Vec::new();
+    loop {
+        let count = commit_certificates.len();
+
+        let mut pending = Vec::new();
+        errors.clear();
+        for commit_certificate in commit_certificates {
+            if let Err(e) = apply_commit_certificate(&commit_certificate, Arc::clone(&mempool), Arc::clone(&db)).await {
+                errors.push((commit_certificate.get_broadcast_transaction_temp().get_transaction().get_txid().clone(), e));
+                pending.push(commit_certificate);
+            }
+        }
+
+        if pending.is_empty() || pending.len() == count {
+            break;
+        }
+        commit_certificates = pending;
+    }
+
+    for (txid, e) in errors {
+        log::warn(format!("reconciled transaction {} is not applied: [{}]", hex::encode(txid), e).as_str()).await.ok();
+    }
+}
\ No newline at end of file
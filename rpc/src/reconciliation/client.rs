@@ -0,0 +1,75 @@
+use concilium_core::rpc::reconciliation::Client;
+use concilium_error::Error;
+use tonic::{codec::Streaming, Response};
+use concilium_proto_defs::reconciliation::{
+    GetBucketTxidsRequest,
+    GetBucketTxidsResponse,
+    GetCommitCertificatesRequest,
+    GetCommitCertificatesResponse,
+    GetDigestRequest,
+    GetDigestResponse,
+    GetStateRootRequest,
+    GetStateRootResponse,
+    LedgerRequest,
+    LedgerResponse,
+    reconciliation_client::ReconciliationClient
+};
+
+#[tonic::async_trait]
+pub trait ClientSupport {
+    async fn connect(dst: &str) -> Result<Client, Error>;
+    async fn get_digest(&mut self) -> Result<Response<GetDigestResponse>, Error>;
+    async fn get_bucket_txids(&mut self, bucket: u32) -> Result<Response<GetBucketTxidsResponse>, Error>;
+    async fn get_commit_certificates(&mut self, txids: &[[u8; 32]]) -> Result<Response<GetCommitCertificatesResponse>, Error>;
+    async fn get_state_root(&mut self, epoch_number: u64) -> Result<Response<GetStateRootResponse>, Error>;
+    async fn ledger(&mut self) -> Result<Response<Streaming<LedgerResponse>>, Error>;
+}
+
+#[tonic::async_trait]
+impl ClientSupport for Client {
+    async fn connect(dst: &str) -> Result<Client, Error> {
+        let client = ReconciliationClient::connect(format!("http://{}", dst)).await?;
+
+        Ok(
+            Self {
+                client
+            }
+        )
+    }
+
+    async fn get_digest(&mut self) -> Result<Response<GetDigestResponse>, Error> {
+        Ok(
+            self.client.get_digest(GetDigestRequest {}).await?
+        )
+    }
+
+    async fn get_bucket_txids(&mut self, bucket: u32) -> Result<Response<GetBucketTxidsResponse>, Error> {
+        Ok(
+            self.client.get_bucket_txids(GetBucketTxidsRequest {
+                bucket
+            }).await?
+        )
+    }
+
+    async fn get_commit_certificates(&mut self, txids: &[[u8; 32]]) -> Result<Response<GetCommitCertificatesResponse>, Error> {
+        Ok(
+            self.client.get_commit_certificates(GetCommitCertificatesRequest {
+                txids: txids.iter().map(|txid| txid.to_vec()).collect()
+            }).await?
+        )
+    }
+
+    async fn get_state_root(&mut self, epoch_number: u64) -> Result<Response<GetStateRootResponse>, Error> {
+        Ok(
+            self.client.get_state_root(GetStateRootRequest {
+                epoch_number
+            }).await?
+        )
+    }
+
+    async fn ledger(&mut self) -> Result<Response<Streaming<LedgerResponse>>, Error> {
+        Ok(
+            self.client.ledger(LedgerRequest {}).await?
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use ahash::{AHashMap, AHashSet};
+use concilium_core::{db::DB, mempool::Mempool, node::ActiveNode, rpc::reconciliation::Client, transaction::CommitCertificate};
+use concilium_core_ext::{
+    chain_state::ChainStateSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, ip::ipv4_to_string, reconciliation::{bucket_digests, get_bucket, RECONCILIATION_BUCKETS}};
+use concilium_transaction::{apply_verified_commit_certificate, certificate::verify_commit_certificate_signatures, selection::calculate_quorum};
+use concilium_log as log;
+use rand::{rng, Rng};
+use tokio::time::{sleep, Duration};
+use tokio_stream::StreamExt;
+use super::client::ClientSupport;
+
+/*
+    A joining node streams the committed ledger from a random peer before it leads or accredits transactions.
+    The signatures of every certificate are checked against the known nodes, but whether the signers were
+    the selected committees can not be checked without the past epochs, so a transaction is applied only
+    once a quorum of the peers committed it too. What is committed meanwhile comes with save requests
+    or is pulled by the reconciliation.
+*/
+pub async fn ledger_sync_handler(mempool: Arc<Mempool>, db: Arc<DB>) {
+    while let Err(e) = sync_ledger(Arc::clone(&mempool), Arc::clone(&db)).await {
+        log::warn(format!("ledger sync is failed: [{}]", e).as_str()).await.ok();
+        sleep(Duration::from_secs(1)).await;
+    }
+
+    mempool.set_synced(true);
+}
+
+async fn sync_ledger(mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let mut node_public_keys = AHashMap::new();
+    {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        node_public_keys.insert(self_node.get_id(), self_node.get_public_key().clone());
+    }
+    let peers: Vec<Arc<ActiveNode>> = {
+        let lock = mempool.get_active_nodes();
+        let active_nodes = lock.read().await;
+
+        for (id, node) in active_nodes.get_nodes_by_id() {
+            node_public_keys.insert(*id, node.get_public_key().clone());
+        }
+
+        active_nodes.get_nodes_by_id().values().map(Arc::clone).collect()
+    };
+
+    // the first node of the network has nobody to catch up with
+    if peers.is_empty() {
+        return Ok(());
+    }
+
+    let source = &peers[rng().random_range(0..peers.len())];
+    let mut client = Client::connect(&node_address(source)).await?;
+    let mut stream = client.ledger().await?.into_inner();
+
+    let mut commit_certificates = Vec::new();
+    let mut staged_txids = AHashSet::new();
+    while let Some(response) = stream.next().await {
+        let commit_certificate = binary::decode::<CommitCertificate>(&response?.commit_certificate)?;
+        let txid = commit_certificate.get_broadcast_transaction_temp().get_transaction().get_txid().clone();
+
+        let is_committed = {
+            let lock = mempool.get_chain_state();
+            let chain_state = lock.read().await;
+
+            chain_state.get_committed_txids().contains(&txid)
+        };
+        if is_committed || staged_txids.contains(&txid) {
+            continue;
+        }
+
+        if let Err(e) = verify_commit_certificate_signatures(&commit_certificate, &node_public_keys) {
+            log::warn(format!("synced transaction {} is not verified: [{}]", hex::encode(txid), e).as_str()).await.ok();
+            continue;
+        }
+
+        staged_txids.insert(txid);
+        commit_certificates.push(commit_certificate);
+    }
+
+    if commit_certificates.is_empty() {
+        return Ok(());
+    }
+
+    let confirmations = confirm_txids(&staged_txids, &peers, Arc::clone(&mempool)).await;
+    let quorum = calculate_quorum(peers.len());
+
+    // the source streams in its commit order, so the inputs of a transaction are applied before it
+    for commit_certificate in commit_certificates {
+        let txid = commit_certificate.get_broadcast_transaction_temp().get_transaction().get_txid().clone();
+
+        let count = confirmations.get(&txid).copied().unwrap_or_default();
+        if count < quorum {
+            log::warn(format!("synced transaction {} is not confirmed: [{}/{}]", hex::encode(txid), count, quorum).as_str()).await.ok();
+            continue;
+        }
+
+        if let Err(e) = apply_verified_commit_certificate(&commit_certificate, Arc::clone(&mempool), Arc::clone(&db)).await {
+            log::warn(format!("synced transaction {} is not applied: [{}]", hex::encode(txid), e).as_str()).await.ok();
+        }
+    }
+
+    Ok(())
+}
+
+/*
+    Counts the peers that committed each staged transaction. A bucket whose digest matches the local one
+    with the staged transactions included confirms all of them, the others are listed and compared.
+*/
+async fn confirm_txids(staged_txids: &AHashSet<[u8; 32]>, peers: &[Arc<ActiveNode>], mempool: Arc<Mempool>) -> AHashMap<[u8; 32], usize> {
+    let local_digests = {
+        let lock = mempool.get_chain_state();
+        let chain_state = lock.read().await;
+
+        bucket_digests(chain_state.get_committed_txids().iter().chain(staged_txids.iter()))
+    };
+
+    let mut staged_buckets: Vec<Vec<[u8; 32]>> = vec![Vec::new(); RECONCILIATION_BUCKETS];
+    for txid in staged_txids {
+        staged_buckets[get_bucket(txid)].push(*txid);
+    }
+
+    let mut confirmations = AHashMap::new();
+    for peer in peers {
+        if let Err(e) = confirm_by_peer(peer, &local_digests, &staged_buckets, &mut confirmations).await {
+            log::warn(format!("ledger confirmation by node {} is failed: [{}]", peer.get_id(), e).as_str()).await.ok();
+        }
+    }
+
+    confirmations
+}
+
+async fn confirm_by_peer(peer: &ActiveNode, local_digests: &[[u8; 32]], staged_buckets: &[Vec<[u8; 32]>], confirmations: &mut AHashMap<[u8; 32], usize>) -> Result<(), Error> {
+    let mut client = Client::connect(&node_address(peer)).await?;
+
+    let remote_digests = client.get_digest().await?.into_inner();
+    if !remote_digests.status || remote_digests.digests.len() != RECONCILIATION_BUCKETS {
+        return Err(Error::new("invalid digests"));
+    }
+
+    for (bucket, txids) in staged_buckets.iter().enumerate() {
+        if txids.is_empty() {
+            continue;
+        }
+
+        if local_digests[bucket].as_slice() == remote_digests.digests[bucket].as_slice() {
+            for txid in txids {
+                *confirmations.entry(*txid).or_insert(0) += 1;
+            }
+            continue;
+        }
+
+        let response = client.get_bucket_txids(bucket as u32).await?.into_inner();
+        if !response.status {
+            continue;
+        }
+
+        let remote_txids: AHashSet<Vec<u8>> = response.txids.into_iter().collect();
+        for txid in txids {
+            if remote_txids.contains(txid.as_slice()) {
+                *confirmations.entry(*txid).or_insert(0) += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn node_address(node: &ActiveNode) -> String {
+    format!("{}:{}", ipv4_to_string(node.get_ip_address()), node.get_port())
+}
\ No newline at end of file
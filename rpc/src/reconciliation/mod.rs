@@ -0,0 +1,5 @@
+pub mod client;
+pub mod server;
+pub mod anti_entropy;
+pub mod ledger_sync;
+pub mod state_commitment;
\ No newline at end of file
@@ -0,0 +1,194 @@
+use std::{ops::Deref, pin::Pin, sync::Arc};
+use concilium_core::{db::DB, mempool::Mempool, rpc::reconciliation::Server as OriginServer};
+use concilium_core_ext::{chain_state::{ChainStateSupport, StateCommitmentSupport}, config::ConfigSupport, db::DBSupport, mempool::MempoolSupport};
+use concilium_shared::{coventor::vec::unsigned_int::vec_to_unsigned_int, reconciliation::{bucket_digests, get_bucket, RECONCILIATION_BUCKETS}};
+use concilium_transaction::state::get_state_commitment;
+use tonic::{Request, Response, Status};
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, wrappers::ReceiverStream};
+use concilium_proto_defs::reconciliation::{
+    GetBucketTxidsRequest,
+    GetBucketTxidsResponse,
+    GetCommitCertificatesRequest,
+    GetCommitCertificatesResponse,
+    GetDigestRequest,
+    GetDigestResponse,
+    GetStateRootRequest,
+    GetStateRootResponse,
+    LedgerRequest,
+    LedgerResponse,
+    reconciliation_server::Reconciliation as ReconciliationServerSupport
+};
+
+pub struct Server(pub OriginServer);
+
+impl Deref for Server {
+    type Target = OriginServer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub trait ServerSupport {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self;
+}
+
+impl ServerSupport for Server {
+    fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self {
+        Self(OriginServer { mempool, db })
+    }
+}
+
+type LedgerStream = Pin<Box<dyn Stream<Item = Result<LedgerResponse, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl ReconciliationServerSupport for Server {
+    type LedgerStream = LedgerStream;
+
+    async fn get_digest(&self, _request: Request<GetDigestRequest>) -> Result<Response<GetDigestResponse>, Status> {
+        let digests = {
+            let lock = self.mempool.get_chain_state();
+            let chain_state = lock.read().await;
+
+            bucket_digests(chain_state.get_committed_txids().iter())
+        };
+
+        Ok(
+            Response::new(
+                GetDigestResponse {
+                    status: true,
+                    digests: digests.iter().map(|digest| digest.to_vec()).collect()
+                }
+            )
+        )
+    }
+
+    async fn get_bucket_txids(&self, request: Request<GetBucketTxidsRequest>) -> Result<Response<GetBucketTxidsResponse>, Status> {
+        let bucket = request.into_inner().bucket as usize;
+        if bucket >= RECONCILIATION_BUCKETS {
+            return Ok(get_bucket_txids_failed_response());
+        }
+
+        let txids = {
+            let lock = self.mempool.get_chain_state();
+            let chain_state = lock.read().await;
+
+            chain_state.get_committed_txids().iter()
+            .filter(|txid| get_bucket(txid) == bucket)
+            .map(|txid| txid.to_vec())
+            .collect()
+        };
+
+        Ok(
+            Response::new(
+                GetBucketTxidsResponse {
+                    status: true,
+                    txids
+                }
+            )
+        )
+    }
+
+    // transactions without a certificate, like the genesis ones, are left out
+    async fn get_commit_certificates(&self, request: Request<GetCommitCertificatesRequest>) -> Result<Response<GetCommitCertificatesResponse>, Status> {
+        let txids = request.into_inner().txids;
+        if txids.len() > self.mempool.get_config().get_max_reconciliation_certificates() {
+            return Ok(get_commit_certificates_failed_response());
+        }
+
+        let mut commit_certificates = Vec::new();
+        for txid in txids {
+            if let Ok(Some(binary_commit_certificate)) = self.db.get(format!("commit_certificate.{}", hex::encode(txid)).as_str()) {
+                commit_certificates.push(binary_commit_certificate);
+            }
+        }
+
+        Ok(
+            Response::new(
+                GetCommitCertificatesResponse {
+                    status: true,
+                    commit_certificates
+                }
+            )
+        )
+    }
+
+    async fn get_state_root(&self, request: Request<GetStateRootRequest>) -> Result<Response<GetStateRootResponse>, Status> {
+        let epoch_number = request.into_inner().epoch_number;
+
+        match get_state_commitment(epoch_number, &self.db) {
+            Ok(Some(state_commitment)) => Ok(
+                Response::new(
+                    GetStateRootResponse {
+                        status: true,
+                        state_root: state_commitment.get_state_root().to_vec()
+                    }
+                )
+            ),
+            _ => Ok(get_state_root_failed_response())
+        }
+    }
+
+    // streams the certificates in commit order, so a joining node can apply them one after another
+    async fn ledger(&self, _request: Request<LedgerRequest>) -> Result<Response<Self::LedgerStream>, Status> {
+        let last_transaction_id = match self.db.get("last_transaction_id") {
+            Ok(Some(data)) => vec_to_unsigned_int::<u64>(&data).unwrap_or_default(),
+            _ => 0
+        };
+
+        let (tx, rx) = mpsc::channel(self.mempool.get_config().get_max_reconciliation_certificates());
+
+        let db = Arc::clone(&self.db);
+        tokio::spawn(async move {
+            for id in 1..=last_transaction_id {
+                let Ok(Some(txid)) = db.get(format!("transaction.id.{}", id).as_str()) else {
+                    continue;
+                };
+                // the genesis transactions are indexed by raw txids and have no certificate
+                let Ok(txid_hex) = String::from_utf8(txid) else {
+                    continue;
+                };
+
+                if let Ok(Some(commit_certificate)) = db.get(format!("commit_certificate.{}", txid_hex).as_str()) {
+                    if tx.send(Ok(LedgerResponse { commit_certificate })).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let out = ReceiverStream::new(rx);
+
+        Ok(
+            Response::new(Box::pin(out) as Self::LedgerStream)
+        )
+    }
+}
+
+fn get_bucket_txids_failed_response() -> Response<GetBucketTxidsResponse> {
+    Response::new(
+        GetBucketTxidsResponse {
+            status: false,
+            txids: Vec::new()
+        }
+    )
+}
+
+fn get_commit_certificates_failed_response() -> Response<GetCommitCertificatesResponse> {
+    Response::new(
+        GetCommitCertificatesResponse {
+            status: false,
+            commit_certificates: Vec::new()
+        }
+    )
+}
+
+fn get_state_root_failed_response() -> Response<GetStateRootResponse> {
+    Response::new(
+        GetStateRootResponse {
+            status: false,
+            state_root: Vec::new()
+        }
+    )
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use concilium_core::{chain_state::StateCommitment, db::DB, mempool::Mempool, node::ActiveNode, rpc::reconciliation::Client};
+use concilium_core_ext::{chain_state::StateCommitmentSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::active_node::ActiveNodeSupport};
+use concilium_error::Error;
+use concilium_shared::ip::ipv4_to_string;
+use concilium_transaction::{checkpoint::get_checkpoint_epoch_number, state::{compute_state_root, get_state_commitment, put_state_commitment}};
+use concilium_log as log;
+use super::client::ClientSupport;
+
+/*
+    The root of an epoch covers the transactions up to the epoch that is checkpointed in it. Their commits
+    had time to reach every node by then, so the root does not depend on when a node takes it.
+*/
+pub async fn commit_state_root(epoch_number: u64, mempool: Arc<Mempool>, db: Arc<DB>) {
+    let Some(checkpoint_epoch_number) = get_checkpoint_epoch_number(epoch_number) else {
+        return;
+    };
+
+    let state_root = match compute_state_root(checkpoint_epoch_number, mempool, &db).await {
+        Ok(data) => data,
+        Err(e) => {
+            log::error(format!("state root of epoch {} is not computed: [{}]", epoch_number, e).as_str()).await.ok();
+            return;
+        }
+    };
+
+    if let Err(e) = put_state_commitment(&StateCommitment::new(epoch_number, state_root, Vec::new()), &db) {
+        log::error(format!("state root of epoch {} is not saved: [{}]", epoch_number, e).as_str()).await.ok();
+    }
+}
+
+/*
+    Asks every peer for its root of the epoch. A peer without one, like a node that joined later,
+    is left out. The peers with another root are logged and kept with the local commitment.
+*/
+pub async fn compare_state_roots(epoch_number: u64, mempool: Arc<Mempool>, db: Arc<DB>) {
+    let mut state_commitment = match get_state_commitment(epoch_number, &db) {
+        Ok(Some(data)) => data,
+        _ => return
+    };
+
+    let nodes: Vec<Arc<ActiveNode>> = {
+        let lock = mempool.get_active_nodes();
+        let active_nodes = lock.read().await;
+
+        active_nodes.get_nodes_by_id().values().map(Arc::clone).collect()
+    };
+
+    let mut diverged_node_ids = Vec::new();
+    for node in nodes {
+        match get_remote_state_root(&node, epoch_number).await {
+            Ok(Some(state_root)) => {
+                if state_root != *state_commitment.get_state_root() {
+                    diverged_node_ids.push(node.get_id());
+                }
+            },
+            Ok(None) => {},
+            Err(e) => {
+                log::warn(format!("state root of node {} is not received: [{}]", node.get_id(), e).as_str()).await.ok();
+            }
+        }
+    }
+
+    if diverged_node_ids.is_empty() {
+        return;
+    }
+    diverged_node_ids.sort();
+
+    log::error(format!("state root of epoch {} diverged from nodes {:?}", epoch_number, diverged_node_ids).as_str()).await.ok();
+
+    state_commitment.set_diverged_node_ids(diverged_node_ids);
+    put_state_commitment(&state_commitment, &db).ok();
+}
+
+async fn get_remote_state_root(node: &ActiveNode, epoch_number: u64) -> Result<Option<[u8; 32]>, Error> {
+    let node_address = format!("{}:{}", ipv4_to_string(node.get_ip_address()), node.get_port());
+    let mut client = Client::connect(&node_address).await?;
+
+    let response = client.get_state_root(epoch_number).await?.into_inner();
+    if !response.status {
+        return Ok(None);
+    }
+
+    Ok(Some(response.state_root.try_into()?))
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use ahash::{AHashMap, AHashSet};
+use concilium_core::{db::DB, mempool::Mempool, transaction::{CommitCertificate, Transaction}};
+use concilium_core_ext::{config::ConfigSupport, mempool::MempoolSupport, transaction::transaction::TransactionSupport};
+use concilium_shared::epoch::timestamp_to_epoch_number;
+use concilium_transaction::coordinator::{DbLedgerStore, TransactionCoordinator};
+use tokio::{sync::oneshot::{self, Receiver, Sender}, time::interval};
+use super::transport::ClientTransport;
+
+/*
+    Hands a validated transaction, whose inputs are already reserved, to the next batch.
+    The receiver gets the commit certificate, or None once the reservation is released.
+*/
+pub async fn enqueue_transaction(transaction: Transaction, mempool: Arc<Mempool>) -> Receiver<Option<CommitCertificate>> {
+    let (tx, rx) = oneshot::channel();
+
+    let lock = mempool.get_batch_queue();
+    lock.lock().await.push((transaction, tx));
+
+    rx
+}
+
+/*
+    Leads the queued transactions once per batch window. The councils of a transaction
+    depend on its epoch, so every epoch gets batches of its own.
+*/
+pub async fn batch_handler(mempool: Arc<Mempool>, db: Arc<DB>) {
+    let config = mempool.get_config();
+    if !config.is_batching_enabled() {
+        return;
+    }
+
+    let coordinator = Arc::new(TransactionCoordinator::new(Arc::new(ClientTransport::new(Arc::clone(&mempool))), Arc::new(DbLedgerStore::new(Arc::clone(&mempool), db)), Arc::clone(&mempool)));
+    let max_batch_size = config.get_max_batch_size();
+    let mut ticker = interval(config.get_batch_window());
+    loop {
+        ticker.tick().await;
+
+        let queued = {
+            let lock = mempool.get_batch_queue();
+            let mut queue = lock.lock().await;
+            std::mem::take(&mut *queue)
+        };
+
+        if queued.is_empty() {
+            continue;
+        }
+
+        let mut txids = AHashSet::new();
+        let mut batches: AHashMap<u64, Vec<(Transaction, Sender<Option<CommitCertificate>>)>> = AHashMap::new();
+        for (transaction, tx) in queued {
+            // the same transaction sent twice is only led once
+            if !txids.insert(transaction.get_txid().clone()) {
+                tx.send(None).ok();
+                continue;
+            }
+
+            let epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
+            batches.entry(epoch_number).or_default().push((transaction, tx));
+        }
+
+        for (epoch_number, mut queued) in batches {
+            while !queued.is_empty() {
+                let rest = queued.split_off(queued.len().min(max_batch_size));
+                let coordinator = Arc::clone(&coordinator);
+                tokio::spawn(async move {
+                    coordinator.lead_batch(epoch_number, queued).await;
+                });
+                queued = rest;
+            }
+        }
+    }
+}
\ No newline at end of file
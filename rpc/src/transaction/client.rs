@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{sync::{Arc, Weak}, time::Duration};
 use ahash::AHashMap;
 use concilium_core::rpc::transaction::Client;
 use rand::random;
 use tokio::{
     sync::{mpsc, oneshot::{self, Receiver}, RwLock},
-    task::JoinHandle
+    task::JoinHandle,
+    time::interval
 };
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 use tonic::{transport::Channel, Streaming};
@@ -24,14 +25,19 @@ pub trait ClientSupport {
     
     fn get_accreditation_council_thread_handler(self) -> JoinHandle<()>;
     async fn accreditation_council_request(&self, node_id: u32, nonce: u64, transaction: &[u8]) -> Result<Receiver<AccreditationCouncilResponse>, Error>;
+    async fn accreditation_council_batch_request(&self, node_id: u32, nonce: u64, transaction_batch: &[u8]) -> Result<Receiver<AccreditationCouncilResponse>, Error>;
     
     fn get_broadcast_thread_handler(self) -> JoinHandle<()>;
     async fn broadcast_request(&self, node_id: u32, nonce: u64, broadcast_transaction_temp: &[u8]) -> Result<Receiver<BroadcastResponse>, Error>;
+    async fn broadcast_batch_request(&self, node_id: u32, nonce: u64, broadcast_batch_temp: &[u8]) -> Result<Receiver<BroadcastResponse>, Error>;
     
     fn get_save_thread_handler(self) -> JoinHandle<()>;
-    async fn save_request(&self, node_id: u32, nonce: u64, transaction: &[u8]) -> Result<Receiver<SaveResponse>, Error>;
+    async fn save_request(&self, node_id: u32, nonce: u64, commit_certificate: &[u8]) -> Result<Receiver<SaveResponse>, Error>;
 }
 
+// how often answered, expired or cancelled request ids are dropped from the response states
+const RESPONSE_STATE_SWEEP_INTERVAL: Duration = Duration::from_secs(12);
+
 #[tonic::async_trait]
 impl ClientSupport for Client {
     async fn connect(dst: &str) -> Result<Self, Error> {
@@ -162,6 +168,25 @@ impl ClientSupport for Client {
             }
         });
 
+        let leader_response_state_weak = Arc::downgrade(&leader_response_state);
+        let accreditation_council_response_state_weak = Arc::downgrade(&accreditation_council_response_state);
+        let broadcast_response_state_weak = Arc::downgrade(&broadcast_response_state);
+        let save_response_state_weak = Arc::downgrade(&save_response_state);
+        tokio::spawn(async move {
+            let mut ticker = interval(RESPONSE_STATE_SWEEP_INTERVAL);
+            loop {
+                ticker.tick().await;
+
+                // the client is dropped, nothing left to sweep
+                if !sweep_response_state(&leader_response_state_weak).await
+                || !sweep_response_state(&accreditation_council_response_state_weak).await
+                || !sweep_response_state(&broadcast_response_state_weak).await
+                || !sweep_response_state(&save_response_state_weak).await {
+                    break;
+                }
+            }
+        });
+
         Ok(
             Self {
                 client,
@@ -202,12 +227,17 @@ impl ClientSupport for Client {
             state.insert(request_id, tx);
         }
 
-        self.leader_tx.send(
+        let result = self.leader_tx.send(
             LeaderRequest { 
                 request_id: request_id.to_vec(),
                 transaction: transaction.to_vec()
             }
-        ).await?;
+        ).await;
+
+        if let Err(e) = result {
+            self.leader_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
         
         Ok(rx)
     }
@@ -225,12 +255,43 @@ impl ClientSupport for Client {
             state.insert(request_id, tx);
         }
 
-        self.accreditation_council_tx.send(
+        let result = self.accreditation_council_tx.send(
             AccreditationCouncilRequest { 
                 request_id: request_id.to_vec(),
-                transaction: transaction.to_vec()
+                transaction: transaction.to_vec(),
+                transaction_batch: Vec::new()
             }
-        ).await?;
+        ).await;
+
+        if let Err(e) = result {
+            self.accreditation_council_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
+        
+        Ok(rx)
+    }
+
+    async fn accreditation_council_batch_request(&self, node_id: u32, nonce: u64, transaction_batch: &[u8]) -> Result<Receiver<AccreditationCouncilResponse>, Error> {
+        let (tx, rx) = oneshot::channel();
+        let request_id = store_u32_pair(node_id, nonce, random::<u32>());
+
+        {
+            let mut state = self.accreditation_council_response_state.write().await;
+            state.insert(request_id, tx);
+        }
+
+        let result = self.accreditation_council_tx.send(
+            AccreditationCouncilRequest { 
+                request_id: request_id.to_vec(),
+                transaction: Vec::new(),
+                transaction_batch: transaction_batch.to_vec()
+            }
+        ).await;
+
+        if let Err(e) = result {
+            self.accreditation_council_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
         
         Ok(rx)
     }
@@ -248,12 +309,43 @@ impl ClientSupport for Client {
             state.insert(request_id, tx);
         }
 
-        self.broadcast_tx.send(
+        let result = self.broadcast_tx.send(
+            BroadcastRequest { 
+                request_id: request_id.to_vec(),
+                broadcast_transaction_temp: broadcast_transaction_temp.to_vec(),
+                broadcast_batch_temp: Vec::new()
+            }
+        ).await;
+
+        if let Err(e) = result {
+            self.broadcast_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
+        
+        Ok(rx)
+    }
+
+    async fn broadcast_batch_request(&self, node_id: u32, nonce: u64, broadcast_batch_temp: &[u8]) -> Result<Receiver<BroadcastResponse>, Error> {
+        let (tx, rx) = oneshot::channel();
+        let request_id = store_u32_pair(node_id, nonce, random::<u32>());
+
+        {
+            let mut state = self.broadcast_response_state.write().await;
+            state.insert(request_id, tx);
+        }
+
+        let result = self.broadcast_tx.send(
             BroadcastRequest { 
                 request_id: request_id.to_vec(),
-                broadcast_transaction_temp: broadcast_transaction_temp.to_vec()
+                broadcast_transaction_temp: Vec::new(),
+                broadcast_batch_temp: broadcast_batch_temp.to_vec()
             }
-        ).await?;
+        ).await;
+
+        if let Err(e) = result {
+            self.broadcast_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
         
         Ok(rx)
     }
@@ -262,7 +354,7 @@ impl ClientSupport for Client {
         self.save_thread_handler
     }
     
-    async fn save_request(&self, node_id: u32, nonce: u64, transaction: &[u8]) -> Result<Receiver<SaveResponse>, Error> {
+    async fn save_request(&self, node_id: u32, nonce: u64, commit_certificate: &[u8]) -> Result<Receiver<SaveResponse>, Error> {
         let (tx, rx) = oneshot::channel();
         let request_id = store_u32_pair(node_id, nonce, random::<u32>());
 
@@ -271,17 +363,32 @@ impl ClientSupport for Client {
             state.insert(request_id, tx);
         }
 
-        self.save_tx.send(
+        let result = self.save_tx.send(
             SaveRequest { 
                 request_id: request_id.to_vec(),
-                transaction: transaction.to_vec()
+                commit_certificate: commit_certificate.to_vec()
             }
-        ).await?;
+        ).await;
+
+        if let Err(e) = result {
+            self.save_response_state.write().await.remove(&request_id);
+            return Err(e.into());
+        }
         
         Ok(rx)
     }
 }
 
+async fn sweep_response_state<T>(state: &Weak<RwLock<AHashMap<[u8; 16], oneshot::Sender<T>>>>) -> bool {
+    match state.upgrade() {
+        Some(state) => {
+            state.write().await.retain(|_, tx| !tx.is_closed());
+            true
+        },
+        None => false
+    }
+}
+
 fn store_u32_pair(node_id: u32, nonce: u64, random_number: u32) -> [u8; 16] {
     let mut bytes = [0u8; 16];
 
@@ -1,2 +1,4 @@
 pub mod client;
 pub mod server;
+pub mod batch;
+pub mod transport;
\ No newline at end of file
@@ -1,18 +1,23 @@
-use blst::min_pk::{AggregateSignature, SecretKey, Signature};
-use concilium_core::{db::DB, mempool::Mempool, rpc::transaction::Server as OriginServer, transaction::{BroadcastTransactionTemp, Transaction}};
-use concilium_core_ext::{mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, transaction::TransactionSupport}};
-use concilium_shared::{binary, DST};
-use concilium_transaction::{get_accreditation_council_node, get_broadcast_node, put_success_transaction_on_db, validation::{validate_signature_and_txid, validate_utxo_exist_and_values}};
-use rayon::prelude::*;
+use blst::min_pk::SecretKey;
+use concilium_core::{db::DB, mempool::Mempool, rpc::transaction::Server as OriginServer, transaction::{BroadcastBatchTemp, BroadcastTransactionTemp, CommitCertificate, Transaction, TransactionBatch, TransactionState}};
+use concilium_core_ext::{
+    config::ConfigSupport, mempool::MempoolSupport, node::self_node::SelfNodeSupport, transaction::{
+        broadcast_batch_temp::BroadcastBatchTempSupport, broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport, transaction_batch::TransactionBatchSupport
+    }
+};
+use concilium_shared::{binary, merkle::merkle_root, DST};
+use concilium_transaction::{
+    apply_commit_certificate, certificate::{verify_accreditation_council_signature, verify_broadcast_batch_temp}, coordinator::{DbLedgerStore, TransactionCoordinator}, get_accreditation_council_node_ids, reservation::{release_inputs, reserve_inputs}, status::put_transaction_status, validation::{validate_signature_and_txid, validate_structure, validate_transaction}
+};
 use tonic::{Request, Response, Streaming, Status};
 use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
 use std::{ops::Deref, pin::Pin, sync::Arc};
-use tokio::{sync::mpsc, task::JoinSet};
+use tokio::sync::mpsc;
 use concilium_log as log;
 use concilium_proto_defs::transaction::{
     transaction_server::Transaction as TransactionServerSupport, AccreditationCouncilRequest, AccreditationCouncilResponse, BroadcastRequest, BroadcastResponse, LeaderRequest, LeaderResponse, SaveRequest, SaveResponse
 };
-use super::client::ClientSupport;
+use super::{batch::enqueue_transaction, transport::ClientTransport};
 
 pub struct Server(pub OriginServer);
 
@@ -52,9 +57,9 @@ impl TransactionServerSupport for Server {
         let mut stream = request.into_inner();
         
         let mempool = Arc::clone(&self.mempool);
-        let db = Arc::clone(&self.db);
+        let coordinator = TransactionCoordinator::new(Arc::new(ClientTransport::new(Arc::clone(&self.mempool))), Arc::new(DbLedgerStore::new(Arc::clone(&self.mempool), Arc::clone(&self.db))), Arc::clone(&self.mempool));
         tokio::spawn(async move {
-            'outer: while let Some(request) = stream.next().await {
+            while let Some(request) = stream.next().await {
                 match request {
                     Ok(data) => {
                         let transaction = match binary::decode::<Transaction>(&data.transaction) {
@@ -65,209 +70,36 @@ impl TransactionServerSupport for Server {
                             }
                         };
 
-                        if validate_signature_and_txid(&transaction, true) == false {
+                        if let Err(e) = coordinator.admit(&transaction, true).await {
+                            log::warn(format!("rejected transaction: [{}]", e).as_str()).await.ok();
                             tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
                             continue;
                         }
 
-                        let self_node_private_key = {
-                            let lock = mempool.get_self_node();
-                            let self_node = lock.read().await;
-
-                            self_node.get_private_key().clone()
-                        };
-                        
-                        if validate_utxo_exist_and_values(&transaction, Arc::clone(&mempool)).await == false {
-                            tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                            continue;
-                        }
-                        let transaction_nonce = transaction.get_nonce();
-
-                        let binary_transaction = match binary::encode(&transaction) {
-                            Ok(data) => Arc::new(data),
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
-                        let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
-                            Ok(data) => data,
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
-                        let binary_transaction_clone = Arc::clone(&binary_transaction);
-                        let sign_transaction = private_key.sign(&binary_transaction_clone, DST, &[]);
-                        
-                        let accreditation_council_node = match get_accreditation_council_node(&transaction, Arc::clone(&mempool)).await {
-                            Ok(data) => data,
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
-                        let mut set = JoinSet::new();
-                        
-                        for node in accreditation_council_node {
-                            let binary = Arc::clone(&binary_transaction);
-                            set.spawn(async move {
-                                if let Ok(result) = node.get_transaction_client().accreditation_council_request(node.get_id(), transaction_nonce, &binary).await {
-                                    if let Ok(response) = result.await {
-                                        response
-                                    } else {
-                                        AccreditationCouncilResponse {
-                                            request_id: Vec::new(),
-                                            status: false,
-                                            signature: Vec::new()
-                                        }
-                                    }
-                                } else {
-                                    AccreditationCouncilResponse {
-                                        request_id: Vec::new(),
-                                        status: false,
-                                        signature: Vec::new()
-                                    }
-                                }
-                            });
-                        }
-
-                        let mut accreditation_council_signatures: Vec<Signature> = Vec::new();
-                        while let Some(result) = set.join_next().await {
-                            match result {
-                                Ok(response) => {
-                                    if response.status == true {
-                                        if let Ok(signature) = Signature::from_bytes(&response.signature) {
-                                            accreditation_council_signatures.push(signature);
-                                        } else {
-                                            tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                            continue 'outer;
-                                        }
-                                    } else {
-                                        tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                        continue 'outer;
-                                    }
-                                },
-                                Err(_) => {
-                                    tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                    continue 'outer;
-                                }
-                            }
-                            
-                        }
-
-                        accreditation_council_signatures.push(sign_transaction);
-
-                        let accreditation_council_signatures = accreditation_council_signatures.par_iter().collect::<Vec<&Signature>>();
-                        let accreditation_council_aggregated_signature = match AggregateSignature::aggregate(&accreditation_council_signatures, false) {
-                            Ok(data) => data,
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
-                        
-                        let broadcast_transaction_temp = BroadcastTransactionTemp::new(transaction.clone(), accreditation_council_aggregated_signature.to_signature().to_bytes());
-                        let binary_broadcast_transaction_temp= match binary::encode(&broadcast_transaction_temp) {
-                            Ok(data) => Arc::new(data),
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
+                        // the batch is led in the background, the stream keeps taking transactions meanwhile
+                        if mempool.get_config().is_batching_enabled() {
+                            let result = enqueue_transaction(transaction, Arc::clone(&mempool)).await;
+                            let tx = tx.clone();
+                            tokio::spawn(async move {
+                                let response = match result.await {
+                                    Ok(Some(commit_certificate)) => leader_successful_response(data.request_id, &commit_certificate),
+                                    _ => leader_failde_response(data.request_id)
+                                };
 
-                        let broadcast_node = match get_broadcast_node(&broadcast_transaction_temp, Arc::clone(&mempool)).await {
-                            Ok(data) => data,
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
-                            }
-                        };
-                        let mut set = JoinSet::new();
-                        
-                        for node in broadcast_node {
-                            let binary = Arc::clone(&binary_broadcast_transaction_temp);
-                            set.spawn(async move {
-                                if let Ok(result) = node.get_transaction_client().broadcast_request(node.get_id(), transaction_nonce, &binary).await {
-                                    if let Ok(response) = result.await {
-                                        response
-                                    } else {
-                                        BroadcastResponse {
-                                            request_id: Vec::new(),
-                                            status: false,
-                                            signature: Vec::new(),
-                                        }
-                                    }
-                                } else {
-                                    BroadcastResponse {
-                                        request_id: Vec::new(),
-                                        status: false,
-                                        signature: Vec::new(),
-                                    }
-                                }
+                                tx.send(Ok(response)).await.ok();
                             });
+                            continue;
                         }
 
-                        let mut broadcast_signatures: Vec<Signature> = Vec::new();
-                        while let Some(result) = set.join_next().await {
-                            match result {
-                                Ok(response) => {
-                                    if response.status == true {
-                                        if let Ok(signature) = Signature::from_bytes(&response.signature) {
-                                            broadcast_signatures.push(signature);
-                                        } else {
-                                            tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                            continue 'outer;
-                                        }
-                                    } else {
-                                        tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                        continue 'outer;
-                                    }
-                                },
-                                Err(_) => {
-                                    tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                    continue 'outer;
-                                }
-                            }
-                        }
-
-                        let broadcast_signatures = broadcast_signatures.par_iter().collect::<Vec<&Signature>>();
-                        let broadcast_aggregated_signature = match AggregateSignature::aggregate(&broadcast_signatures, false) {
-                            Ok(data) => data,
-                            Err(_) => {
-                                tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                                continue;
+                        let response = match coordinator.commit(&transaction).await {
+                            Ok(commit_certificate) => leader_successful_response(data.request_id, &commit_certificate),
+                            Err(e) => {
+                                log::warn(format!("transaction is failed: [{}]", e).as_str()).await.ok();
+                                leader_failde_response(data.request_id)
                             }
                         };
 
-                        if let Err(_) = put_success_transaction_on_db(&transaction, Arc::clone(&mempool), Arc::clone(&db)).await {
-                            tx.send(Ok(leader_failde_response(data.request_id.clone()))).await.ok();
-                            continue;
-                        }
-
-                        
-                        let active_nodes = {
-                            let active_nodes_lock = mempool.get_active_nodes();
-                            let active_nodes = active_nodes_lock.read().await;
-                            active_nodes.get_nodes_by_id().clone()
-                        };                 
-                        for (_, node) in active_nodes {
-                            let binary = Arc::clone(&binary_transaction);
-                            tokio::spawn(async move {
-                                node.get_transaction_client().save_request(node.get_id(), transaction_nonce, &binary).await.ok();
-                            });
-                        }
-
-                        tx.send(
-                            Ok(
-                                LeaderResponse {
-                                    request_id: data.request_id,
-                                    status: true,
-                                    accreditation_council_aggregated_signature: accreditation_council_aggregated_signature.to_signature().to_bytes().to_vec(),
-                                    broadcast_aggregated_signature: broadcast_aggregated_signature.to_signature().to_bytes().to_vec()
-                                }
-                            )
-                        ).await.ok();
+                        tx.send(Ok(response)).await.ok();
                     },
                     Err(e) => {
                         log::error(format!("connect to network error: [{}]", e.message()).as_str()).await.ok();
@@ -289,10 +121,23 @@ impl TransactionServerSupport for Server {
         let mut stream = request.into_inner();
         
         let mempool = Arc::clone(&self.mempool);
+        let db = Arc::clone(&self.db);
         tokio::spawn(async move {
             while let Some(request) = stream.next().await {
                 match request {
                     Ok(data) => {    
+                        // a node that is still catching up can not tell whether the inputs are spent
+                        if !mempool.is_synced() {
+                            tx.send(Ok(accreditation_council_failde_response(data.request_id.clone()))).await.ok();
+                            continue;
+                        }
+
+                        if !data.transaction_batch.is_empty() {
+                            let response = accredit_transaction_batch(data.request_id, &data.transaction_batch, Arc::clone(&mempool), Arc::clone(&db)).await;
+                            tx.send(Ok(response)).await.ok();
+                            continue;
+                        }
+
                         let transaction = match binary::decode::<Transaction>(&data.transaction) {
                             Ok(t) => t,
                             Err(_) => {
@@ -301,7 +146,8 @@ impl TransactionServerSupport for Server {
                             }
                         };
                         
-                        if validate_signature_and_txid(&transaction, true) == false {
+                        if let Err(e) = validate_transaction(&transaction, true, Arc::clone(&mempool)).await {
+                            log::warn(format!("rejected transaction: [{}]", e).as_str()).await.ok();
                             tx.send(Ok(accreditation_council_failde_response(data.request_id.clone()))).await.ok();
                             continue;
                         }
@@ -312,8 +158,8 @@ impl TransactionServerSupport for Server {
 
                             self_node.get_private_key().clone()
                         };
-                        
-                        if validate_utxo_exist_and_values(&transaction, Arc::clone(&mempool)).await == false {
+
+                        if reserve_inputs(&transaction, Arc::clone(&mempool)).await == false {
                             tx.send(Ok(accreditation_council_failde_response(data.request_id.clone()))).await.ok();
                             continue;
                         }
@@ -321,6 +167,7 @@ impl TransactionServerSupport for Server {
                         let binary_transaction = match binary::encode(&transaction) {
                             Ok(data) => data,
                             Err(_) =>  {
+                                release_inputs(&transaction, Arc::clone(&mempool)).await;
                                 tx.send(Ok(accreditation_council_failde_response(data.request_id.clone()))).await.ok();
                                 continue;
                             }
@@ -328,18 +175,21 @@ impl TransactionServerSupport for Server {
                         let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
                             Ok(data) => data,
                             Err(_) => {
+                                release_inputs(&transaction, Arc::clone(&mempool)).await;
                                 tx.send(Ok(accreditation_council_failde_response(data.request_id.clone()))).await.ok();
                                 continue;
                             }
                         };
                         let sign_transaction = private_key.sign(&binary_transaction, DST, &[]);
+                        put_transaction_status(transaction.get_txid(), TransactionState::Accredited, None, &db).ok();
                     
                         tx.send(
                             Ok(
                                 AccreditationCouncilResponse {
                                     request_id: data.request_id,
                                     status: true,
-                                    signature: sign_transaction.to_bytes().to_vec()
+                                    signature: sign_transaction.to_bytes().to_vec(),
+                                    rejected_txids: Vec::new()
                                 }
                             )
                         ).await.ok();
@@ -364,10 +214,36 @@ impl TransactionServerSupport for Server {
         let mut stream = request.into_inner();
         
         let mempool = Arc::clone(&self.mempool);
+        let db = Arc::clone(&self.db);
         tokio::spawn(async move {
             while let Some(request) = stream.next().await {
                 match request {
                     Ok(data) => {    
+                        if !data.broadcast_batch_temp.is_empty() {
+                            let response = sign_broadcast_batch_temp(data.request_id, &data.broadcast_batch_temp, Arc::clone(&mempool), Arc::clone(&db)).await;
+                            tx.send(Ok(response)).await.ok();
+                            continue;
+                        }
+
+                        let broadcast_transaction_temp = match binary::decode::<BroadcastTransactionTemp>(&data.broadcast_transaction_temp) {
+                            Ok(t) => t,
+                            Err(_) => {
+                                tx.send(Ok(broadcast_failde_response(data.request_id.clone(), "invalid broadcast transaction temp"))).await.ok();
+                                continue;
+                            }
+                        };
+
+                        let transaction = broadcast_transaction_temp.get_transaction();
+                        if let Err(e) = validate_structure(transaction).and_then(|_| validate_signature_and_txid(transaction, true)) {
+                            tx.send(Ok(broadcast_failde_response(data.request_id.clone(), e.get_message()))).await.ok();
+                            continue;
+                        }
+
+                        if let Err(e) = verify_accreditation_council_signature(&broadcast_transaction_temp, Arc::clone(&mempool)).await {
+                            tx.send(Ok(broadcast_failde_response(data.request_id.clone(), format!("invalid accreditation council signature: {}", e).as_str()))).await.ok();
+                            continue;
+                        }
+
                         let self_node_private_key = {
                             let lock = mempool.get_self_node();
                             let self_node = lock.read().await;
@@ -378,18 +254,28 @@ impl TransactionServerSupport for Server {
                         let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
                             Ok(data) => data,
                             Err(_) => {
-                                tx.send(Ok(broadcast_failde_response(data.request_id.clone()))).await.ok();
+                                tx.send(Ok(broadcast_failde_response(data.request_id.clone(), "invalid private key"))).await.ok();
+                                continue;
+                            }
+                        };
+                        // the signature covers exactly the temp that was checked, not the bytes it came in
+                        let binary_broadcast_transaction_temp = match binary::encode(&broadcast_transaction_temp) {
+                            Ok(data) => data,
+                            Err(_) => {
+                                tx.send(Ok(broadcast_failde_response(data.request_id.clone(), "invalid broadcast transaction temp"))).await.ok();
                                 continue;
                             }
                         };
-                        let sign = private_key.sign(&data.broadcast_transaction_temp, DST, &[]);
+                        let sign = private_key.sign(&binary_broadcast_transaction_temp, DST, &[]);
+                        put_transaction_status(transaction.get_txid(), TransactionState::Broadcast, None, &db).ok();
                     
                         tx.send(
                             Ok(
                                 BroadcastResponse {
                                     request_id: data.request_id,
                                     status: true,
-                                    signature: sign.to_bytes().to_vec()
+                                    signature: sign.to_bytes().to_vec(),
+                                    reason: String::new()
                                 }
                             )
                         ).await.ok();
@@ -419,7 +305,7 @@ impl TransactionServerSupport for Server {
             while let Some(request) = stream.next().await {
                 match request {
                     Ok(data) => {    
-                        let transaction = match binary::decode::<Transaction>(&data.transaction) {
+                        let commit_certificate = match binary::decode::<CommitCertificate>(&data.commit_certificate) {
                             Ok(t) => t,
                             Err(_) => {
                                 tx.send(Ok(save_failde_response(data.request_id.clone()))).await.ok();
@@ -427,7 +313,8 @@ impl TransactionServerSupport for Server {
                             }
                         };
 
-                        if let Err(_) = put_success_transaction_on_db(&transaction, Arc::clone(&mempool), Arc::clone(&db)).await {
+                        if let Err(e) = apply_commit_certificate(&commit_certificate, Arc::clone(&mempool), Arc::clone(&db)).await {
+                            log::warn(format!("rejected commit certificate: [{}]", e).as_str()).await.ok();
                             tx.send(Ok(save_failde_response(data.request_id.clone()))).await.ok();
                             continue;
                         }
@@ -457,12 +344,155 @@ impl TransactionServerSupport for Server {
     }
 }
 
+/*
+    Accredits the transactions of a batch this node is council for and signs the merkle root
+    of the whole batch only if every one of them passes. Otherwise their reservations are
+    released and the rejected txids are returned, so the leader can drop them and try again.
+*/
+async fn accredit_transaction_batch(request_id: Vec<u8>, binary_transaction_batch: &[u8], mempool: Arc<Mempool>, db: Arc<DB>) -> AccreditationCouncilResponse {
+    let transaction_batch = match binary::decode::<TransactionBatch>(binary_transaction_batch) {
+        Ok(t) => t,
+        Err(_) => return accreditation_council_failde_response(request_id)
+    };
+
+    let transactions = transaction_batch.get_transactions();
+    if transactions.is_empty() || transactions.len() > mempool.get_config().get_max_batch_size() {
+        return accreditation_council_failde_response(request_id);
+    }
+
+    let (self_node_id, self_node_private_key) = {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        (self_node.get_id(), self_node.get_private_key().clone())
+    };
+
+    let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
+        Ok(data) => data,
+        Err(_) => return accreditation_council_failde_response(request_id)
+    };
+
+    let mut accredited_transactions = Vec::new();
+    let mut rejected_txids = Vec::new();
+    for transaction in transactions {
+        // the rest of the batch is accredited by the councils of its own transactions
+        match get_accreditation_council_node_ids(transaction, Arc::clone(&mempool)).await {
+            Ok(node_ids) if node_ids.contains(&self_node_id) => {},
+            Ok(_) => continue,
+            Err(_) => {
+                rejected_txids.push(transaction.get_txid().to_vec());
+                continue;
+            }
+        }
+
+        if let Err(e) = validate_transaction(transaction, true, Arc::clone(&mempool)).await {
+            log::warn(format!("rejected transaction: [{}]", e).as_str()).await.ok();
+            rejected_txids.push(transaction.get_txid().to_vec());
+            continue;
+        }
+
+        if reserve_inputs(transaction, Arc::clone(&mempool)).await == false {
+            rejected_txids.push(transaction.get_txid().to_vec());
+            continue;
+        }
+
+        accredited_transactions.push(transaction);
+    }
+
+    if !rejected_txids.is_empty() || accredited_transactions.is_empty() {
+        for transaction in accredited_transactions {
+            release_inputs(transaction, Arc::clone(&mempool)).await;
+        }
+
+        return AccreditationCouncilResponse {
+            request_id: request_id,
+            status: false,
+            signature: Vec::new(),
+            rejected_txids: rejected_txids
+        };
+    }
+
+    let root = merkle_root(&transaction_batch.get_txids());
+    let sign_merkle_root = private_key.sign(&root, DST, &[]);
+
+    for transaction in accredited_transactions {
+        put_transaction_status(transaction.get_txid(), TransactionState::Accredited, None, &db).ok();
+    }
+
+    AccreditationCouncilResponse {
+        request_id: request_id,
+        status: true,
+        signature: sign_merkle_root.to_bytes().to_vec(),
+        rejected_txids: Vec::new()
+    }
+}
+
+// a broadcast node signs the batch temp, which every certificate of the batch carries
+async fn sign_broadcast_batch_temp(request_id: Vec<u8>, binary_broadcast_batch_temp: &[u8], mempool: Arc<Mempool>, db: Arc<DB>) -> BroadcastResponse {
+    let broadcast_batch_temp = match binary::decode::<BroadcastBatchTemp>(binary_broadcast_batch_temp) {
+        Ok(t) => t,
+        Err(_) => return broadcast_failde_response(request_id, "invalid broadcast batch temp")
+    };
+
+    if broadcast_batch_temp.get_transactions().len() > mempool.get_config().get_max_batch_size() {
+        return broadcast_failde_response(request_id, "batch is too large");
+    }
+
+    if let Err(e) = verify_broadcast_batch_temp(&broadcast_batch_temp, Arc::clone(&mempool)).await {
+        return broadcast_failde_response(request_id, format!("invalid batch: {}", e).as_str());
+    }
+
+    let self_node_private_key = {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        self_node.get_private_key().clone()
+    };
+
+    let private_key = match SecretKey::from_bytes(self_node_private_key.as_slice()) {
+        Ok(data) => data,
+        Err(_) => return broadcast_failde_response(request_id, "invalid private key")
+    };
+
+    let binary_batch_temp = match binary::encode(broadcast_batch_temp.get_batch_temp()) {
+        Ok(data) => data,
+        Err(_) => return broadcast_failde_response(request_id, "invalid batch temp")
+    };
+    let sign = private_key.sign(&binary_batch_temp, DST, &[]);
+
+    for transaction in broadcast_batch_temp.get_transactions() {
+        put_transaction_status(transaction.get_txid(), TransactionState::Broadcast, None, &db).ok();
+    }
+
+    BroadcastResponse {
+        request_id: request_id,
+        status: true,
+        signature: sign.to_bytes().to_vec(),
+        reason: String::new()
+    }
+}
+
+fn leader_successful_response(request_id: Vec<u8>, commit_certificate: &CommitCertificate) -> LeaderResponse {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+
+    LeaderResponse {
+        request_id: request_id,
+        status: true,
+        accreditation_council_aggregated_signature: broadcast_transaction_temp.get_signature().to_vec(),
+        broadcast_aggregated_signature: commit_certificate.get_broadcast_aggregated_signature().to_vec(),
+        accreditation_council_signers: broadcast_transaction_temp.get_accreditation_council_signers().clone(),
+        broadcast_signers: commit_certificate.get_broadcast_signers().clone()
+    }
+}
+
 fn leader_failde_response(request_id: Vec<u8>) -> LeaderResponse {
     LeaderResponse {
         request_id: request_id,
         status: false,
         accreditation_council_aggregated_signature: Vec::new(),
-        broadcast_aggregated_signature: Vec::new()
+        broadcast_aggregated_signature: Vec::new(),
+        accreditation_council_signers: Vec::new(),
+        broadcast_signers: Vec::new()
     }
 }
 
@@ -470,15 +500,17 @@ fn accreditation_council_failde_response(request_id: Vec<u8>) -> AccreditationCo
     AccreditationCouncilResponse {
         request_id: request_id,
         status: false,
-        signature: Vec::new()
+        signature: Vec::new(),
+        rejected_txids: Vec::new()
     }
 }
 
-fn broadcast_failde_response(request_id: Vec<u8>) -> BroadcastResponse {
+fn broadcast_failde_response(request_id: Vec<u8>, reason: &str) -> BroadcastResponse {
     BroadcastResponse {
         request_id: request_id,
         status: false,
-        signature: Vec::new()
+        signature: Vec::new(),
+        reason: reason.to_string()
     }
 }
 
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use concilium_core::{mempool::Mempool, rpc::evidence::Client as EvidenceClient};
+use concilium_core_ext::{mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::active_node::ActiveNodeSupport};
+use concilium_error::Error;
+use concilium_transaction::{coordinator::{PeerError, PeerTransport}, get_active_nodes_by_ids};
+use crate::evidence::{client::ClientSupport as EvidenceClientSupport, gossip::node_address};
+use super::client::ClientSupport;
+
+// reaches the peers through the transaction clients of the active nodes
+pub struct ClientTransport {
+    mempool: Arc<Mempool>,
+}
+
+impl ClientTransport {
+    pub fn new(mempool: Arc<Mempool>) -> Self {
+        Self {
+            mempool
+        }
+    }
+}
+
+impl PeerTransport for ClientTransport {
+    async fn get_peer_ids(&self) -> Vec<u32> {
+        let lock = self.mempool.get_active_nodes();
+        let active_nodes = lock.read().await;
+
+        active_nodes.get_nodes_by_id().keys().copied().collect()
+    }
+
+    async fn accreditation_council_request(&self, node_id: u32, nonce: u64, transaction: &[u8]) -> Result<Vec<u8>, PeerError> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected").into())
+        };
+
+        let response = node.get_transaction_client().accreditation_council_request(node_id, nonce, transaction).await?;
+        match response.await {
+            Ok(data) if data.status == true => Ok(data.signature),
+            Ok(_) => Err(PeerError::Refused("node refused to sign".to_string())),
+            Err(_) => Err(Error::new("no response").into())
+        }
+    }
+
+    async fn broadcast_request(&self, node_id: u32, nonce: u64, broadcast_transaction_temp: &[u8]) -> Result<Vec<u8>, PeerError> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected").into())
+        };
+
+        let response = node.get_transaction_client().broadcast_request(node_id, nonce, broadcast_transaction_temp).await?;
+        match response.await {
+            Ok(data) if data.status == true => Ok(data.signature),
+            Ok(data) => Err(PeerError::Refused(data.reason)),
+            Err(_) => Err(Error::new("no response").into())
+        }
+    }
+
+    async fn accreditation_council_batch_request(&self, node_id: u32, epoch_number: u64, transaction_batch: &[u8]) -> Result<Vec<u8>, PeerError> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected").into())
+        };
+
+        let response = node.get_transaction_client().accreditation_council_batch_request(node_id, epoch_number, transaction_batch).await?;
+        match response.await {
+            Ok(data) if data.status == true => Ok(data.signature),
+            Ok(data) => Err(PeerError::RejectedTransactions(data.rejected_txids.into_iter().filter_map(|txid| txid.try_into().ok()).collect())),
+            Err(_) => Err(Error::new("no response").into())
+        }
+    }
+
+    async fn broadcast_batch_request(&self, node_id: u32, epoch_number: u64, broadcast_batch_temp: &[u8]) -> Result<Vec<u8>, PeerError> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected").into())
+        };
+
+        let response = node.get_transaction_client().broadcast_batch_request(node_id, epoch_number, broadcast_batch_temp).await?;
+        match response.await {
+            Ok(data) if data.status == true => Ok(data.signature),
+            Ok(data) => Err(PeerError::Refused(data.reason)),
+            Err(_) => Err(Error::new("no response").into())
+        }
+    }
+
+    async fn save_request(&self, node_id: u32, nonce: u64, commit_certificate: &[u8]) -> Result<(), Error> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected"))
+        };
+
+        let response = node.get_transaction_client().save_request(node_id, nonce, commit_certificate).await?;
+        match response.await {
+            Ok(data) if data.status == true => Ok(()),
+            Ok(_) => Err(Error::new("node did not save the transaction")),
+            Err(_) => Err(Error::new("no response"))
+        }
+    }
+
+    async fn put_evidence_request(&self, node_id: u32, evidence: &[u8]) -> Result<(), Error> {
+        let node = match get_active_nodes_by_ids(&[node_id], Arc::clone(&self.mempool)).await.pop() {
+            Some(data) => data,
+            None => return Err(Error::new("node is not connected"))
+        };
+
+        let mut client = EvidenceClient::connect(&node_address(&node)).await?;
+        match client.put_binary_evidence(evidence).await?.into_inner() {
+            data if data.status == true => Ok(()),
+            _ => Err(Error::new("node did not save the evidence"))
+        }
+    }
+}
\ No newline at end of file
@@ -2,12 +2,15 @@ use ahash::AHashSet;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
+// distinct numbers from 1 to `to`, at most `to` of them are returned
 pub fn generate_random_number_by_seed(hash: [u8; 32], to: u32, how_many: u32) -> Vec<u32> {
+    let how_many = how_many.min(to) as usize;
+
     let mut rng = ChaCha20Rng::from_seed(hash);
-    let mut seen = AHashSet::with_capacity(how_many as usize);
-    let mut result = Vec::with_capacity(how_many as usize);
+    let mut seen = AHashSet::with_capacity(how_many);
+    let mut result = Vec::with_capacity(how_many);
 
-    while result.len() < how_many as usize {
+    while result.len() < how_many {
         let num = rng.random_range(1..=to);
         if seen.contains(&num) {
             continue;
@@ -18,3 +21,79 @@ pub fn generate_random_number_by_seed(hash: [u8; 32], to: u32, how_many: u32) ->
 
     result
 }
+
+/*
+    Distinct items drawn with a probability proportional to their weight, without replacement:
+    a drawn item leaves the pool with its weight, so every round draws a new item.
+    A larger how_many starts with the same items as a smaller one. At most every item with a weight is returned.
+*/
+pub fn generate_weighted_random_number_by_seed(hash: [u8; 32], weights: &[(u32, u64)], how_many: u32) -> Vec<u32> {
+    let mut remaining: Vec<(u32, u64)> = weights.iter().filter(|(_, weight)| *weight > 0).copied().collect();
+    let mut total_weight: u128 = remaining.iter().map(|(_, weight)| *weight as u128).sum();
+    let how_many = (how_many as usize).min(remaining.len());
+
+    let mut rng = ChaCha20Rng::from_seed(hash);
+    let mut result = Vec::with_capacity(how_many);
+
+    while result.len() < how_many {
+        let mut point = rng.random_range(0..total_weight);
+
+        let mut index = 0;
+        while point >= remaining[index].1 as u128 {
+            point -= remaining[index].1 as u128;
+            index += 1;
+        }
+
+        // the order of the remaining items stays the same, every node walks the same list
+        let (item, weight) = remaining.remove(index);
+        total_weight -= weight as u128;
+        result.push(item);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_numbers_stop_at_the_id_space() {
+        let numbers = generate_random_number_by_seed([7; 32], 5, 50);
+
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3, 4, 5]);
+        assert!(generate_random_number_by_seed([7; 32], 0, 3).is_empty());
+    }
+
+    #[test]
+    fn weighted_numbers_stop_at_the_weighted_items() {
+        let weights = [(3, 10), (900, 0), (70_000, 5)];
+        let numbers = generate_weighted_random_number_by_seed([7; 32], &weights, 10);
+
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![3, 70_000]);
+        assert!(generate_weighted_random_number_by_seed([7; 32], &[], 3).is_empty());
+    }
+
+    #[test]
+    fn skewed_weights_draw_every_item() {
+        let weights = [(1, 1), (2, 1_000_000 * 100_000_000), (3, 1), (4, u64::MAX)];
+        let numbers = generate_weighted_random_number_by_seed([7; 32], &weights, 4);
+
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn larger_draws_start_with_smaller_ones() {
+        let weights = [(1, 4), (17, 1), (250, 9), (4_096, 2), (65_000, 7)];
+        let small = generate_weighted_random_number_by_seed([9; 32], &weights, 2);
+        let large = generate_weighted_random_number_by_seed([9; 32], &weights, 4);
+
+        assert_eq!(small[..], large[..2]);
+    }
+}
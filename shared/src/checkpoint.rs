@@ -0,0 +1,2 @@
+pub const CHECKPOINT_DELAY: i64 = 2; // epochs after the validity window of an epoch before its checkpoint is signed
+pub const CHECKPOINT_COMMITTEE_SIZE: u32 = 16; // nodes selected to sign a checkpoint
\ No newline at end of file
@@ -12,4 +12,12 @@ pub fn timestamp_to_epoch_number(timestamp: i64) -> i64 {
     let start_timestamp = DateTime::<Utc>::from_naive_utc_and_offset(start_naive, Utc).timestamp();
 
     ((timestamp - start_timestamp) / 12) + 1
-} 
\ No newline at end of file
+} 
+
+// the first second of an epoch, none when it is out of range
+pub fn epoch_number_to_timestamp(epoch_number: i64) -> Option<i64> {
+    let start_naive = NaiveDateTime::parse_from_str("2009-01-03 21:45:00", "%Y-%m-%d %H:%M:%S").unwrap();
+    let start_timestamp = DateTime::<Utc>::from_naive_utc_and_offset(start_naive, Utc).timestamp();
+
+    epoch_number.checked_sub(1)?.checked_mul(12)?.checked_add(start_timestamp)
+}
\ No newline at end of file
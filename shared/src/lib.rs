@@ -5,6 +5,9 @@ pub mod epoch;
 pub mod ip;
 pub mod sha;
 pub mod transaction;
+pub mod merkle;
+pub mod reconciliation;
+pub mod checkpoint;
 
 /*
     index 0 => public key
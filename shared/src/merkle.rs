@@ -0,0 +1,95 @@
+use crate::sha::sha256;
+
+// leaves and inner nodes are hashed with different prefixes, so a node can never pass as a leaf
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0; 32];
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(hash_leaf).collect();
+
+    while level.len() > 1 {
+        level = level.chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!()
+        })
+        .collect();
+    }
+
+    level[0]
+}
+
+/*
+    Siblings from the leaf up to the root, each with a flag that is true when the
+    sibling is on the left. A node without a sibling is carried up unchanged.
+*/
+pub fn merkle_path(leaves: &[[u8; 32]], index: usize) -> Vec<([u8; 32], bool)> {
+    let mut path = Vec::new();
+
+    if index >= leaves.len() {
+        return path;
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(hash_leaf).collect();
+    let mut index = index;
+
+    while level.len() > 1 {
+        let sibling_index = index ^ 1;
+
+        if sibling_index < level.len() {
+            path.push((level[sibling_index], sibling_index < index));
+        }
+
+        level = level.chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!()
+        })
+        .collect();
+        index /= 2;
+    }
+
+    path
+}
+
+pub fn verify_merkle_path(leaf: &[u8; 32], path: &[([u8; 32], bool)], root: &[u8; 32]) -> bool {
+    merkle_root_from_path(leaf, path) == *root
+}
+
+// the root a leaf and its path lead to
+pub fn merkle_root_from_path(leaf: &[u8; 32], path: &[([u8; 32], bool)]) -> [u8; 32] {
+    let mut hash = hash_leaf(leaf);
+
+    for (sibling, sibling_is_left) in path {
+        hash = if *sibling_is_left {
+            hash_node(sibling, &hash)
+        } else {
+            hash_node(&hash, sibling)
+        };
+    }
+
+    hash
+}
+
+fn hash_leaf(leaf: &[u8; 32]) -> [u8; 32] {
+    let mut message = [0u8; 33];
+    message[0] = LEAF_PREFIX;
+    message[1..].copy_from_slice(leaf);
+
+    sha256(&message)
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut message = [0u8; 65];
+    message[0] = NODE_PREFIX;
+    message[1..33].copy_from_slice(left);
+    message[33..].copy_from_slice(right);
+
+    sha256(&message)
+}
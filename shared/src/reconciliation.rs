@@ -0,0 +1,25 @@
+use crate::merkle::merkle_root;
+
+pub const RECONCILIATION_BUCKETS: usize = 256; // committed txids are grouped by their first byte
+
+pub fn get_bucket(txid: &[u8; 32]) -> usize {
+    txid[0] as usize % RECONCILIATION_BUCKETS
+}
+
+/*
+    A merkle root over the sorted txids of every bucket. Two nodes that committed
+    the same transactions of a bucket end up with the same digest for it.
+*/
+pub fn bucket_digests<'a>(txids: impl Iterator<Item = &'a [u8; 32]>) -> Vec<[u8; 32]> {
+    let mut buckets: Vec<Vec<[u8; 32]>> = vec![Vec::new(); RECONCILIATION_BUCKETS];
+    for txid in txids {
+        buckets[get_bucket(txid)].push(*txid);
+    }
+
+    buckets.into_iter()
+    .map(|mut bucket| {
+        bucket.sort();
+        merkle_root(&bucket)
+    })
+    .collect()
+}
\ No newline at end of file
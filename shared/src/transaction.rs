@@ -1,8 +1,28 @@
-pub const STAKED_PER_NODE: f32 = 500.0;
 pub const NTP: f32 = 500.0; // number of transactions processed
+pub const PENDING_SPEND_TIMEOUT: i64 = 36; // seconds an input stays reserved by an in-flight transaction
+pub const LEADER_CANDIDATES: u32 = 3; // the selected leader followed by its backups
+pub const MAX_TRANSACTION_INPUTS: usize = 256;
+pub const MAX_TRANSACTION_OUTPUTS: usize = 256;
+pub const AMOUNT_DECIMALS: u32 = 8;
+pub const BASE_UNITS_PER_COIN: u64 = 100_000_000; // 10 ^ AMOUNT_DECIMALS
+
+// every node has to agree on these, a node with other values would accept or sign what its peers reject
+pub const TRANSACTION_VALIDITY_WINDOW: i64 = 1; // epochs a transaction stays valid after the epoch of its created_at
+pub const QUORUM_NUMERATOR: u32 = 2; // share of a committee that has to sign, numerator / denominator
+pub const QUORUM_DENOMINATOR: u32 = 3;
+
+// stake a node needs bonded to its BLS public key to be a member, in base units
+pub const STAKED_PER_NODE: u64 = 500 * BASE_UNITS_PER_COIN;
+pub const STAKE_ACTIVATION_EPOCHS: u64 = 2; // epochs before a bond or an unbond changes the membership
+pub const UNBONDING_EPOCHS: u64 = 64; // epochs after an unbond before the stake can be spent, longer than selection data is kept
+
+// percent of a transaction fee for each role, the rounding remainder goes to the leader
+pub const LEADER_FEE_SHARE: u64 = 40;
+pub const ACCREDITATION_COUNCIL_FEE_SHARE: u64 = 40;
+pub const BROADCAST_FEE_SHARE: u64 = 20;
 
 // calculating the number of nodes required
 pub fn calculating_nnr(cyn: f32) -> u32 {
-    let result = (24.0 * NTP * cyn) / STAKED_PER_NODE;
+    let result = (24.0 * NTP * cyn) / (STAKED_PER_NODE as f32 / BASE_UNITS_PER_COIN as f32);
     result.ceil() as u32
 }
\ No newline at end of file
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+use blst::{min_pk::{AggregatePublicKey, PublicKey, Signature}, BLST_ERROR};
+use ahash::{AHashMap, AHashSet};
+use concilium_core::{epoch::EpochSnapshot, mempool::Mempool, transaction::{BatchInclusion, BatchTemp, BroadcastBatchTemp, BroadcastTransactionTemp, CommitCertificate, Transaction}};
+use concilium_core_ext::{
+    epoch::EpochSnapshotSupport, transaction::{batch_inclusion::BatchInclusionSupport, batch_temp::BatchTempSupport, broadcast_batch_temp::BroadcastBatchTempSupport, broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, merkle::{merkle_root, verify_merkle_path}, DST};
+use crate::{
+    get_epoch_snapshot, get_epoch_snapshot_by_epoch_number, selection::{calculate_broadcast_node_count, calculate_quorum, select_accreditation_council_node_ids, select_batch_broadcast_node_ids, select_broadcast_node_ids, select_leader_ids}, validation::{validate_signature_and_txid, validate_structure}
+};
+
+/*
+    Checks a committed transaction with nothing but its certificate and the
+    selection data of its epoch, so it can run outside of a node.
+*/
+pub fn verify_transaction_certificate(transaction: &Transaction, commit_certificate: &CommitCertificate, epoch_snapshot: &EpochSnapshot) -> bool {
+    let certified_transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction();
+
+    let (binary_transaction, binary_certified_transaction) = match (binary::encode(transaction), binary::encode(certified_transaction)) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return false
+    };
+
+    if binary_transaction != binary_certified_transaction {
+        return false;
+    }
+
+    verify_commit_certificate_by_snapshot(commit_certificate, epoch_snapshot).is_ok()
+}
+
+pub async fn verify_accreditation_council_signature(broadcast_transaction_temp: &BroadcastTransactionTemp, mempool: Arc<Mempool>) -> Result<(), Error> {
+    let epoch_snapshot = get_epoch_snapshot(broadcast_transaction_temp.get_transaction(), mempool).await?;
+
+    verify_accreditation_council_signature_by_snapshot(broadcast_transaction_temp, &epoch_snapshot)
+}
+
+pub async fn verify_commit_certificate(commit_certificate: &CommitCertificate, mempool: Arc<Mempool>) -> Result<(), Error> {
+    let epoch_snapshot = get_epoch_snapshot(commit_certificate.get_broadcast_transaction_temp().get_transaction(), mempool).await?;
+
+    verify_commit_certificate_by_snapshot(commit_certificate, &epoch_snapshot)
+}
+
+/*
+    The aggregated signature of the temp must come from its leader, which has
+    to be one of the leader candidates, and a quorum of the Accreditation Council plus the leader.
+*/
+pub fn verify_accreditation_council_signature_by_snapshot(broadcast_transaction_temp: &BroadcastTransactionTemp, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let transaction = broadcast_transaction_temp.get_transaction();
+
+    let leader_ids = select_leader_ids(transaction, epoch_snapshot)?;
+    let council_node_ids = select_accreditation_council_node_ids(transaction, epoch_snapshot)?;
+
+    let leader_id = broadcast_transaction_temp.get_leader_id();
+    if !leader_ids.contains(&leader_id) {
+        return Err(Error::new("leader is not a leader candidate"));
+    }
+
+    if !broadcast_transaction_temp.get_accreditation_council_signers().contains(&leader_id) {
+        return Err(Error::new("leader did not sign"));
+    }
+
+    let mut expected_signers = council_node_ids;
+    expected_signers.push(leader_id);
+
+    let signers = verify_signers(broadcast_transaction_temp.get_accreditation_council_signers(), expected_signers, epoch_snapshot)?;
+
+    let message = binary::encode(transaction)?;
+
+    verify_aggregated_signature(&message, broadcast_transaction_temp.get_signature(), &signers, epoch_snapshot.get_node_public_keys())
+}
+
+pub fn verify_broadcast_signature_by_snapshot(commit_certificate: &CommitCertificate, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+
+    let expected_signers = select_broadcast_node_ids(broadcast_transaction_temp, epoch_snapshot)?;
+
+    let signers = verify_signers(commit_certificate.get_broadcast_signers(), expected_signers, epoch_snapshot)?;
+
+    let message = binary::encode(broadcast_transaction_temp)?;
+
+    verify_aggregated_signature(&message, commit_certificate.get_broadcast_aggregated_signature(), &signers, epoch_snapshot.get_node_public_keys())
+}
+
+pub fn verify_commit_certificate_by_snapshot(commit_certificate: &CommitCertificate, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction();
+
+    validate_signature_and_txid(transaction, true)?;
+
+    match commit_certificate.get_batch_inclusion() {
+        Some(batch_inclusion) => verify_batch_inclusion_by_snapshot(commit_certificate, batch_inclusion, epoch_snapshot),
+        None => {
+            verify_accreditation_council_signature_by_snapshot(commit_certificate.get_broadcast_transaction_temp(), epoch_snapshot)?;
+            verify_broadcast_signature_by_snapshot(commit_certificate, epoch_snapshot)
+        }
+    }
+}
+
+/*
+    What a joining node can check without the selection data of past epochs: the transaction,
+    its batch inclusion and that the listed signers produced both aggregated signatures.
+    That the signers were the selected committees has to be confirmed by a quorum of the peers.
+*/
+pub fn verify_commit_certificate_signatures(commit_certificate: &CommitCertificate, node_public_keys: &AHashMap<u32, [u8; 48]>) -> Result<(), Error> {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+    let transaction = broadcast_transaction_temp.get_transaction();
+
+    validate_structure(transaction)?;
+    validate_signature_and_txid(transaction, true)?;
+
+    let broadcast_signers = verify_unique_signers(commit_certificate.get_broadcast_signers())?;
+
+    match commit_certificate.get_batch_inclusion() {
+        Some(batch_inclusion) => {
+            verify_batch_inclusion(commit_certificate, batch_inclusion)?;
+
+            let batch_temp = batch_inclusion.get_batch_temp();
+            let council_signers = verify_unique_signers(batch_temp.get_accreditation_council_signers())?;
+
+            verify_aggregated_signature(batch_temp.get_merkle_root(), batch_temp.get_signature(), &council_signers, node_public_keys)?;
+            verify_aggregated_signature(&binary::encode(batch_temp)?, commit_certificate.get_broadcast_aggregated_signature(), &broadcast_signers, node_public_keys)
+        },
+        None => {
+            let council_signers = verify_unique_signers(broadcast_transaction_temp.get_accreditation_council_signers())?;
+            if council_signers.binary_search(&broadcast_transaction_temp.get_leader_id()).is_err() {
+                return Err(Error::new("leader did not sign"));
+            }
+
+            verify_aggregated_signature(&binary::encode(transaction)?, broadcast_transaction_temp.get_signature(), &council_signers, node_public_keys)?;
+            verify_aggregated_signature(&binary::encode(broadcast_transaction_temp)?, commit_certificate.get_broadcast_aggregated_signature(), &broadcast_signers, node_public_keys)
+        }
+    }
+}
+
+/*
+    Checks a batch as a broadcast node receives it, before the batch temp is signed.
+    The merkle root has to be built from exactly these transactions.
+*/
+pub async fn verify_broadcast_batch_temp(broadcast_batch_temp: &BroadcastBatchTemp, mempool: Arc<Mempool>) -> Result<(), Error> {
+    let batch_temp = broadcast_batch_temp.get_batch_temp();
+    let transactions = broadcast_batch_temp.get_transactions();
+
+    if transactions.is_empty() {
+        return Err(Error::new("batch is empty"));
+    }
+
+    let txids: Vec<[u8; 32]> = transactions.iter().map(|t| t.get_txid().clone()).collect();
+    if txids.iter().collect::<AHashSet<_>>().len() != txids.len() {
+        return Err(Error::new("duplicate transaction in the batch"));
+    }
+
+    if merkle_root(&txids) != *batch_temp.get_merkle_root() {
+        return Err(Error::new("merkle root does not match the batch"));
+    }
+
+    let epoch_snapshot = get_epoch_snapshot_by_epoch_number(batch_temp.get_epoch_number(), mempool).await?;
+
+    for transaction in transactions {
+        validate_structure(transaction)?;
+        validate_signature_and_txid(transaction, true)?;
+
+        verify_batch_transaction_by_snapshot(transaction, batch_temp, &epoch_snapshot)?;
+    }
+
+    verify_batch_temp_signature_by_snapshot(batch_temp, &epoch_snapshot)
+}
+
+/*
+    One transaction of a batch: the leader must be one of its candidates, a quorum of its own
+    Accreditation Council plus the leader must be among the signers of the merkle root and
+    the broadcast set of the batch must not be smaller than the one of the transaction.
+*/
+pub fn verify_batch_transaction_by_snapshot(transaction: &Transaction, batch_temp: &BatchTemp, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    if batch_temp.get_epoch_number() != epoch_snapshot.get_epoch_number() {
+        return Err(Error::new("batch does not belong to the epoch"));
+    }
+
+    let leader_ids = select_leader_ids(transaction, epoch_snapshot)?;
+    let council_node_ids = select_accreditation_council_node_ids(transaction, epoch_snapshot)?;
+
+    let leader_id = batch_temp.get_leader_id();
+    if !leader_ids.contains(&leader_id) {
+        return Err(Error::new("leader is not a leader candidate"));
+    }
+
+    let signers = verify_unique_signers(batch_temp.get_accreditation_council_signers())?;
+    if signers.binary_search(&leader_id).is_err() {
+        return Err(Error::new("leader did not sign"));
+    }
+
+    let mut expected_signers = council_node_ids;
+    expected_signers.push(leader_id);
+    expected_signers.sort();
+    expected_signers.dedup();
+
+    let council_signers_count = signers.iter().filter(|id| expected_signers.binary_search(id).is_ok()).count();
+    let quorum = calculate_quorum(expected_signers.len());
+    if council_signers_count < quorum {
+        return Err(Error::new("quorum is not reached"));
+    }
+
+    let broadcast_node_count = calculate_broadcast_node_count(transaction, epoch_snapshot.get_node_public_keys().len() as u32);
+    if batch_temp.get_broadcast_node_count() < broadcast_node_count {
+        return Err(Error::new("broadcast set of the batch is too small"));
+    }
+
+    Ok(())
+}
+
+// every signer of the batch signed the same merkle root, whichever transactions it is council for
+pub fn verify_batch_temp_signature_by_snapshot(batch_temp: &BatchTemp, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let signers = verify_unique_signers(batch_temp.get_accreditation_council_signers())?;
+
+    verify_aggregated_signature(batch_temp.get_merkle_root(), batch_temp.get_signature(), &signers, epoch_snapshot.get_node_public_keys())
+}
+
+pub fn verify_batch_broadcast_signature_by_snapshot(batch_temp: &BatchTemp, broadcast_aggregated_signature: &[u8; 96], broadcast_signers: &[u32], epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let expected_signers = select_batch_broadcast_node_ids(batch_temp, epoch_snapshot)?;
+
+    let signers = verify_signers(broadcast_signers, expected_signers, epoch_snapshot)?;
+
+    let message = binary::encode(batch_temp)?;
+
+    verify_aggregated_signature(&message, broadcast_aggregated_signature, &signers, epoch_snapshot.get_node_public_keys())
+}
+
+/*
+    The temp of a batched transaction repeats the accreditation of its batch,
+    so the leader and the signers are read the same way as for a single transaction.
+*/
+fn verify_batch_inclusion_by_snapshot(commit_certificate: &CommitCertificate, batch_inclusion: &BatchInclusion, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    verify_batch_inclusion(commit_certificate, batch_inclusion)?;
+
+    let transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction();
+    let batch_temp = batch_inclusion.get_batch_temp();
+
+    verify_batch_transaction_by_snapshot(transaction, batch_temp, epoch_snapshot)?;
+    verify_batch_temp_signature_by_snapshot(batch_temp, epoch_snapshot)?;
+    verify_batch_broadcast_signature_by_snapshot(batch_temp, commit_certificate.get_broadcast_aggregated_signature(), commit_certificate.get_broadcast_signers(), epoch_snapshot)
+}
+
+fn verify_batch_inclusion(commit_certificate: &CommitCertificate, batch_inclusion: &BatchInclusion) -> Result<(), Error> {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+    let batch_temp = batch_inclusion.get_batch_temp();
+
+    if broadcast_transaction_temp.get_signature() != batch_temp.get_signature()
+    || broadcast_transaction_temp.get_accreditation_council_signers() != batch_temp.get_accreditation_council_signers()
+    || broadcast_transaction_temp.get_leader_id() != batch_temp.get_leader_id() {
+        return Err(Error::new("transaction temp does not match the batch"));
+    }
+
+    let transaction = broadcast_transaction_temp.get_transaction();
+    if !verify_merkle_path(transaction.get_txid(), batch_inclusion.get_merkle_path(), batch_temp.get_merkle_root()) {
+        return Err(Error::new("transaction is not included in the batch"));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn verify_unique_signers(signers: &[u32]) -> Result<Vec<u32>, Error> {
+    let mut signers = signers.to_vec();
+    signers.sort();
+
+    let signers_count = signers.len();
+    signers.dedup();
+
+    if signers.len() != signers_count {
+        return Err(Error::new("duplicate signers"));
+    }
+
+    Ok(signers)
+}
+
+pub(crate) fn verify_signers(signers: &[u32], mut expected_signers: Vec<u32>, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, Error> {
+    let signers = verify_unique_signers(signers)?;
+
+    expected_signers.sort();
+    expected_signers.dedup();
+
+    if signers.iter().any(|id| expected_signers.binary_search(id).is_err()) {
+        return Err(Error::new("signer is not a member of the selected committee"));
+    }
+
+    let quorum = calculate_quorum(expected_signers.len());
+    if signers.len() < quorum {
+        return Err(Error::new("quorum is not reached"));
+    }
+
+    Ok(signers)
+}
+
+// a single signature of a peer, checked against its public key before it counts toward a quorum
+pub fn verify_node_signature(message: &[u8], signature: &[u8], node_id: u32, node_public_keys: &AHashMap<u32, [u8; 48]>) -> Result<Signature, Error> {
+    let public_key = match node_public_keys.get(&node_id) {
+        Some(public_key) => PublicKey::from_bytes(public_key)?,
+        None => return Err(Error::new(format!("node {} not found", node_id).as_str()))
+    };
+    let signature = Signature::from_bytes(signature)?;
+
+    match signature.verify(true, message, DST, &[], &public_key, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(signature),
+        error => Err(error.into())
+    }
+}
+
+pub(crate) fn verify_aggregated_signature(message: &[u8], aggregated_signature: &[u8; 96], signers: &[u32], node_public_keys: &AHashMap<u32, [u8; 48]>) -> Result<(), Error> {
+    let mut public_keys = Vec::with_capacity(signers.len());
+    for id in signers {
+        match node_public_keys.get(id) {
+            Some(public_key) => public_keys.push(PublicKey::from_bytes(public_key)?),
+            None => return Err(Error::new(format!("node {} not found", id).as_str()))
+        }
+    }
+    let public_keys: Vec<&PublicKey> = public_keys.iter().collect();
+
+    let agg_pub = AggregatePublicKey::aggregate(&public_keys, false)?.to_public_key();
+    let agg_sig = Signature::from_bytes(aggregated_signature)?;
+
+    match agg_sig.verify(false, message, DST, &[], &agg_pub, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        error => Err(error.into())
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use concilium_core::{checkpoint::{Checkpoint, SignedCheckpoint}, db::DB, mempool::Mempool};
+use concilium_core_ext::{
+    chain_state::ChainStateSupport, checkpoint::{CheckpointSupport, SignedCheckpointSupport}, db::DBSupport, epoch::EpochSnapshotSupport, mempool::MempoolSupport
+};
+use concilium_error::Error;
+use concilium_shared::{binary, checkpoint::{CHECKPOINT_COMMITTEE_SIZE, CHECKPOINT_DELAY}, coventor::vec::unsigned_int::vec_to_unsigned_int, transaction::TRANSACTION_VALIDITY_WINDOW};
+use crate::{
+    certificate::{verify_aggregated_signature, verify_signers, verify_unique_signers}, get_epoch_snapshot_by_epoch_number, selection::{calculate_quorum, select_checkpoint_committee_node_ids}
+};
+
+/*
+    Transactions of an epoch can be committed until its validity window is over, and late commits
+    still reach the other nodes for a few epochs after. Only then the epoch is checkpointed.
+*/
+pub fn get_checkpoint_epoch_number(committee_epoch_number: u64) -> Option<u64> {
+    committee_epoch_number.checked_sub((TRANSACTION_VALIDITY_WINDOW + CHECKPOINT_DELAY) as u64)
+}
+
+pub fn get_checkpoint(epoch_number: u64, db: &DB) -> Result<Option<SignedCheckpoint>, Error> {
+    match db.get(format!("checkpoint.{}", epoch_number).as_str())? {
+        Some(data) => Ok(Some(binary::decode::<SignedCheckpoint>(&data)?)),
+        None => Ok(None)
+    }
+}
+
+pub fn get_last_checkpoint(db: &DB) -> Result<Option<SignedCheckpoint>, Error> {
+    match db.get("last_checkpoint_epoch")? {
+        Some(data) => {
+            let epoch_number = vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid last checkpoint epoch"))?;
+
+            get_checkpoint(epoch_number, db)
+        },
+        None => Ok(None)
+    }
+}
+
+// checkpoints are sparse over the epochs, so each one points to the checkpoint stored after it
+pub fn get_next_checkpoint_epoch_number(epoch_number: u64, db: &DB) -> Result<Option<u64>, Error> {
+    match db.get(format!("next_checkpoint.{}", epoch_number).as_str())? {
+        Some(data) => Ok(Some(vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid next checkpoint epoch"))?)),
+        None => Ok(None)
+    }
+}
+
+pub fn get_transaction_sequence(txid: &[u8; 32], db: &DB) -> Result<Option<u64>, Error> {
+    match db.get(format!("transaction_sequence.{}", hex::encode(txid)).as_str())? {
+        Some(data) => Ok(Some(vec_to_unsigned_int::<u64>(&data).ok_or(Error::new("invalid transaction sequence"))?)),
+        None => Ok(None)
+    }
+}
+
+/*
+    Every committed transaction of the epoch or before that has no sequence yet, sorted by txid.
+    Nothing is built for an epoch without such transactions.
+*/
+pub async fn build_checkpoint(committee_epoch_number: u64, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<Option<Checkpoint>, Error> {
+    let Some(epoch_number) = get_checkpoint_epoch_number(committee_epoch_number) else {
+        return Ok(None);
+    };
+
+    let last_checkpoint = get_last_checkpoint(&db)?.ok_or(Error::new("genesis checkpoint not found"))?;
+    let last_checkpoint = last_checkpoint.get_checkpoint();
+    if last_checkpoint.get_epoch_number() >= epoch_number {
+        return Ok(None);
+    }
+
+    let mut txids: Vec<[u8; 32]> = {
+        let lock = mempool.get_chain_state();
+        let chain_state = lock.read().await;
+
+        chain_state.get_uncheckpointed_txids().iter()
+        .filter(|(_, transaction_epoch_number)| **transaction_epoch_number <= epoch_number)
+        .map(|(txid, _)| *txid)
+        .collect()
+    };
+    if txids.is_empty() {
+        return Ok(None);
+    }
+    txids.sort();
+
+    Ok(
+        Some(
+            Checkpoint::new(
+                epoch_number,
+                committee_epoch_number,
+                last_checkpoint.get_hash()?,
+                last_checkpoint.get_next_sequence(),
+                txids
+            )
+        )
+    )
+}
+
+/*
+    What a committee member checks before it signs: the checkpoint follows its last one and
+    every txid is committed here without a sequence. A member that committed more keeps the rest for later.
+*/
+pub async fn validate_checkpoint_proposal(checkpoint: &Checkpoint, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    verify_checkpoint_epoch(checkpoint)?;
+    verify_sorted_txids(checkpoint)?;
+
+    let last_checkpoint = get_last_checkpoint(&db)?.ok_or(Error::new("genesis checkpoint not found"))?;
+    verify_checkpoint_link(checkpoint, last_checkpoint.get_checkpoint())?;
+
+    let lock = mempool.get_chain_state();
+    let chain_state = lock.read().await;
+
+    for txid in checkpoint.get_txids() {
+        match chain_state.get_uncheckpointed_txids().get(txid) {
+            Some(transaction_epoch_number) if *transaction_epoch_number <= checkpoint.get_epoch_number() => {},
+            _ => return Err(Error::new(format!("transaction {} can not be checkpointed", hex::encode(txid)).as_str()))
+        }
+    }
+
+    Ok(())
+}
+
+/*
+    Checks the committee signatures. Within the retained epochs the signers have to be a quorum of the
+    selected committee. A node that catches up on older checkpoints has no selection data for them,
+    so a quorum of a committee of CHECKPOINT_COMMITTEE_SIZE among the confirmed nodes has to have signed.
+*/
+pub async fn verify_signed_checkpoint(signed_checkpoint: &SignedCheckpoint, mempool: Arc<Mempool>) -> Result<(), Error> {
+    let checkpoint = signed_checkpoint.get_checkpoint();
+
+    verify_checkpoint_epoch(checkpoint)?;
+    verify_sorted_txids(checkpoint)?;
+
+    let hash = checkpoint.get_hash()?;
+
+    match get_epoch_snapshot_by_epoch_number(checkpoint.get_committee_epoch_number(), Arc::clone(&mempool)).await {
+        Ok(epoch_snapshot) => {
+            let committee_node_ids = select_checkpoint_committee_node_ids(checkpoint, CHECKPOINT_COMMITTEE_SIZE, &epoch_snapshot)?;
+            let signers = verify_signers(signed_checkpoint.get_signers(), committee_node_ids, &epoch_snapshot)?;
+
+            verify_aggregated_signature(&hash, signed_checkpoint.get_aggregated_signature(), &signers, epoch_snapshot.get_node_public_keys())
+        },
+        Err(_) => {
+            let node_public_keys = {
+                let lock = mempool.get_node_registry();
+                let node_registry = lock.read().await;
+
+                node_registry.clone()
+            };
+
+            let signers = verify_unique_signers(signed_checkpoint.get_signers())?;
+            let quorum = calculate_quorum((CHECKPOINT_COMMITTEE_SIZE as usize).min(node_public_keys.len()));
+            if signers.len() < quorum {
+                return Err(Error::new("quorum is not reached"));
+            }
+
+            verify_aggregated_signature(&hash, signed_checkpoint.get_aggregated_signature(), &signers, &node_public_keys)
+        }
+    }
+}
+
+/*
+    Stores a signed checkpoint on top of the last one and numbers its transactions.
+    The chain state lock is held, so a commit can not put a sequenced transaction back into the pending ones.
+*/
+pub async fn put_checkpoint(signed_checkpoint: &SignedCheckpoint, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let checkpoint = signed_checkpoint.get_checkpoint();
+
+    let lock = mempool.get_chain_state();
+    let mut chain_state = lock.write().await;
+
+    let last_checkpoint = get_last_checkpoint(&db)?.ok_or(Error::new("genesis checkpoint not found"))?;
+    verify_checkpoint_link(checkpoint, last_checkpoint.get_checkpoint())?;
+
+    write_checkpoint(signed_checkpoint, &db)?;
+    db.put(format!("next_checkpoint.{}", last_checkpoint.get_checkpoint().get_epoch_number()).as_str(), &checkpoint.get_epoch_number().to_le_bytes())?;
+
+    for txid in checkpoint.get_txids() {
+        chain_state.remove_uncheckpointed_txid(txid);
+    }
+
+    Ok(())
+}
+
+// the genesis transactions are the same on every node, so the first checkpoint needs no signatures
+pub fn put_genesis_checkpoint(mut txids: Vec<[u8; 32]>, db: &DB) -> Result<(), Error> {
+    if db.get("last_checkpoint_epoch")?.is_some() {
+        return Ok(());
+    }
+    txids.sort();
+
+    write_checkpoint(&SignedCheckpoint::new(Checkpoint::new(0, 0, [0; 32], 1, txids), Vec::new(), [0; 96]), db)
+}
+
+fn write_checkpoint(signed_checkpoint: &SignedCheckpoint, db: &DB) -> Result<(), Error> {
+    let checkpoint = signed_checkpoint.get_checkpoint();
+
+    for (index, txid) in checkpoint.get_txids().iter().enumerate() {
+        let sequence = checkpoint.get_first_sequence() + index as u64;
+        let txid_hex = hex::encode(txid);
+
+        db.put(format!("sequence.{}", sequence).as_str(), &txid_hex)?;
+        db.put(format!("transaction_sequence.{}", txid_hex).as_str(), &sequence.to_le_bytes())?;
+    }
+
+    db.put(format!("checkpoint.{}", checkpoint.get_epoch_number()).as_str(), &binary::encode(signed_checkpoint)?)?;
+    db.put("last_checkpoint_epoch", &checkpoint.get_epoch_number().to_le_bytes())
+}
+
+fn verify_checkpoint_epoch(checkpoint: &Checkpoint) -> Result<(), Error> {
+    if get_checkpoint_epoch_number(checkpoint.get_committee_epoch_number()) != Some(checkpoint.get_epoch_number()) {
+        return Err(Error::new("checkpoint is not built in its committee epoch"));
+    }
+
+    Ok(())
+}
+
+fn verify_sorted_txids(checkpoint: &Checkpoint) -> Result<(), Error> {
+    if checkpoint.get_txids().is_empty() {
+        return Err(Error::new("checkpoint is empty"));
+    }
+
+    if checkpoint.get_txids().windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(Error::new("checkpoint txids are not sorted"));
+    }
+
+    Ok(())
+}
+
+fn verify_checkpoint_link(checkpoint: &Checkpoint, last_checkpoint: &Checkpoint) -> Result<(), Error> {
+    if checkpoint.get_epoch_number() <= last_checkpoint.get_epoch_number() {
+        return Err(Error::new("checkpoint is not after the last one"));
+    }
+
+    if *checkpoint.get_previous_hash() != last_checkpoint.get_hash()? || checkpoint.get_first_sequence() != last_checkpoint.get_next_sequence() {
+        return Err(Error::new("checkpoint does not follow the last one"));
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,915 @@
+use std::{fmt, future::Future, sync::Arc, time::{Duration, Instant}};
+use ahash::{AHashMap, AHashSet};
+use blst::min_pk::{AggregateSignature, SecretKey, Signature};
+use concilium_core::{db::DB, epoch::EpochSnapshot, evidence::{Evidence, SignedSpend}, mempool::Mempool, transaction::{BatchInclusion, BatchTemp, BroadcastBatchTemp, BroadcastTransactionTemp, CommitCertificate, Transaction, TransactionBatch, TransactionState}};
+use concilium_core_ext::{
+    config::ConfigSupport, epoch::EpochSnapshotSupport, evidence::SignedSpendSupport, mempool::MempoolSupport, node::self_node::SelfNodeSupport, transaction::{
+        batch_inclusion::BatchInclusionSupport, batch_temp::BatchTempSupport, broadcast_batch_temp::BroadcastBatchTempSupport, broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, transaction::TransactionSupport, transaction_batch::TransactionBatchSupport
+    }
+};
+use concilium_error::Error;
+use concilium_shared::{binary, merkle::{merkle_path, merkle_root}, DST};
+use tokio::{sync::oneshot::Sender, task::JoinSet, time::timeout};
+use crate::{
+    certificate::verify_node_signature, evidence::{put_evidence, record_signed_spend}, get_epoch_snapshot, get_epoch_snapshot_by_epoch_number, health::record_node_response, get_leader_ids, put_success_transaction_on_db, reservation::{release_inputs, reserve_inputs}, selection::{calculate_broadcast_node_count, calculate_quorum, select_accreditation_council_node_ids, select_batch_broadcast_node_ids, select_broadcast_node_ids}, status::put_transaction_status, validation::{validate_transaction, Rejection}
+};
+
+// a second council round only runs without the transactions the first one rejected
+const BATCH_COUNCIL_ROUNDS: usize = 2;
+
+/*
+    Everything the coordinator needs from the network. The rpc crate implements it over the
+    transaction clients of the active nodes, a test can implement it in memory.
+    A signing request answers with the signature of the peer, the reason it refused or why it could not be reached.
+*/
+pub trait PeerTransport: Send + Sync + 'static {
+    // every connected peer, the local node excluded
+    fn get_peer_ids(&self) -> impl Future<Output = Vec<u32>> + Send;
+    fn accreditation_council_request(&self, node_id: u32, nonce: u64, transaction: &[u8]) -> impl Future<Output = Result<Vec<u8>, PeerError>> + Send;
+    fn broadcast_request(&self, node_id: u32, nonce: u64, broadcast_transaction_temp: &[u8]) -> impl Future<Output = Result<Vec<u8>, PeerError>> + Send;
+    // a council node signs the merkle root of the batch, or names the transactions it rejects
+    fn accreditation_council_batch_request(&self, node_id: u32, epoch_number: u64, transaction_batch: &[u8]) -> impl Future<Output = Result<Vec<u8>, PeerError>> + Send;
+    fn broadcast_batch_request(&self, node_id: u32, epoch_number: u64, broadcast_batch_temp: &[u8]) -> impl Future<Output = Result<Vec<u8>, PeerError>> + Send;
+    fn save_request(&self, node_id: u32, nonce: u64, commit_certificate: &[u8]) -> impl Future<Output = Result<(), Error>> + Send;
+    fn put_evidence_request(&self, node_id: u32, evidence: &[u8]) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+// why a peer did not sign: it refused, it rejected transactions of a batch, or it could not be reached at all
+#[derive(Debug)]
+pub enum PeerError {
+    Refused(String),
+    RejectedTransactions(Vec<[u8; 32]>),
+    Unreachable(Error),
+}
+
+impl From<Error> for PeerError {
+    fn from(error: Error) -> Self {
+        PeerError::Unreachable(error)
+    }
+}
+
+/*
+    Where the coordinator keeps what it leads. A node writes to its database and its mempool,
+    a test can keep everything in memory.
+*/
+pub trait LedgerStore: Send + Sync + 'static {
+    // the status is kept on a best effort basis, it never fails the transaction
+    fn put_transaction_status(&self, txid: &[u8; 32], state: TransactionState, reason: Option<&str>);
+    fn put_commit_certificate(&self, commit_certificate: &CommitCertificate) -> impl Future<Output = Result<(), Error>> + Send;
+    // true when the evidence was not known before
+    fn put_evidence(&self, evidence: &Evidence) -> impl Future<Output = Result<bool, Error>> + Send;
+}
+
+pub struct DbLedgerStore {
+    mempool: Arc<Mempool>,
+    db: Arc<DB>,
+}
+
+impl DbLedgerStore {
+    pub fn new(mempool: Arc<Mempool>, db: Arc<DB>) -> Self {
+        Self {
+            mempool,
+            db
+        }
+    }
+}
+
+impl LedgerStore for DbLedgerStore {
+    fn put_transaction_status(&self, txid: &[u8; 32], state: TransactionState, reason: Option<&str>) {
+        put_transaction_status(txid, state, reason, &self.db).ok();
+    }
+
+    async fn put_commit_certificate(&self, commit_certificate: &CommitCertificate) -> Result<(), Error> {
+        put_success_transaction_on_db(commit_certificate, Arc::clone(&self.mempool), Arc::clone(&self.db)).await
+    }
+
+    async fn put_evidence(&self, evidence: &Evidence) -> Result<bool, Error> {
+        put_evidence(evidence, Arc::clone(&self.mempool), &self.db).await
+    }
+}
+
+// why a transaction could not be led
+#[derive(Debug)]
+pub enum LeadError {
+    Rejected(Rejection),
+    NotSynced,
+    NotLeaderCandidate,
+    InputsReserved,
+    AccreditationCouncilQuorumNotReached { signers: usize, quorum: usize },
+    BroadcastQuorumNotReached { signers: usize, quorum: usize },
+    Internal(Error),
+}
+
+impl LeadError {
+    pub fn get_message(&self) -> String {
+        match self {
+            LeadError::Rejected(rejection) => rejection.get_message().to_string(),
+            LeadError::NotSynced => "node is not synced with the network".to_string(),
+            LeadError::NotLeaderCandidate => "node is not a leader candidate".to_string(),
+            LeadError::InputsReserved => "utxo is reserved by another transaction".to_string(),
+            LeadError::AccreditationCouncilQuorumNotReached { signers, quorum } => format!("accreditation council quorum is not reached: [{}/{}]", signers, quorum),
+            LeadError::BroadcastQuorumNotReached { signers, quorum } => format!("broadcast quorum is not reached: [{}/{}]", signers, quorum),
+            LeadError::Internal(e) => format!("internal error: {}", e.get_message()),
+        }
+    }
+}
+
+impl fmt::Display for LeadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_message())
+    }
+}
+
+impl From<Rejection> for LeadError {
+    fn from(rejection: Rejection) -> Self {
+        LeadError::Rejected(rejection)
+    }
+}
+
+impl From<Error> for LeadError {
+    fn from(error: Error) -> Self {
+        LeadError::Internal(error)
+    }
+}
+
+/*
+    Leads transactions through the Accreditation Council and the broadcast set and
+    persists their certificates. The gRPC leader, the JSON-RPC and the batches all lead through it.
+*/
+pub struct TransactionCoordinator<T: PeerTransport, S: LedgerStore> {
+    transport: Arc<T>,
+    store: Arc<S>,
+    mempool: Arc<Mempool>,
+}
+
+impl<T: PeerTransport, S: LedgerStore> TransactionCoordinator<T, S> {
+    pub fn new(transport: Arc<T>, store: Arc<S>, mempool: Arc<Mempool>) -> Self {
+        Self {
+            transport,
+            store,
+            mempool
+        }
+    }
+
+    pub async fn lead(&self, transaction: &Transaction, check_txid: bool) -> Result<CommitCertificate, LeadError> {
+        self.admit(transaction, check_txid).await?;
+
+        self.commit(transaction).await
+    }
+
+    /*
+        Validates the transaction, checks that this node may lead it and reserves its inputs.
+        An admitted transaction has to be committed, or handed to a batch, so the reservation is released again.
+        The status is recorded on a best effort basis, it never fails the transaction.
+    */
+    pub async fn admit(&self, transaction: &Transaction, check_txid: bool) -> Result<(), LeadError> {
+        let result = self.do_admit(transaction, check_txid).await;
+
+        let (state, reason) = match &result {
+            Ok(_) => (TransactionState::Received, None),
+            Err(e) => (TransactionState::Rejected, Some(e.get_message()))
+        };
+        self.store.put_transaction_status(transaction.get_txid(), state, reason.as_deref());
+
+        result
+    }
+
+    async fn do_admit(&self, transaction: &Transaction, check_txid: bool) -> Result<(), LeadError> {
+        // a node that is still catching up validates against an incomplete ledger
+        if !self.mempool.is_synced() {
+            return Err(LeadError::NotSynced);
+        }
+
+        validate_transaction(transaction, check_txid, Arc::clone(&self.mempool)).await?;
+
+        let self_node_id = {
+            let lock = self.mempool.get_self_node();
+            let self_node = lock.read().await;
+
+            self_node.get_id()
+        };
+
+        // only the selected leader or one of its backups may lead the transaction
+        match get_leader_ids(transaction, Arc::clone(&self.mempool)).await {
+            Ok(leader_ids) if leader_ids.contains(&self_node_id) => {},
+            _ => return Err(LeadError::NotLeaderCandidate)
+        }
+
+        if reserve_inputs(transaction, Arc::clone(&self.mempool)).await == false {
+            return Err(LeadError::InputsReserved);
+        }
+
+        Ok(())
+    }
+
+    // the reservation of the admitted transaction is released when it can not be committed
+    pub async fn commit(&self, transaction: &Transaction) -> Result<CommitCertificate, LeadError> {
+        let result = self.do_commit(transaction).await;
+
+        if let Err(e) = &result {
+            release_inputs(transaction, Arc::clone(&self.mempool)).await;
+            self.store.put_transaction_status(transaction.get_txid(), TransactionState::Rejected, Some(e.get_message().as_str()));
+        }
+
+        result
+    }
+
+    async fn do_commit(&self, transaction: &Transaction) -> Result<CommitCertificate, LeadError> {
+        let (self_node_id, private_key) = self.get_self_node_key().await?;
+
+        let transaction_nonce = transaction.get_nonce();
+        let request_timeout = self.mempool.get_config().get_request_timeout();
+
+        let binary_transaction = Arc::new(binary::encode(transaction)?);
+
+        // peers are selected and their signatures checked against the same epoch snapshot
+        let epoch_snapshot = get_epoch_snapshot(transaction, Arc::clone(&self.mempool)).await?;
+
+        let accreditation_council_node_ids = select_accreditation_council_node_ids(transaction, &epoch_snapshot).map_err(Error::from)?;
+        let accreditation_council_node_ids: Vec<u32> = accreditation_council_node_ids.into_iter().filter(|id| *id != self_node_id).collect();
+
+        // the leader signs as part of the council
+        let accreditation_council_quorum = calculate_quorum(accreditation_council_node_ids.len() + 1);
+        let sign_transaction = private_key.sign(&binary_transaction, DST, &[]);
+        let accreditation_council_signatures = self.collect_signatures(
+            accreditation_council_node_ids,
+            vec![(self_node_id, sign_transaction)],
+            accreditation_council_quorum,
+            request_timeout,
+            &binary_transaction,
+            epoch_snapshot.get_node_public_keys(),
+            {
+                let binary_transaction = Arc::clone(&binary_transaction);
+                move |transport, node_id| {
+                    let binary = Arc::clone(&binary_transaction);
+                    async move {
+                        transport.accreditation_council_request(node_id, transaction_nonce, &binary).await
+                    }
+                }
+            }
+        ).await;
+
+        let signed_spends = accreditation_council_signatures.iter()
+        .filter(|(node_id, _)| *node_id != self_node_id)
+        .map(|(node_id, signature)| (*node_id, SignedSpend::new(transaction.clone(), None, signature.to_bytes())))
+        .collect();
+        self.record_signed_spends(signed_spends);
+
+        let (accreditation_council_signers, accreditation_council_signatures): (Vec<u32>, Vec<Signature>) = accreditation_council_signatures.into_iter().unzip();
+        if accreditation_council_signers.len() < accreditation_council_quorum {
+            return Err(LeadError::AccreditationCouncilQuorumNotReached { signers: accreditation_council_signers.len(), quorum: accreditation_council_quorum });
+        }
+
+        let accreditation_council_aggregated_signature = aggregate_signatures(&accreditation_council_signatures)?;
+        self.store.put_transaction_status(transaction.get_txid(), TransactionState::Accredited, None);
+
+        let broadcast_transaction_temp = BroadcastTransactionTemp::new(transaction.clone(), accreditation_council_aggregated_signature, accreditation_council_signers, self_node_id);
+        let binary_broadcast_transaction_temp = Arc::new(binary::encode(&broadcast_transaction_temp)?);
+
+        let broadcast_node_ids = select_broadcast_node_ids(&broadcast_transaction_temp, &epoch_snapshot).map_err(Error::from)?;
+        let broadcast_quorum = calculate_quorum(broadcast_node_ids.len());
+
+        // the leader can be drawn into the broadcast set as well
+        let mut own_broadcast_signature = Vec::new();
+        if broadcast_node_ids.contains(&self_node_id) {
+            own_broadcast_signature.push((self_node_id, private_key.sign(&binary_broadcast_transaction_temp, DST, &[])));
+        }
+
+        let broadcast_node_ids: Vec<u32> = broadcast_node_ids.into_iter().filter(|id| *id != self_node_id).collect();
+        let (broadcast_signers, broadcast_signatures): (Vec<u32>, Vec<Signature>) = self.collect_signatures(
+            broadcast_node_ids,
+            own_broadcast_signature,
+            broadcast_quorum,
+            request_timeout,
+            &binary_broadcast_transaction_temp,
+            epoch_snapshot.get_node_public_keys(),
+            {
+                let binary_broadcast_transaction_temp = Arc::clone(&binary_broadcast_transaction_temp);
+                move |transport, node_id| {
+                    let binary = Arc::clone(&binary_broadcast_transaction_temp);
+                    async move {
+                        transport.broadcast_request(node_id, transaction_nonce, &binary).await
+                    }
+                }
+            }
+        ).await.into_iter().unzip();
+
+        if broadcast_signers.len() < broadcast_quorum {
+            return Err(LeadError::BroadcastQuorumNotReached { signers: broadcast_signers.len(), quorum: broadcast_quorum });
+        }
+
+        let broadcast_aggregated_signature = aggregate_signatures(&broadcast_signatures)?;
+        self.store.put_transaction_status(transaction.get_txid(), TransactionState::Broadcast, None);
+
+        let commit_certificate = CommitCertificate::new(broadcast_transaction_temp, broadcast_aggregated_signature, broadcast_signers, None);
+        let binary_commit_certificate = Arc::new(binary::encode(&commit_certificate)?);
+
+        self.store.put_commit_certificate(&commit_certificate).await?;
+
+        self.spread_commit_certificate(transaction_nonce, binary_commit_certificate).await;
+
+        Ok(commit_certificate)
+    }
+
+    /*
+        Leads the transactions of one batch window and epoch together: a council round over the merkle root
+        of the batch, a broadcast round over the batch and a commit certificate for every transaction.
+        Every transaction is answered with its certificate, or with None once its reservation is released.
+    */
+    pub async fn lead_batch(&self, epoch_number: u64, mut queued: Vec<(Transaction, Sender<Option<CommitCertificate>>)>) {
+        let (self_node_id, private_key) = match self.get_self_node_key().await {
+            Ok(data) => data,
+            Err(_) => return self.reject_transactions(queued, "invalid private key").await
+        };
+
+        let epoch_snapshot = match get_epoch_snapshot_by_epoch_number(epoch_number, Arc::clone(&self.mempool)).await {
+            Ok(data) => data,
+            Err(_) => return self.reject_transactions(queued, "epoch not found").await
+        };
+
+        let mut accreditation = None;
+        for _ in 0..BATCH_COUNCIL_ROUNDS {
+            if queued.is_empty() {
+                break;
+            }
+
+            let transactions: Vec<Transaction> = queued.iter().map(|(transaction, _)| transaction.clone()).collect();
+            match self.accredit_batch(transactions, self_node_id, &private_key, &epoch_snapshot).await {
+                Ok(data) => {
+                    accreditation = Some(data);
+                    break;
+                },
+                Err(rejected_txids) if !rejected_txids.is_empty() => {
+                    let (rejected, accepted): (Vec<_>, Vec<_>) = queued.into_iter().partition(|(transaction, _)| rejected_txids.contains(transaction.get_txid()));
+                    self.reject_transactions(rejected, "rejected by the accreditation council").await;
+                    queued = accepted;
+                },
+                Err(_) => break
+            }
+        }
+
+        let Some((accreditation_council_aggregated_signature, accreditation_council_signers)) = accreditation else {
+            return self.reject_transactions(queued, "accreditation council quorum is not reached").await;
+        };
+
+        let transactions: Vec<Transaction> = queued.iter().map(|(transaction, _)| transaction.clone()).collect();
+        let txids: Vec<[u8; 32]> = transactions.iter().map(|transaction| transaction.get_txid().clone()).collect();
+        for txid in &txids {
+            self.store.put_transaction_status(txid, TransactionState::Accredited, None);
+        }
+
+        // the broadcast set of the batch has to be as large as the largest one of its transactions
+        let node_count = epoch_snapshot.get_node_public_keys().len() as u32;
+        let broadcast_node_count = transactions.iter()
+        .map(|transaction| calculate_broadcast_node_count(transaction, node_count))
+        .max()
+        .unwrap_or_default();
+
+        let batch_temp = BatchTemp::new(epoch_number, merkle_root(&txids), accreditation_council_aggregated_signature, accreditation_council_signers.clone(), self_node_id, broadcast_node_count);
+        let Some((broadcast_aggregated_signature, broadcast_signers)) = self.broadcast_batch(&batch_temp, transactions, self_node_id, &private_key, &epoch_snapshot).await else {
+            return self.reject_transactions(queued, "broadcast quorum is not reached").await;
+        };
+        for txid in &txids {
+            self.store.put_transaction_status(txid, TransactionState::Broadcast, None);
+        }
+
+        for (index, (transaction, tx)) in queued.into_iter().enumerate() {
+            let transaction_nonce = transaction.get_nonce();
+
+            let broadcast_transaction_temp = BroadcastTransactionTemp::new(transaction.clone(), accreditation_council_aggregated_signature, accreditation_council_signers.clone(), self_node_id);
+            let batch_inclusion = BatchInclusion::new(batch_temp.clone(), merkle_path(&txids, index));
+            let commit_certificate = CommitCertificate::new(broadcast_transaction_temp, broadcast_aggregated_signature, broadcast_signers.clone(), Some(batch_inclusion));
+
+            let binary_commit_certificate = match binary::encode(&commit_certificate) {
+                Ok(data) => Arc::new(data),
+                Err(_) => {
+                    self.reject_transactions(vec![(transaction, tx)], "commit certificate to binary is failed").await;
+                    continue;
+                }
+            };
+
+            if let Err(_) = self.store.put_commit_certificate(&commit_certificate).await {
+                self.reject_transactions(vec![(transaction, tx)], "save transaction is failed").await;
+                continue;
+            }
+
+            self.spread_commit_certificate(transaction_nonce, binary_commit_certificate).await;
+
+            tx.send(Some(commit_certificate)).ok();
+        }
+    }
+
+    /*
+        One council round over the whole batch. The request goes to the union of the councils
+        of its transactions and every transaction needs the quorum of its own council, the leader
+        included, among the nodes that signed the merkle root.
+        On failure the txids to drop before the next round are returned, none means give up.
+    */
+    async fn accredit_batch(&self, transactions: Vec<Transaction>, self_node_id: u32, private_key: &SecretKey, epoch_snapshot: &EpochSnapshot) -> Result<([u8; 96], Vec<u32>), AHashSet<[u8; 32]>> {
+        let mut rejected_txids = AHashSet::new();
+        let mut councils = Vec::with_capacity(transactions.len());
+        let mut council_union = AHashSet::new();
+
+        for transaction in &transactions {
+            match select_accreditation_council_node_ids(transaction, epoch_snapshot) {
+                Ok(mut node_ids) => {
+                    node_ids.push(self_node_id);
+                    node_ids.sort();
+                    node_ids.dedup();
+
+                    council_union.extend(node_ids.iter().copied());
+                    councils.push(node_ids);
+                },
+                Err(_) => {
+                    rejected_txids.insert(transaction.get_txid().clone());
+                }
+            }
+        }
+
+        if !rejected_txids.is_empty() {
+            return Err(rejected_txids);
+        }
+
+        let transaction_batch = TransactionBatch::new(self_node_id, transactions);
+        let binary_transaction_batch = match binary::encode(&transaction_batch) {
+            Ok(data) => Arc::new(data),
+            Err(_) => return Err(rejected_txids)
+        };
+        let root = merkle_root(&transaction_batch.get_txids());
+
+        council_union.remove(&self_node_id);
+        let request_timeout = self.mempool.get_config().get_request_timeout();
+        let epoch_number = epoch_snapshot.get_epoch_number();
+        let mut set = self.request_peers(council_union.into_iter().collect(), request_timeout, move |transport, node_id| {
+            let binary = Arc::clone(&binary_transaction_batch);
+            async move {
+                transport.accreditation_council_batch_request(node_id, epoch_number, &binary).await
+            }
+        });
+
+        let mut accreditation_council_signatures: Vec<Signature> = vec![private_key.sign(&root, DST, &[])];
+        let mut accreditation_council_signers: Vec<u32> = vec![self_node_id];
+        let mut council_signatures: Vec<(u32, [u8; 96])> = Vec::new();
+
+        // every council has its own quorum, so all answers of the round are collected
+        while let Some(result) = set.join_next().await {
+            let Ok((node_id, response)) = result else {
+                continue;
+            };
+
+            match response {
+                // a signature that does not verify would spoil the aggregate, its node does not count toward any quorum
+                Ok(signature) => {
+                    if let Ok(signature) = verify_node_signature(&root, &signature, node_id, epoch_snapshot.get_node_public_keys()) {
+                        council_signatures.push((node_id, signature.to_bytes()));
+                        accreditation_council_signatures.push(signature);
+                        accreditation_council_signers.push(node_id);
+                    }
+                },
+                // a node can only reject the transactions it is council for
+                Err(PeerError::RejectedTransactions(txids)) => {
+                    for txid in txids {
+                        if let Some(index) = transaction_batch.get_transactions().iter().position(|transaction| *transaction.get_txid() == txid) {
+                            if councils[index].binary_search(&node_id).is_ok() {
+                                rejected_txids.insert(txid);
+                            }
+                        }
+                    }
+                },
+                Err(_) => {}
+            }
+        }
+
+        accreditation_council_signers.sort();
+        self.record_signed_spends(get_batch_signed_spends(transaction_batch.get_transactions(), &councils, &council_signatures));
+
+        for (transaction, council) in transaction_batch.get_transactions().iter().zip(councils.iter()) {
+            let council_signers_count = accreditation_council_signers.iter().filter(|id| council.binary_search(id).is_ok()).count();
+
+            if council_signers_count < calculate_quorum(council.len()) {
+                rejected_txids.insert(transaction.get_txid().clone());
+            }
+        }
+
+        if !rejected_txids.is_empty() {
+            return Err(rejected_txids);
+        }
+
+        match aggregate_signatures(&accreditation_council_signatures) {
+            Ok(data) => Ok((data, accreditation_council_signers)),
+            Err(_) => Err(rejected_txids)
+        }
+    }
+
+    async fn broadcast_batch(&self, batch_temp: &BatchTemp, transactions: Vec<Transaction>, self_node_id: u32, private_key: &SecretKey, epoch_snapshot: &EpochSnapshot) -> Option<([u8; 96], Vec<u32>)> {
+        let binary_batch_temp = binary::encode(batch_temp).ok()?;
+        let broadcast_batch_temp = BroadcastBatchTemp::new(batch_temp.clone(), transactions);
+        let binary_broadcast_batch_temp = Arc::new(binary::encode(&broadcast_batch_temp).ok()?);
+
+        let broadcast_node_ids = select_batch_broadcast_node_ids(broadcast_batch_temp.get_batch_temp(), epoch_snapshot).ok()?;
+        let broadcast_quorum = calculate_quorum(broadcast_node_ids.len());
+
+        // the leader can be drawn into the broadcast set as well
+        let mut own_broadcast_signature = Vec::new();
+        if broadcast_node_ids.contains(&self_node_id) {
+            own_broadcast_signature.push((self_node_id, private_key.sign(&binary_batch_temp, DST, &[])));
+        }
+
+        let request_timeout = self.mempool.get_config().get_request_timeout();
+        let epoch_number = batch_temp.get_epoch_number();
+        let broadcast_node_ids: Vec<u32> = broadcast_node_ids.into_iter().filter(|id| *id != self_node_id).collect();
+        let (broadcast_signers, broadcast_signatures): (Vec<u32>, Vec<Signature>) = self.collect_signatures(
+            broadcast_node_ids,
+            own_broadcast_signature,
+            broadcast_quorum,
+            request_timeout,
+            &binary_batch_temp,
+            epoch_snapshot.get_node_public_keys(),
+            move |transport, node_id| {
+                let binary = Arc::clone(&binary_broadcast_batch_temp);
+                async move {
+                    transport.broadcast_batch_request(node_id, epoch_number, &binary).await
+                }
+            }
+        ).await.into_iter().unzip();
+
+        if broadcast_signers.len() < broadcast_quorum {
+            return None;
+        }
+
+        Some((aggregate_signatures(&broadcast_signatures).ok()?, broadcast_signers))
+    }
+
+    async fn reject_transactions(&self, queued: Vec<(Transaction, Sender<Option<CommitCertificate>>)>, reason: &str) {
+        for (transaction, tx) in queued {
+            release_inputs(&transaction, Arc::clone(&self.mempool)).await;
+            self.store.put_transaction_status(transaction.get_txid(), TransactionState::Rejected, Some(reason));
+            tx.send(None).ok();
+        }
+    }
+
+    async fn get_self_node_key(&self) -> Result<(u32, SecretKey), Error> {
+        let (self_node_id, self_node_private_key) = {
+            let lock = self.mempool.get_self_node();
+            let self_node = lock.read().await;
+
+            (self_node.get_id(), self_node.get_private_key().clone())
+        };
+
+        Ok((self_node_id, SecretKey::from_bytes(self_node_private_key.as_slice())?))
+    }
+
+    /*
+        Sends the request to every peer at once. A refusal says nothing about the health of a peer,
+        a peer that can not be reached or does not answer in time counts as a failure.
+    */
+    fn request_peers<F, Fut>(&self, node_ids: Vec<u32>, request_timeout: Duration, request: F) -> JoinSet<(u32, Result<Vec<u8>, PeerError>)>
+    where
+        F: Fn(Arc<T>, u32) -> Fut,
+        Fut: Future<Output = Result<Vec<u8>, PeerError>> + Send + 'static
+    {
+        let mut set = JoinSet::new();
+
+        for node_id in node_ids {
+            let response = request(Arc::clone(&self.transport), node_id);
+            let mempool = Arc::clone(&self.mempool);
+            set.spawn(async move {
+                let started_at = Instant::now();
+
+                let response = match timeout(request_timeout, response).await {
+                    Ok(data) => data,
+                    Err(_) => Err(Error::new("no response").into())
+                };
+
+                match &response {
+                    Ok(_) => record_node_response(node_id, Some(started_at.elapsed()), mempool).await,
+                    Err(PeerError::Unreachable(_)) => record_node_response(node_id, None, mempool).await,
+                    Err(_) => {}
+                }
+
+                (node_id, response)
+            });
+        }
+
+        set
+    }
+
+    /*
+        Asks the peers for their signature, starting with the ones this node already has.
+        A signature that does not verify over the message against the key of its peer is dropped, it would spoil the aggregate.
+        It stops as soon as the quorum is reached or can not be reached anymore,
+        the signatures are returned sorted by their signers.
+    */
+    async fn collect_signatures<F, Fut>(&self, node_ids: Vec<u32>, mut signatures: Vec<(u32, Signature)>, quorum: usize, request_timeout: Duration, message: &[u8], node_public_keys: &AHashMap<u32, [u8; 48]>, request: F) -> Vec<(u32, Signature)>
+    where
+        F: Fn(Arc<T>, u32) -> Fut,
+        Fut: Future<Output = Result<Vec<u8>, PeerError>> + Send + 'static
+    {
+        let mut set = self.request_peers(node_ids, request_timeout, request);
+
+        let mut pending = set.len();
+        while signatures.len() < quorum && signatures.len() + pending >= quorum {
+            let Some(result) = set.join_next().await else {
+                break;
+            };
+            pending -= 1;
+
+            if let Ok((node_id, Ok(signature))) = result {
+                if let Ok(signature) = verify_node_signature(message, &signature, node_id, node_public_keys) {
+                    signatures.push((node_id, signature));
+                }
+            }
+        }
+        set.abort_all();
+
+        signatures.sort_by_key(|(node_id, _)| *node_id);
+
+        signatures
+    }
+
+    /*
+        Checks the council signatures of the peers against the ones they gave for other transactions
+        spending the same outputs. It runs beside the commit, a conflict is stored and handed to every peer.
+    */
+    fn record_signed_spends(&self, signed_spends: Vec<(u32, SignedSpend)>) {
+        let transport = Arc::clone(&self.transport);
+        let store = Arc::clone(&self.store);
+        let mempool = Arc::clone(&self.mempool);
+        tokio::spawn(async move {
+            let request_timeout = mempool.get_config().get_request_timeout();
+
+            for (node_id, signed_spend) in signed_spends {
+                if let Some(evidence) = record_signed_spend(node_id, Arc::new(signed_spend), Arc::clone(&mempool)).await {
+                    spread_evidence(&evidence, Arc::clone(&transport), Arc::clone(&store), request_timeout).await;
+                }
+            }
+        });
+    }
+
+    // every peer stores the committed transaction, a peer that does not confirm is asked again
+    async fn spread_commit_certificate(&self, transaction_nonce: u64, binary_commit_certificate: Arc<Vec<u8>>) {
+        let request_timeout = self.mempool.get_config().get_request_timeout();
+        let save_request_retries = self.mempool.get_config().get_save_request_retries();
+
+        for node_id in self.transport.get_peer_ids().await {
+            let transport = Arc::clone(&self.transport);
+            let binary = Arc::clone(&binary_commit_certificate);
+            tokio::spawn(async move {
+                for _ in 0..=save_request_retries {
+                    if let Ok(Ok(_)) = timeout(request_timeout, transport.save_request(node_id, transaction_nonce, &binary)).await {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+}
+
+/*
+    A council member signs the merkle root, which covers the whole batch. It is only held to the
+    transactions it is council for, those are the ones it reserved the inputs of.
+*/
+fn get_batch_signed_spends(transactions: &[Transaction], councils: &[Vec<u32>], signatures: &[(u32, [u8; 96])]) -> Vec<(u32, SignedSpend)> {
+    let txids: Vec<[u8; 32]> = transactions.iter().map(|transaction| transaction.get_txid().clone()).collect();
+    let mut signed_spends = Vec::new();
+
+    for (index, (transaction, council)) in transactions.iter().zip(councils).enumerate() {
+        let merkle_path = merkle_path(&txids, index);
+
+        for (node_id, signature) in signatures {
+            if council.binary_search(node_id).is_ok() {
+                signed_spends.push((*node_id, SignedSpend::new(transaction.clone(), Some(merkle_path.clone()), *signature)));
+            }
+        }
+    }
+
+    signed_spends
+}
+
+// evidence this node already knew has been spread before
+async fn spread_evidence<T: PeerTransport, S: LedgerStore>(evidence: &Evidence, transport: Arc<T>, store: Arc<S>, request_timeout: Duration) {
+    match store.put_evidence(evidence).await {
+        Ok(true) => {},
+        _ => return
+    }
+
+    let binary_evidence = match binary::encode(evidence) {
+        Ok(data) => Arc::new(data),
+        Err(_) => return
+    };
+
+    for node_id in transport.get_peer_ids().await {
+        let transport = Arc::clone(&transport);
+        let binary = Arc::clone(&binary_evidence);
+        tokio::spawn(async move {
+            timeout(request_timeout, transport.put_evidence_request(node_id, &binary)).await.ok();
+        });
+    }
+}
+
+fn aggregate_signatures(signatures: &[Signature]) -> Result<[u8; 96], Error> {
+    let signatures: Vec<&Signature> = signatures.iter().collect();
+
+    Ok(AggregateSignature::aggregate(&signatures, false)?.to_signature().to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicBool, Mutex as StdMutex};
+    use concilium_core::{active_nodes::ActiveNodes, amount::Amount, chain_state::ChainState, config::Config, epoch::{Epoch, EpochPool}, node::SelfNode, nodes_awaiting_confirmation::NodesAwaitingConfirmation, temporary_node_ids::TemporaryNodeIds, transaction::TransactionKind};
+    use concilium_core_ext::{
+        amount::AmountSupport, chain_state::ChainStateSupport, epoch::{EpochPoolSupport, EpochSupport}, mempool::{active_nodes::ActiveNodesSupport, nodes_awaiting_confirmation::NodesAwaitingConfirmationSupport}, temporary_node_ids::TemporaryNodeIdsSupport
+    };
+    use concilium_shared::epoch::timestamp_to_epoch_number;
+    use tokio::sync::{Mutex, RwLock};
+    use crate::{certificate::{verify_accreditation_council_signature_by_snapshot, verify_broadcast_signature_by_snapshot}, selection::select_leader_id};
+    use super::*;
+
+    const CREATED_AT: i64 = 1_700_000_000;
+    const NODE_COUNT: u32 = 4;
+
+    // peers that sign whatever they are asked for, or refuse everything
+    struct MemoryTransport {
+        private_keys: AHashMap<u32, SecretKey>,
+        refuse: bool,
+    }
+
+    impl MemoryTransport {
+        fn sign(&self, node_id: u32, message: &[u8]) -> Result<Vec<u8>, PeerError> {
+            if self.refuse {
+                return Err(PeerError::Refused("node refused to sign".to_string()));
+            }
+
+            match self.private_keys.get(&node_id) {
+                Some(private_key) => Ok(private_key.sign(message, DST, &[]).to_bytes().to_vec()),
+                None => Err(Error::new("node is not connected").into())
+            }
+        }
+    }
+
+    impl PeerTransport for MemoryTransport {
+        async fn get_peer_ids(&self) -> Vec<u32> {
+            self.private_keys.keys().copied().collect()
+        }
+
+        async fn accreditation_council_request(&self, node_id: u32, _: u64, transaction: &[u8]) -> Result<Vec<u8>, PeerError> {
+            self.sign(node_id, transaction)
+        }
+
+        async fn broadcast_request(&self, node_id: u32, _: u64, broadcast_transaction_temp: &[u8]) -> Result<Vec<u8>, PeerError> {
+            self.sign(node_id, broadcast_transaction_temp)
+        }
+
+        async fn accreditation_council_batch_request(&self, _: u32, _: u64, _: &[u8]) -> Result<Vec<u8>, PeerError> {
+            Err(PeerError::Refused("batches are not signed in memory".to_string()))
+        }
+
+        async fn broadcast_batch_request(&self, _: u32, _: u64, _: &[u8]) -> Result<Vec<u8>, PeerError> {
+            Err(PeerError::Refused("batches are not signed in memory".to_string()))
+        }
+
+        async fn save_request(&self, _: u32, _: u64, _: &[u8]) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn put_evidence_request(&self, _: u32, _: &[u8]) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        states: StdMutex<Vec<TransactionState>>,
+        commit_certificates: StdMutex<Vec<CommitCertificate>>,
+    }
+
+    impl LedgerStore for MemoryStore {
+        fn put_transaction_status(&self, _: &[u8; 32], state: TransactionState, _: Option<&str>) {
+            self.states.lock().unwrap().push(state);
+        }
+
+        async fn put_commit_certificate(&self, commit_certificate: &CommitCertificate) -> Result<(), Error> {
+            self.commit_certificates.lock().unwrap().push(commit_certificate.clone());
+            Ok(())
+        }
+
+        async fn put_evidence(&self, _: &Evidence) -> Result<bool, Error> {
+            Ok(true)
+        }
+    }
+
+    fn transaction() -> Transaction {
+        Transaction::new([0; 32], [1; 32], [0; 64], 0, CREATED_AT, Vec::new(), Vec::new(), TransactionKind::Transfer)
+    }
+
+    fn private_key(node_id: u32) -> SecretKey {
+        SecretKey::key_gen(&[node_id as u8; 32], &[]).unwrap()
+    }
+
+    fn self_node(node_id: u32) -> SelfNode {
+        let private_key = private_key(node_id);
+
+        SelfNode::new(node_id, Vec::new(), private_key.sk_to_pk().to_bytes(), private_key.to_bytes(), [127, 0, 0, 1], 0, Vec::new(), 0)
+    }
+
+    /*
+        A network of NODE_COUNT members behind the same temporary ids in the epoch of the transaction
+        and the one before, with the local node being the leader of the transaction.
+    */
+    async fn network(transaction: &Transaction, refuse: bool) -> (Arc<Mempool>, Arc<MemoryTransport>) {
+        let epoch_number = timestamp_to_epoch_number(CREATED_AT) as u64;
+        let node_ids: Arc<AHashMap<u32, u32>> = Arc::new((1..=NODE_COUNT).map(|node_id| (node_id, node_id)).collect());
+
+        let epoch_pool = EpochPool::new();
+        {
+            let lock = epoch_pool.get_write();
+            let mut epoch_pool_write = lock.lock().await;
+            epoch_pool_write.insert(epoch_number, Arc::new(Epoch::new(epoch_number, NODE_COUNT, [0; 32], AHashMap::new(), AHashMap::new(), AHashMap::new(), AHashMap::new())));
+            epoch_pool_write.publish();
+        }
+
+        let temporary_node_ids = TemporaryNodeIds::new();
+        {
+            let lock = temporary_node_ids.get_write();
+            let mut temporary_node_ids_write = lock.lock().await;
+            temporary_node_ids_write.insert(epoch_number - 1, Arc::clone(&node_ids));
+            temporary_node_ids_write.insert(epoch_number, Arc::clone(&node_ids));
+            temporary_node_ids_write.publish();
+        }
+
+        let node_registry = (1..=NODE_COUNT).map(|node_id| (node_id, private_key(node_id).sk_to_pk().to_bytes())).collect();
+
+        let mempool = Arc::new(Mempool {
+            self_node: Arc::new(RwLock::new(self_node(0))),
+            active_nodes: Arc::new(RwLock::new(ActiveNodes::new())),
+            nodes_awaiting_confirmation: Arc::new(RwLock::new(NodesAwaitingConfirmation::new())),
+            node_registry: Arc::new(RwLock::new(node_registry)),
+            epoch_pool: Arc::new(epoch_pool),
+            temporary_node_ids: Arc::new(temporary_node_ids),
+            utxos: Arc::new(RwLock::new(AHashMap::new())),
+            pending_spends: Arc::new(RwLock::new(AHashMap::new())),
+            chain_state: Arc::new(RwLock::new(ChainState::new())),
+            bootstrap_node_signature: Arc::new(Mutex::new([0; 96])),
+            batch_queue: Arc::new(Mutex::new(Vec::new())),
+            signed_spends: Arc::new(RwLock::new(AHashMap::new())),
+            synced: Arc::new(AtomicBool::new(true)),
+            config: Arc::new(Config {
+                request_timeout: 1_000,
+                leader_request_timeout: 1_000,
+                save_request_retries: 0,
+                min_transaction_fee: Amount::zero(),
+                batch_window: 0,
+                max_batch_size: 1,
+                reconciliation_interval: 0,
+                max_reconciliation_certificates: 1,
+                health_min_samples: 1,
+                health_min_success_rate: 0,
+                health_max_silence: 60
+            })
+        });
+
+        let epoch_snapshot = get_epoch_snapshot(transaction, Arc::clone(&mempool)).await.unwrap();
+        let leader_id = select_leader_id(transaction, &epoch_snapshot).unwrap();
+        *mempool.get_self_node().write().await = self_node(leader_id);
+
+        let transport = MemoryTransport {
+            private_keys: (1..=NODE_COUNT).filter(|node_id| *node_id != leader_id).map(|node_id| (node_id, private_key(node_id))).collect(),
+            refuse
+        };
+
+        (mempool, Arc::new(transport))
+    }
+
+    #[tokio::test]
+    async fn leads_transaction_over_transport() {
+        let transaction = transaction();
+        let (mempool, transport) = network(&transaction, false).await;
+        let store = Arc::new(MemoryStore::default());
+        let coordinator = TransactionCoordinator::new(transport, Arc::clone(&store), Arc::clone(&mempool));
+
+        let commit_certificate = coordinator.commit(&transaction).await.unwrap();
+
+        let epoch_snapshot = get_epoch_snapshot(&transaction, mempool).await.unwrap();
+        verify_accreditation_council_signature_by_snapshot(commit_certificate.get_broadcast_transaction_temp(), &epoch_snapshot).unwrap();
+        verify_broadcast_signature_by_snapshot(&commit_certificate, &epoch_snapshot).unwrap();
+
+        assert_eq!(*store.states.lock().unwrap(), vec![TransactionState::Accredited, TransactionState::Broadcast]);
+        assert_eq!(store.commit_certificates.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn refusals_leave_council_short_of_quorum() {
+        let transaction = transaction();
+        let (mempool, transport) = network(&transaction, true).await;
+        let store = Arc::new(MemoryStore::default());
+        let coordinator = TransactionCoordinator::new(transport, Arc::clone(&store), mempool);
+
+        match coordinator.commit(&transaction).await {
+            Err(LeadError::AccreditationCouncilQuorumNotReached { signers, quorum }) => assert_eq!((signers, quorum), (1, 3)),
+            result => panic!("unexpected result: {:?}", result.map(|_| ()))
+        }
+
+        assert_eq!(*store.states.lock().unwrap(), vec![TransactionState::Rejected]);
+        assert!(store.commit_certificates.lock().unwrap().is_empty());
+    }
+}
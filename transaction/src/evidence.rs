@@ -0,0 +1,213 @@
+use std::sync::Arc;
+use blst::{min_pk::{PublicKey, Signature}, BLST_ERROR};
+use chrono::Utc;
+use concilium_core::{db::DB, evidence::{Evidence, SignedSpend}, mempool::Mempool};
+use concilium_core_ext::{
+    db::DBSupport, evidence::{EvidenceSupport, SignedSpendSupport}, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, self_node::SelfNodeSupport}, transaction::{transaction::TransactionSupport, txinput::TXInputSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, epoch::{epoch_number_to_timestamp, timestamp_to_epoch_number}, transaction::{PENDING_SPEND_TIMEOUT, TRANSACTION_VALIDITY_WINDOW}, DST};
+use crate::{get_active_nodes_by_ids, validation::validate_signature_and_txid};
+
+/*
+    Remembers the transaction a peer signed for every output it spends. An honest node keeps
+    its reservation for PENDING_SPEND_TIMEOUT, so signing another transaction spending the same output
+    within it is a conflict. Signatures are only verified once they conflict, and the conflict
+    only counts when both signatures had to be made within one reservation.
+*/
+pub async fn record_signed_spend(node_id: u32, signed_spend: Arc<SignedSpend>, mempool: Arc<Mempool>) -> Option<Evidence> {
+    let now = Utc::now().timestamp();
+    let transaction = signed_spend.get_transaction();
+
+    let conflict = {
+        let lock = mempool.get_signed_spends();
+        let mut signed_spends = lock.write().await;
+
+        let mut conflict = None;
+        for input in transaction.get_vin() {
+            let key = ((input.get_txid().clone(), input.get_vout()), node_id);
+
+            match signed_spends.get(&key) {
+                Some((signed_at, previous)) if now - signed_at < PENDING_SPEND_TIMEOUT => {
+                    if previous.get_transaction().get_txid() != transaction.get_txid() && conflict.is_none() {
+                        conflict = Some((key, Arc::clone(previous)));
+                    }
+                },
+                _ => {
+                    signed_spends.insert(key, (now, Arc::clone(&signed_spend)));
+                }
+            }
+        }
+
+        conflict
+    };
+
+    let (key, previous) = conflict?;
+    let node = get_active_nodes_by_ids(&[node_id], Arc::clone(&mempool)).await.pop()?;
+    let public_key = PublicKey::from_bytes(node.get_public_key()).ok()?;
+
+    // a signature the node never made must not stand in the way of the ones it did
+    if verify_signed_spend(&previous, &public_key).is_err() {
+        let lock = mempool.get_signed_spends();
+        lock.write().await.insert(key, (now, Arc::clone(&signed_spend)));
+
+        return None;
+    }
+
+    let (first, second) = if previous.get_transaction().get_txid() < transaction.get_txid() {
+        (previous.as_ref().clone(), signed_spend.as_ref().clone())
+    } else {
+        (signed_spend.as_ref().clone(), previous.as_ref().clone())
+    };
+
+    let ((txid, vout), _) = key;
+    let evidence = Evidence::new(node_id, node.get_public_key().clone(), txid, vout, first, second, now);
+    verify_conflict(&evidence).ok()?;
+
+    Some(evidence)
+}
+
+// drops the signatures whose reservations are over
+pub async fn prune_signed_spends(mempool: Arc<Mempool>) {
+    let now = Utc::now().timestamp();
+
+    let lock = mempool.get_signed_spends();
+    let mut signed_spends = lock.write().await;
+
+    signed_spends.retain(|_, (signed_at, _)| now - *signed_at < PENDING_SPEND_TIMEOUT);
+}
+
+// evidence from a peer has to be about a node this one knows, signed with the key it knows
+pub async fn verify_evidence(evidence: &Evidence, mempool: Arc<Mempool>) -> Result<(), Error> {
+    let self_node_public_key = {
+        let lock = mempool.get_self_node();
+        let self_node = lock.read().await;
+
+        (self_node.get_id() == evidence.get_node_id()).then(|| self_node.get_public_key().clone())
+    };
+
+    let public_key = match self_node_public_key {
+        Some(data) => Some(data),
+        None => {
+            let lock = mempool.get_active_nodes();
+            let active_nodes = lock.read().await;
+
+            active_nodes.get_by_id(evidence.get_node_id()).map(|node| node.get_public_key().clone())
+        }
+    };
+
+    match public_key {
+        Some(public_key) if public_key == *evidence.get_public_key() => verify_conflict(evidence),
+        Some(_) => Err(Error::new("evidence public key does not match the node")),
+        None => Err(Error::new(format!("node {} not found", evidence.get_node_id()).as_str()))
+    }
+}
+
+/*
+    Stores the evidence and indexes it by the node. False when it was already known.
+    The signed spends lock is held, so two evidences against one node can not overwrite the index of each other.
+*/
+pub async fn put_evidence(evidence: &Evidence, mempool: Arc<Mempool>, db: &DB) -> Result<bool, Error> {
+    let id = evidence.get_id()?;
+    let key = format!("evidence.{}", hex::encode(id));
+
+    let lock = mempool.get_signed_spends();
+    let _signed_spends = lock.write().await;
+
+    if db.get(key.as_str())?.is_some() {
+        return Ok(false);
+    }
+
+    let mut evidence_ids = get_evidence_ids(evidence.get_node_id(), db)?;
+    evidence_ids.push(id);
+
+    db.put(key.as_str(), &binary::encode(evidence)?)?;
+    db.put(format!("evidence_ids.{}", evidence.get_node_id()).as_str(), &binary::encode(&evidence_ids)?)?;
+
+    Ok(true)
+}
+
+pub fn get_evidence(id: &[u8; 32], db: &DB) -> Result<Option<Evidence>, Error> {
+    match db.get(format!("evidence.{}", hex::encode(id)).as_str())? {
+        Some(data) => Ok(Some(binary::decode::<Evidence>(&data)?)),
+        None => Ok(None)
+    }
+}
+
+pub fn get_evidences_by_node_id(node_id: u32, db: &DB) -> Result<Vec<Evidence>, Error> {
+    let mut evidences = Vec::new();
+
+    for id in get_evidence_ids(node_id, db)? {
+        if let Some(evidence) = get_evidence(&id, db)? {
+            evidences.push(evidence);
+        }
+    }
+
+    Ok(evidences)
+}
+
+fn get_evidence_ids(node_id: u32, db: &DB) -> Result<Vec<[u8; 32]>, Error> {
+    match db.get(format!("evidence_ids.{}", node_id).as_str())? {
+        Some(data) => Ok(binary::decode::<Vec<[u8; 32]>>(&data)?),
+        None => Ok(Vec::new())
+    }
+}
+
+/*
+    Two valid transactions spending the output, both signed by the key of the evidence.
+    A transaction is only signed within the validity window after the epoch of its created_at,
+    and created_at is covered by the signature, so the signatures must have been made within
+    PENDING_SPEND_TIMEOUT of each other. Otherwise the first reservation may have run out.
+*/
+fn verify_conflict(evidence: &Evidence) -> Result<(), Error> {
+    let first = evidence.get_first();
+    let second = evidence.get_second();
+
+    if first.get_transaction().get_txid() >= second.get_transaction().get_txid() {
+        return Err(Error::new("evidence transactions are not distinct and sorted"));
+    }
+
+    let (first_from, first_to) = signing_window(first.get_transaction().get_created_at())?;
+    let (second_from, second_to) = signing_window(second.get_transaction().get_created_at())?;
+
+    if first_to.max(second_to).saturating_sub(first_from.min(second_from)) > PENDING_SPEND_TIMEOUT {
+        return Err(Error::new("evidence transactions could be signed in different reservations"));
+    }
+
+    let public_key = PublicKey::from_bytes(evidence.get_public_key())?;
+
+    for signed_spend in [first, second] {
+        let transaction = signed_spend.get_transaction();
+
+        validate_signature_and_txid(transaction, true)?;
+
+        if !transaction.get_vin().iter().any(|input| input.get_txid() == evidence.get_txid() && input.get_vout() == evidence.get_vout()) {
+            return Err(Error::new("evidence transaction does not spend the output"));
+        }
+
+        verify_signed_spend(signed_spend, &public_key)?;
+    }
+
+    Ok(())
+}
+
+// the seconds a transaction can be signed in, from the start of its epoch until its validity window is over
+fn signing_window(created_at: i64) -> Result<(i64, i64), Error> {
+    let epoch_number = timestamp_to_epoch_number(created_at);
+    let from = epoch_number_to_timestamp(epoch_number);
+    let to = epoch_number.checked_add(TRANSACTION_VALIDITY_WINDOW + 1).and_then(epoch_number_to_timestamp);
+
+    match (from, to) {
+        (Some(from), Some(to)) => Ok((from, to)),
+        _ => Err(Error::new("evidence transaction created_at is out of range"))
+    }
+}
+
+fn verify_signed_spend(signed_spend: &SignedSpend, public_key: &PublicKey) -> Result<(), Error> {
+    let signature = Signature::from_bytes(signed_spend.get_signature())?;
+
+    match signature.verify(true, &signed_spend.get_message()?, DST, &[], public_key, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        error => Err(error.into())
+    }
+}
\ No newline at end of file
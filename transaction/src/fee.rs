@@ -0,0 +1,77 @@
+use ahash::AHashMap;
+use concilium_core::{amount::Amount, transaction::{FeeDistribution, TXOutput, Transaction}};
+use concilium_core_ext::{amount::AmountSupport, transaction::{fee_distribution::FeeDistributionSupport, transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}};
+use concilium_shared::transaction::{ACCREDITATION_COUNCIL_FEE_SHARE, BROADCAST_FEE_SHARE};
+use crate::validation::Rejection;
+
+// the fee is implicit: everything the inputs carry that the outputs do not spend
+pub fn calculate_fee(transaction: &Transaction, utxos: &AHashMap<([u8; 32], usize), TXOutput>) -> Result<Amount, Rejection> {
+    let mut sum_vin_values = Amount::zero();
+
+    for txinput in transaction.get_vin() {
+        match utxos.get(&(txinput.get_txid().clone(), txinput.get_vout())) {
+            Some(utxo) => {
+                if utxo.get_public_key() != transaction.get_from() {
+                    return Err(Rejection::InputNotOwned);
+                }
+
+                sum_vin_values = match sum_vin_values.checked_add(utxo.get_value()) {
+                    Some(data) => data,
+                    None => return Err(Rejection::ValueOverflow)
+                };
+            },
+            None => {
+                return Err(Rejection::InputNotFound);
+            }
+        }
+    }
+
+    let sum_vout_values = match Amount::checked_sum(transaction.get_vout().iter().map(|v| &v.value)) {
+        Some(data) => data,
+        None => return Err(Rejection::ValueOverflow)
+    };
+
+    match sum_vin_values.checked_sub(sum_vout_values) {
+        Some(fee) => Ok(fee),
+        None => Err(Rejection::OutputsExceedInputs)
+    }
+}
+
+/*
+    Splits a fee between the roles that committed the transaction: the council
+    and broadcast shares are divided evenly between their signers and the leader
+    gets its own share plus everything that can not be divided.
+*/
+pub fn distribute_fee(fee: Amount, leader_id: u32, accreditation_council_signers: &[u32], broadcast_signers: &[u32]) -> FeeDistribution {
+    let mut shares: AHashMap<u32, u64> = AHashMap::new();
+    let mut distributed: u64 = 0;
+
+    let accreditation_council_signers: Vec<u32> = accreditation_council_signers.iter()
+    .filter(|id| **id != leader_id)
+    .copied()
+    .collect();
+
+    for (signers, percent) in [(accreditation_council_signers.as_slice(), ACCREDITATION_COUNCIL_FEE_SHARE), (broadcast_signers, BROADCAST_FEE_SHARE)] {
+        if signers.is_empty() {
+            continue;
+        }
+
+        let pool = (fee.get_base_units() as u128 * percent as u128 / 100) as u64;
+        let share = pool / signers.len() as u64;
+
+        for id in signers {
+            *shares.entry(*id).or_default() += share;
+            distributed += share;
+        }
+    }
+
+    *shares.entry(leader_id).or_default() += fee.get_base_units() - distributed;
+
+    let mut shares: Vec<(u32, Amount)> = shares.into_iter()
+    .filter(|(_, share)| *share > 0)
+    .map(|(id, share)| (id, Amount::new(share)))
+    .collect();
+    shares.sort_by_key(|(id, _)| *id);
+
+    FeeDistribution::new(fee, shares)
+}
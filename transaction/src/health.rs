@@ -0,0 +1,100 @@
+use std::{sync::Arc, time::Duration};
+use ahash::AHashMap;
+use chrono::Utc;
+use concilium_core::{epoch::Epoch, mempool::Mempool};
+use concilium_core_ext::{
+    config::ConfigSupport, epoch::EpochSupport, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::{active_node::ActiveNodeSupport, node_health::NodeHealthSupport}
+};
+use crate::selection::calculate_quorum;
+
+// a response with its latency, or None for a request the peer did not answer in time
+pub async fn record_node_response(node_id: u32, latency: Option<Duration>, mempool: Arc<Mempool>) {
+    let lock = mempool.get_active_nodes();
+    let active_nodes = lock.read().await;
+
+    if let Some(node) = active_nodes.get_by_id(node_id) {
+        match latency {
+            Some(latency) => node.get_health().record_success(Some(latency), Utc::now().timestamp()),
+            None => node.get_health().record_failure()
+        }
+    }
+}
+
+/*
+    Peers this node considers unhealthy: silent for longer than the configured time,
+    or failing too many requests once enough of them were counted.
+*/
+pub async fn get_unhealthy_node_ids(mempool: Arc<Mempool>) -> Vec<u32> {
+    let config = mempool.get_config();
+    let now = Utc::now().timestamp();
+    let max_silence = config.get_health_max_silence().as_secs() as i64;
+
+    let lock = mempool.get_active_nodes();
+    let active_nodes = lock.read().await;
+
+    let mut node_ids: Vec<u32> = active_nodes.get_nodes_by_id().iter()
+    .filter(|(_, node)| {
+        let health = node.get_health();
+        let success_count = health.get_success_count();
+        let samples = success_count + health.get_failure_count();
+
+        now - health.get_last_response_at() > max_silence
+            || (samples >= config.get_health_min_samples() && success_count * 100 < samples * config.get_health_min_success_rate() as u64)
+    })
+    .map(|(id, _)| *id)
+    .collect();
+    node_ids.sort();
+
+    node_ids
+}
+
+// old results weigh less every epoch, so a peer that recovered is not held to its past
+pub async fn decay_node_health(mempool: Arc<Mempool>) {
+    let lock = mempool.get_active_nodes();
+    let active_nodes = lock.read().await;
+
+    for node in active_nodes.get_nodes_by_id().values() {
+        node.get_health().decay();
+    }
+}
+
+/*
+    Nodes reported unhealthy by a quorum of the nodes that took part in the epoch.
+    At most a third of the network is excluded at once, the most reported first,
+    so a wave of failing links can not empty the committees.
+*/
+pub fn select_excluded_node_ids(exclusion_votes: &AHashMap<u32, u32>, voter_count: usize, node_count: usize) -> Vec<u32> {
+    let quorum = calculate_quorum(voter_count);
+    if quorum == 0 {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<(u32, u32)> = exclusion_votes.iter()
+    .filter(|(_, votes)| **votes as usize >= quorum)
+    .map(|(id, votes)| (*id, *votes))
+    .collect();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    candidates.truncate(node_count / 3);
+
+    let mut node_ids: Vec<u32> = candidates.into_iter().map(|(id, _)| id).collect();
+    node_ids.sort();
+
+    node_ids
+}
+
+/*
+    The exclusions agreed on for an epoch are the ones sent along with its final hash
+    by a quorum of the nodes that sent that hash. Without a quorum behind them nobody is excluded.
+*/
+pub fn get_agreed_excluded_node_ids(epoch: &Epoch) -> Vec<u32> {
+    let hash_count = epoch.get_hashes().get(epoch.get_final_hash()).copied().unwrap_or(0) as usize;
+    let quorum = calculate_quorum(hash_count);
+    if quorum == 0 {
+        return Vec::new();
+    }
+
+    epoch.get_exclusions().iter()
+    .find(|((hash, _), count)| hash == epoch.get_final_hash() && **count as usize >= quorum)
+    .map(|((_, node_ids), _)| node_ids.clone())
+    .unwrap_or_default()
+}
\ No newline at end of file
@@ -1,13 +1,33 @@
 use std::sync::Arc;
 use ahash::{AHashMap, AHashSet};
-use concilium_core::{db::DB, jrpc::transaction::SendRawTransactionRequest, mempool::Mempool, node::ActiveNode, transaction::{BroadcastTransactionTemp, TXInput, TXOutput, Transaction}};
+use concilium_core::{amount::Amount, db::DB, epoch::EpochSnapshot, jrpc::transaction::SendRawTransactionRequest, mempool::Mempool, node::ActiveNode, transaction::{BroadcastTransactionTemp, CommitCertificate, TXInput, TXOutput, Transaction, TransactionKind, TransactionState}};
 use concilium_core_ext::{
-    chain_state::ChainStateSupport, db::DBSupport, epoch::{EpochPoolSupport, EpochSupport}, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, node::active_node::ActiveNodeSupport, temporary_node_ids::TemporaryNodeIdsSupport, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}
+    amount::AmountSupport, chain_state::ChainStateSupport, db::DBSupport, epoch::{EpochPoolSupport, EpochSnapshotSupport, EpochSupport}, mempool::{active_nodes::ActiveNodesSupport, MempoolSupport}, temporary_node_ids::TemporaryNodeIdsSupport, transaction::{broadcast_transaction_temp::BroadcastTransactionTempSupport, commit_certificate::CommitCertificateSupport, fee_distribution::FeeDistributionSupport, transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}
 };
 use concilium_error::Error;
-use concilium_shared::{binary, chacha20::generate_random_number_by_seed, coventor::vec::unsigned_int::vec_to_unsigned_int, epoch::timestamp_to_epoch_number, sha::sha256, transaction::calculating_nnr};
+use concilium_shared::{binary, coventor::vec::unsigned_int::vec_to_unsigned_int, epoch::timestamp_to_epoch_number, sha::sha256};
+use fee::{calculate_fee, distribute_fee};
+use certificate::verify_commit_certificate;
+use checkpoint::get_transaction_sequence;
+use stake::{apply_stake, get_member_stakes, get_node_stakes};
+use status::put_transaction_status;
+use health::get_agreed_excluded_node_ids;
+use validation::{validate_stake, validate_structure, validate_utxo_exist_and_values};
+use selection::{calculate_nmac, select_accreditation_council_node_ids, select_broadcast_node_ids, select_leader_id, select_leader_ids};
 
 pub mod validation;
+pub mod certificate;
+pub mod selection;
+pub mod reservation;
+pub mod fee;
+pub mod coordinator;
+pub mod status;
+pub mod state;
+pub mod checkpoint;
+pub mod evidence;
+pub mod health;
+pub mod stake;
+pub mod randomness;
 
 pub fn send_raw_transaction_request_to_transaction(trx_request: SendRawTransactionRequest) -> Result<Transaction, Error> {
     let mut inputs = Vec::new();
@@ -22,11 +42,23 @@ pub fn send_raw_transaction_request_to_transaction(trx_request: SendRawTransacti
     
     for item in trx_request.vout {
         outputs.push(TXOutput::new(
-            item.value, 
+            Amount::from_decimal_str(&item.value)?, 
             hex::decode(item.public_key)?.try_into()?
         ));
     }
 
+    let kind = match (trx_request.stake, trx_request.unstake) {
+        (None, None) => TransactionKind::Transfer,
+        (Some(node_public_key), None) => TransactionKind::Stake { 
+            node_public_key: hex::decode(node_public_key)?.try_into()? 
+        },
+        (None, Some(item)) => TransactionKind::Unstake { 
+            txid: hex::decode(item.txid)?.try_into()?, 
+            vout: item.vout 
+        },
+        (Some(_), Some(_)) => return Err(Error::new("a transaction can not stake and unstake at once"))
+    };
+
     let mut transaction = Transaction::new(
         [0; 32], 
         hex::decode(trx_request.from)?.try_into()?, 
@@ -34,7 +66,8 @@ pub fn send_raw_transaction_request_to_transaction(trx_request: SendRawTransacti
         trx_request.nonce, 
         trx_request.created_at, 
         inputs, 
-        outputs
+        outputs,
+        kind
     );
 
     let binary_transaction = binary::encode(&transaction)?;
@@ -47,27 +80,7 @@ pub fn send_raw_transaction_request_to_transaction(trx_request: SendRawTransacti
 }
 
 pub async fn get_leader(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<Option<Arc<ActiveNode>>, Error> {
-    let transaction_epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
-    let hash = sha256(transaction.get_from());
-   
-    let epoch_pool = mempool.get_epoch_pool().get_read();
-    let last_node_id = match epoch_pool.get(&transaction_epoch_number) {
-        Some(data) => data.get_last_node_id(),
-        None => return Err(Error::new("epoch not found"))
-    };
-
-    let id = generate_random_number_by_seed(hash, last_node_id, 1)[0];
-
-    let temporary_node_ids = mempool.get_temporary_node_ids().get_read();
-    let leader_id = match temporary_node_ids.get(&transaction_epoch_number) {
-        Some(m) => {
-            match m.get(&id) {
-                Some(data) => data.clone(),
-                None => return Err(Error::new("epoch not found"))
-            }
-        },
-        None => return Err(Error::new("epoch not found"))
-    };
+    let leader_id = get_leader_id(transaction, Arc::clone(&mempool)).await?;
 
     let lock = mempool.get_active_nodes();
     let nodes = lock.read().await;
@@ -79,248 +92,227 @@ pub async fn get_leader(transaction: &Transaction, mempool: Arc<Mempool>) -> Res
     }
 }
 
-pub async fn get_accreditation_council_node(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<Vec<Arc<ActiveNode>>, Error> {
-    let (_, node_count_per_before_cycle, node_count_per_current_cycle, node_count_per_trx) = get_nmac(transaction, Arc::clone(&mempool)).await;
-    let transaction_epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
-    let mut exist_self_on_consensus = false;
-    let epoch_pool = mempool.get_epoch_pool().get_read();
-    
-    let last_node_id = match epoch_pool.get(&transaction_epoch_number) {
-        Some(data) => data.get_last_node_id(),
-        None => return Err(Error::new("epoch not found"))
-    };
-
-    let temporary_node_ids = mempool.get_temporary_node_ids().get_read();
-    
-    let active_nodes_lock = mempool.get_active_nodes();
-    let active_nodes = active_nodes_lock.read().await;
-    
-    let public_key_hash = sha256(&binary::encode(transaction.get_from())?);
-    let transaction_hash = sha256(&binary::encode(&transaction)?);
+pub async fn get_leader_id(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<u32, Error> {
+    let epoch_snapshot = get_epoch_snapshot(transaction, mempool).await?;
 
-    let before_temporary_node_ids = match temporary_node_ids.get(&(transaction_epoch_number - 1)) {
-        Some(data) => data,
-        None => return Err(Error::new("temporary node ids not found"))
-    };
-    let current_temporary_node_ids = match temporary_node_ids.get(&transaction_epoch_number) {
-        Some(data) => data,
-        None => return Err(Error::new("temporary node ids not found"))
-    }; 
+    select_leader_id(transaction, &epoch_snapshot)
+}
 
-    let mut nodes = AHashMap::new();
+pub async fn get_leader_ids(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<Vec<u32>, Error> {
+    let epoch_snapshot = get_epoch_snapshot(transaction, mempool).await?;
 
-    let ids_by_current_cycle = generate_random_number_by_seed(public_key_hash, last_node_id, node_count_per_current_cycle);
-    for item in ids_by_current_cycle {
-        let node_id = match current_temporary_node_ids.get(&item) {
-            Some(data) => data.clone(),
-            None => return Err(Error::new("node_id not found"))
-        };
+    select_leader_ids(transaction, &epoch_snapshot)
+}
 
-        if let Some(n) = active_nodes.get_by_id(node_id) {
-            nodes.insert(n.get_id(), Arc::clone(&n));
-        } else {
-            exist_self_on_consensus = true;
-        }
-    }
+pub async fn get_accreditation_council_node(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<Vec<Arc<ActiveNode>>, Error> {
+    let node_ids = get_accreditation_council_node_ids(transaction, Arc::clone(&mempool)).await?;
 
-    let blacklist: AHashSet<u32> = nodes.keys().cloned().collect();
+    Ok(get_active_nodes_by_ids(&node_ids, mempool).await)
+}
 
-    let nodes_needed_for_before_cycle = node_count_per_before_cycle as usize;
-    let mut initial_before_cycle_how_many= node_count_per_before_cycle;
-    loop {
-        let ids_by_before_cycle = generate_random_number_by_seed(public_key_hash, last_node_id, initial_before_cycle_how_many);
-        let mut nodes_by_before_cycle = AHashMap::new();
-        
-        for item in ids_by_before_cycle {
-            let node_id = match before_temporary_node_ids.get(&item) {
-                Some(data) => data.clone(),
-                None => return Err(Error::new("node_id not found"))
-            };
-            
-            if let Some(n) = active_nodes.get_by_id(node_id) {
-                if !blacklist.contains(&n.get_id()) {
-                    nodes_by_before_cycle.insert(n.get_id(), Arc::clone(n));
-                }
-            } else {
-                exist_self_on_consensus = true;
-            }
-        }
+pub async fn get_accreditation_council_node_ids(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<Vec<u32>, Error> {
+    let epoch_snapshot = get_epoch_snapshot(transaction, mempool).await?;
 
-        if nodes_by_before_cycle.len() != nodes_needed_for_before_cycle {
-            initial_before_cycle_how_many += 1;
-        } else {
-            nodes.extend(nodes_by_before_cycle);
-            break;
-        }
-    }
-    
-    let blacklist: AHashSet<u32> = nodes.keys().cloned().collect();
+    select_accreditation_council_node_ids(transaction, &epoch_snapshot).map_err(Error::from)
+}
 
-    let mut nodes_needed_for_transaction = node_count_per_trx as usize;
-    if exist_self_on_consensus {
-        nodes_needed_for_transaction -= 1;
-    } 
+pub async fn get_broadcast_node(broadcast_transaction_temp: &BroadcastTransactionTemp, mempool: Arc<Mempool>) -> Result<Vec<Arc<ActiveNode>>, Error> {
+    let node_ids = get_broadcast_node_ids(broadcast_transaction_temp, Arc::clone(&mempool)).await?;
 
-    let mut initial_transaction_how_many = nodes_needed_for_transaction;
-    loop {
-        let ids_by_transaction = generate_random_number_by_seed(transaction_hash, last_node_id, initial_transaction_how_many as u32);        
-        let mut nodes_by_transaction = AHashMap::new();
-        
-        for item in ids_by_transaction {
-            let node_id = match current_temporary_node_ids.get(&item) {
-                Some(data) => data.clone(),
-                None => return Err(Error::new("node_id not found"))
-            };
-        
-            if let Some(n) = active_nodes.get_by_id(node_id) {
-                if !blacklist.contains(&n.get_id()) {
-                    nodes_by_transaction.insert(n.get_id(), Arc::clone(n));
-                }
-            }
-        }
+    Ok(get_active_nodes_by_ids(&node_ids, mempool).await)
+}
 
-        if nodes_by_transaction.len() == nodes_needed_for_transaction {
-            nodes.extend(nodes_by_transaction);
-            break;
-        } else {            
-            initial_transaction_how_many += 1;
-        }
-    }
+pub async fn get_broadcast_node_ids(broadcast_transaction_temp: &BroadcastTransactionTemp, mempool: Arc<Mempool>) -> Result<Vec<u32>, Error> {
+    let epoch_snapshot = get_epoch_snapshot(broadcast_transaction_temp.get_transaction(), mempool).await?;
 
-    Ok(nodes.into_values().collect())
+    select_broadcast_node_ids(broadcast_transaction_temp, &epoch_snapshot).map_err(Error::from)
 }
 
-pub async fn get_broadcast_node(broadcast_transaction_temp: &BroadcastTransactionTemp, mempool: Arc<Mempool>) -> Result<Vec<Arc<ActiveNode>>, Error> {
-    let (nnr, _, _, _) = get_nmac(broadcast_transaction_temp.get_transaction(), Arc::clone(&mempool)).await;
-    let nnr = (nnr as f32) * 0.10;
-    let mut nnr = nnr.ceil() as u32;
+// resolves node ids to active nodes, the local node is skipped since it is never connected to itself
+pub async fn get_active_nodes_by_ids(node_ids: &[u32], mempool: Arc<Mempool>) -> Vec<Arc<ActiveNode>> {
+    let lock = mempool.get_active_nodes();
+    let active_nodes = lock.read().await;
 
-    if nnr > 128 {
-        nnr = 128;
-    }
+    node_ids.iter()
+    .filter_map(|id| active_nodes.get_by_id(*id).map(Arc::clone))
+    .collect()
+}
+
+pub async fn get_epoch_snapshot(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<EpochSnapshot, Error> {
+    let transaction_epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
 
-    let transaction_epoch_number = timestamp_to_epoch_number(broadcast_transaction_temp.get_transaction().get_created_at()) as u64;
+    get_epoch_snapshot_by_epoch_number(transaction_epoch_number, mempool).await
+}
 
+/*
+    Selection data of an epoch from what every node agrees on: the nodes behind the temporary ids of the epoch,
+    less the ones the network excluded for it, are members while their stake as of the epoch is enough.
+    Whether a node is connected to this one plays no part, so a certificate checks the same on every node.
+*/
+pub async fn get_epoch_snapshot_by_epoch_number(epoch_number: u64, mempool: Arc<Mempool>) -> Result<EpochSnapshot, Error> {
     let epoch_pool = mempool.get_epoch_pool().get_read();
-    let last_node_id = match epoch_pool.get(&transaction_epoch_number) {
-        Some(data) => data.get_last_node_id(),
+    let (last_node_id, excluded_node_ids) = match epoch_pool.get(&epoch_number) {
+        Some(data) => (data.get_last_node_id(), get_agreed_excluded_node_ids(&data)),
         None => return Err(Error::new("epoch not found"))
     };
 
     let temporary_node_ids = mempool.get_temporary_node_ids().get_read();
-    
-    let active_nodes_lock = mempool.get_active_nodes();
-    let active_nodes = active_nodes_lock.read().await;
-    
-    let broadcast_transaction_temp_hash = sha256(&binary::encode(broadcast_transaction_temp)?);
-
-    let current_temporary_node_ids = match temporary_node_ids.get(&transaction_epoch_number) {
+    let current_temporary_node_ids = match temporary_node_ids.get(&epoch_number) {
         Some(data) => data,
-        None => return Err(Error::new("node_id not found"))
-    }; 
+        None => return Err(Error::new("temporary node ids not found"))
+    };
+    let before_temporary_node_ids = epoch_number.checked_sub(1).and_then(|number| temporary_node_ids.get(&number)).unwrap_or_default();
 
-    let mut nodes = AHashMap::new();
+    let mut node_public_keys = AHashMap::new();
+    {
+        let lock = mempool.get_node_registry();
+        let node_registry = lock.read().await;
 
-    let nodes_needed = nnr as usize;
-    let mut initial_how_many = nnr;
-    loop {
-        let ids = generate_random_number_by_seed(broadcast_transaction_temp_hash, last_node_id, initial_how_many);
-        let mut nodes_broadcast = AHashMap::new();
-        
-        for item in ids {
-            let node_id = match current_temporary_node_ids.get(&item) {
-                Some(data) => data.clone(),
-                None => return Err(Error::new("node_id not found"))
-            };
-
-            if let Some(n) = active_nodes.get_by_id(node_id) {
-                nodes_broadcast.insert(n.get_id(), Arc::clone(n));
+        for node_id in current_temporary_node_ids.values() {
+            if excluded_node_ids.contains(node_id) {
+                continue;
             }
-        }
 
-        if nodes_broadcast.len() != nodes_needed {
-            initial_how_many += 1;
-        } else {
-            nodes.extend(nodes_broadcast);
-            break;
+            if let Some(public_key) = node_registry.get(node_id) {
+                node_public_keys.insert(*node_id, *public_key);
+            }
         }
     }
 
-    Ok(nodes.into_values().collect())
+    let node_stakes = {
+        let lock = mempool.get_chain_state();
+        let chain_state = lock.read().await;
+
+        get_member_stakes(&node_public_keys, &get_node_stakes(epoch_number, &chain_state))
+    };
+    node_public_keys.retain(|id, _| node_stakes.contains_key(id));
+
+    Ok(
+        EpochSnapshot::new(
+            epoch_number, 
+            last_node_id, 
+            current_temporary_node_ids, 
+            before_temporary_node_ids, 
+            node_public_keys, 
+            node_stakes,
+            excluded_node_ids
+        )
+    )
 }
 
-/* 
-    Number of members of the Accreditation Council
+// the Accreditation Council sizes of a transaction, counted over the members of its epoch like the selection does
+pub async fn get_nmac(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<(u32, u32, u32, u32), Error> {
+    let epoch_snapshot = get_epoch_snapshot(transaction, mempool).await?;
 
-    returns (
-        all nodes count, 
-        before cycle nodes count, 
-        current cycle nodes count, 
-        nodes count by transaction
-    )
+    Ok(calculate_nmac(transaction, epoch_snapshot.get_node_public_keys().len() as u32))
+}
+
+/*
+    Commits a transaction a peer already committed, sent with a save request or pulled by the reconciliation.
+    The certificate is checked against the epoch as this node sees it and the inputs have to be unspent here.
 */
-pub async fn get_nmac(transaction: &Transaction, mempool: Arc<Mempool>) -> (u32, u32, u32, u32) {
-    let sum_vouts: f32 = transaction.get_vout()
-    .iter()
-    .map(|v| { v.value })
-    .sum();
-
-    let mut nnr = calculating_nnr(sum_vouts);
-    if nnr < 128 {
-        nnr = 128;
+pub async fn apply_commit_certificate(commit_certificate: &CommitCertificate, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction();
+
+    // already persisted, e.g. a retried save request
+    if db.get(format!("transaction.{}", hex::encode(transaction.get_txid())).as_str())?.is_some() {
+        return Ok(());
     }
 
-    let node_count = {
-        let lock = mempool.get_active_nodes();
-        let nodes = lock.read().await;
-        (nodes.get_nodes_by_id().len() + 1) as u32
-    };
+    verify_commit_certificate(commit_certificate, Arc::clone(&mempool)).await?;
 
-    if nnr > node_count {
-        nnr = node_count;
-    }
-    
-    let node_count_per_section = nnr / 3;
-    let node_count_per_before_cycle = node_count_per_section;
-    let node_count_per_current_cycle = node_count_per_section;
-    let node_count_per_trx = nnr - (node_count_per_before_cycle + node_count_per_current_cycle);
+    apply_verified_commit_certificate(commit_certificate, mempool, db).await
+}
+
+/*
+    Commits a transaction whose certificate was checked by the caller, like the ledger sync of a joining node
+    that has no selection data of the past epochs and relies on the confirmation of its peers instead.
+*/
+pub async fn apply_verified_commit_certificate(commit_certificate: &CommitCertificate, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let transaction = commit_certificate.get_broadcast_transaction_temp().get_transaction();
+
+    validate_structure(transaction).map_err(|e| Error::new(e.get_message()))?;
+    validate_utxo_exist_and_values(transaction, Arc::clone(&mempool)).await.map_err(|e| Error::new(e.get_message()))?;
+    validate_stake(transaction, Arc::clone(&mempool)).await.map_err(|e| Error::new(e.get_message()))?;
 
-    (nnr, node_count_per_before_cycle, node_count_per_current_cycle, node_count_per_trx)
+    put_success_transaction_on_db(commit_certificate, mempool, db).await
 }
 
-pub async fn put_success_transaction_on_db(transaction: &Transaction, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+pub async fn put_success_transaction_on_db(commit_certificate: &CommitCertificate, mempool: Arc<Mempool>, db: Arc<DB>) -> Result<(), Error> {
+    let broadcast_transaction_temp = commit_certificate.get_broadcast_transaction_temp();
+    let transaction = broadcast_transaction_temp.get_transaction();
+
     let db_last_transaction_id = db.get("last_transaction_id")?;
     
     if let Some(id) = db_last_transaction_id {
         let u64_id = vec_to_unsigned_int::<u64>(&id);
         
         if let Some(last_transaction_id) = u64_id {
+            let lock = mempool.get_utxos();
+            let mut utxos = lock.write().await;
+
             let txid_hex = hex::encode(transaction.get_txid().clone());
-        
+
+            // every commit holds the utxos lock, so a transaction that arrives through a save request
+            // and the reconciliation at the same time is applied only once
+            if db.get(format!("transaction.{}", txid_hex).as_str())?.is_some() {
+                return Ok(());
+            }
+
+            // the inputs are still unspent here, so the fee can be taken from them
+            let fee = calculate_fee(transaction, &utxos)?;
+            let fee_distribution = distribute_fee(
+                fee,
+                broadcast_transaction_temp.get_leader_id(),
+                broadcast_transaction_temp.get_accreditation_council_signers(),
+                commit_certificate.get_broadcast_signers()
+            );
+
+            // the sender pays the outputs and the fee out of the inputs credited to it before, checked before anything is written
+            let mut outputs_fee = fee;
+            for output in transaction.get_vout() {
+                outputs_fee = outputs_fee.checked_add(output.get_value()).ok_or(Error::new("amount overflow"))?;
+            }
+            {
+                let chain_state_lock = mempool.get_chain_state();
+                let chain_state = chain_state_lock.read().await;
+
+                match chain_state.get_balances().get(transaction.get_from()) {
+                    Some(data) if *data >= outputs_fee => {},
+                    _ => return Err(Error::new("balance of the sender is short of its outputs and fee"))
+                }
+            }
+
             let binary_transaction = binary::encode(transaction)?;
 
             db.put(format!("transaction.{}", txid_hex).as_str(),&binary_transaction)?;
+            db.put(format!("fee_distribution.{}", txid_hex).as_str(), &binary::encode(&fee_distribution)?)?;
+            db.put(format!("commit_certificate.{}", txid_hex).as_str(), &binary::encode(commit_certificate)?)?;
             
             db.put(format!("transaction.id.{}", (last_transaction_id + 1)).as_str(),&txid_hex)?;
             db.put("last_transaction_id", &(last_transaction_id + 1).to_le_bytes())?;
-
-            let lock = mempool.get_utxos();
-            let mut utxos = lock.write().await;
+            put_transaction_status(transaction.get_txid(), TransactionState::Committed, None, &db)?;
 
             for input in transaction.get_vin() {
                 utxos.remove(&(input.get_txid().clone(), input.get_vout()));    
             }
 
+            {
+                let pending_spends_lock = mempool.get_pending_spends();
+                let mut pending_spends = pending_spends_lock.write().await;
+
+                for input in transaction.get_vin() {
+                    pending_spends.remove(&(input.get_txid().clone(), input.get_vout()));
+                }
+            }
+
             let chain_state_lock = mempool.get_chain_state();
             let mut chain_state = chain_state_lock.write().await;
 
-            let mut outputs_fee = 0.0;
             for (index, output ) in transaction.get_vout().iter().enumerate() {
-                outputs_fee += output.get_value();
-                
                 let account_balance = match chain_state.get_balances().get(output.get_public_key()) {
-                    Some(data) => *data + output.value,
-                    None => output.value
+                    Some(data) => data.checked_add(output.get_value()).ok_or(Error::new("amount overflow"))?,
+                    None => output.get_value()
                 };
                 chain_state.get_mut_balances().insert(output.get_public_key().clone(), account_balance);
 
@@ -335,11 +327,22 @@ pub async fn put_success_transaction_on_db(transaction: &Transaction, mempool: A
                 utxos.insert((transaction.get_txid().clone(), index), output.clone());
             }
 
-            let account_balance = match chain_state.get_balances().get(transaction.get_from()) {
-                Some(data) => *data - outputs_fee,
-                None => -outputs_fee
-            };
+            let account_balance = chain_state.get_balances().get(transaction.get_from())
+            .and_then(|data| data.checked_sub(outputs_fee))
+            .ok_or(Error::new("balance of the sender is short of its outputs and fee"))?;
             chain_state.get_mut_balances().insert(transaction.get_from().clone(), account_balance);
+            chain_state.update_nonce(transaction.get_from().clone(), transaction.get_nonce());
+            chain_state.add_committed_txid(transaction.get_txid().clone());
+            apply_stake(transaction, &mut chain_state);
+
+            // a checkpoint can already name a transaction that reached this node late
+            if get_transaction_sequence(transaction.get_txid(), &db)?.is_none() {
+                chain_state.add_uncheckpointed_txid(transaction.get_txid().clone(), timestamp_to_epoch_number(transaction.get_created_at()) as u64);
+            }
+
+            for (node_id, share) in fee_distribution.get_shares() {
+                chain_state.add_node_reward(*node_id, *share);
+            }
 
             if let Some(data) = chain_state.get_mut_transactions().get_mut(transaction.get_from()) {
                 data.insert(transaction.get_txid().clone());
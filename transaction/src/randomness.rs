@@ -0,0 +1,75 @@
+use ahash::AHashMap;
+use blst::{min_pk::{AggregateSignature, PublicKey, SecretKey, Signature}, BLST_ERROR};
+use concilium_error::Error;
+use concilium_shared::{binary, sha::sha256, DST};
+
+/*
+    Epoch randomness is the BLS signature of every node over the final hash of the epoch before.
+    A node has exactly one valid signature for it, so it can withhold its contribution but not choose it.
+*/
+pub fn sign_randomness(epoch_number: u64, previous_final_hash: &[u8; 32], private_key: &[u8; 32]) -> Result<[u8; 96], Error> {
+    let private_key = SecretKey::from_bytes(private_key)?;
+
+    Ok(private_key.sign(&get_randomness_message(epoch_number, previous_final_hash)?, DST, &[]).to_bytes())
+}
+
+// a contribution of a peer, checked against its public key
+pub fn verify_randomness(epoch_number: u64, previous_final_hash: &[u8; 32], signature: &[u8], public_key: &[u8; 48]) -> Result<[u8; 96], Error> {
+    let signature = Signature::from_bytes(signature)?;
+    let public_key = PublicKey::from_bytes(public_key)?;
+
+    match signature.verify(true, &get_randomness_message(epoch_number, previous_final_hash)?, DST, &[], &public_key, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(signature.to_bytes()),
+        error => Err(error.into())
+    }
+}
+
+/*
+    The unhealthy node ids a node votes to exclude are signed apart from its randomness,
+    so a contribution can't be replayed with votes the node never cast.
+*/
+pub fn sign_exclusion_votes(epoch_number: u64, previous_final_hash: &[u8; 32], unhealthy_node_ids: &[u32], private_key: &[u8; 32]) -> Result<[u8; 96], Error> {
+    let private_key = SecretKey::from_bytes(private_key)?;
+
+    Ok(private_key.sign(&get_exclusion_votes_message(epoch_number, previous_final_hash, unhealthy_node_ids)?, DST, &[]).to_bytes())
+}
+
+pub fn verify_exclusion_votes(epoch_number: u64, previous_final_hash: &[u8; 32], unhealthy_node_ids: &[u32], signature: &[u8], public_key: &[u8; 48]) -> Result<(), Error> {
+    let signature = Signature::from_bytes(signature)?;
+    let public_key = PublicKey::from_bytes(public_key)?;
+
+    match signature.verify(true, &get_exclusion_votes_message(epoch_number, previous_final_hash, unhealthy_node_ids)?, DST, &[], &public_key, true) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        error => Err(error.into())
+    }
+}
+
+/*
+    The candidate hash of an epoch: the aggregate of the contributions, the nodes they came from
+    and the nodes excluded with them. Nodes that received the same contributions compute the same hash.
+*/
+pub fn hash_randomness(randomness: &AHashMap<u32, [u8; 96]>, excluded_node_ids: &[u32]) -> Result<[u8; 32], Error> {
+    let mut node_ids: Vec<u32> = randomness.keys().copied().collect();
+    node_ids.sort();
+
+    let mut signatures = Vec::with_capacity(node_ids.len());
+    for node_id in node_ids.iter() {
+        signatures.push(Signature::from_bytes(&randomness[node_id])?);
+    }
+    let signatures: Vec<&Signature> = signatures.iter().collect();
+
+    let aggregated_signature = match signatures.is_empty() {
+        true => Vec::new(),
+        false => AggregateSignature::aggregate(&signatures, false)?.to_signature().to_bytes().to_vec()
+    };
+
+    Ok(sha256(&binary::encode(&(aggregated_signature, node_ids, excluded_node_ids))?))
+}
+
+fn get_randomness_message(epoch_number: u64, previous_final_hash: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    binary::encode(&(epoch_number, previous_final_hash))
+}
+
+fn get_exclusion_votes_message(epoch_number: u64, previous_final_hash: &[u8; 32], unhealthy_node_ids: &[u32]) -> Result<Vec<u8>, Error> {
+    binary::encode(&(epoch_number, previous_final_hash, unhealthy_node_ids))
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use chrono::Utc;
+use concilium_core::{mempool::Mempool, transaction::Transaction};
+use concilium_core_ext::{mempool::MempoolSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport}};
+use concilium_shared::transaction::PENDING_SPEND_TIMEOUT;
+
+/*
+    Claims the inputs of an in-flight transaction so that a conflicting
+    transaction can not pass validation on this node at the same time.
+    Claims of other transactions older than PENDING_SPEND_TIMEOUT are ignored.
+*/
+pub async fn reserve_inputs(transaction: &Transaction, mempool: Arc<Mempool>) -> bool {
+    let now = Utc::now().timestamp();
+
+    let lock = mempool.get_pending_spends();
+    let mut pending_spends = lock.write().await;
+
+    for input in transaction.get_vin() {
+        if let Some((txid, reserved_at)) = pending_spends.get(&(input.get_txid().clone(), input.get_vout())) {
+            if txid != transaction.get_txid() && now - reserved_at < PENDING_SPEND_TIMEOUT {
+                return false;
+            }
+        }
+    }
+
+    for input in transaction.get_vin() {
+        pending_spends.insert((input.get_txid().clone(), input.get_vout()), (transaction.get_txid().clone(), now));
+    }
+
+    true
+}
+
+pub async fn release_inputs(transaction: &Transaction, mempool: Arc<Mempool>) {
+    let lock = mempool.get_pending_spends();
+    let mut pending_spends = lock.write().await;
+
+    for input in transaction.get_vin() {
+        let key = (input.get_txid().clone(), input.get_vout());
+
+        if let Some((txid, _)) = pending_spends.get(&key) {
+            if txid == transaction.get_txid() {
+                pending_spends.remove(&key);
+            }
+        }
+    }
+}
@@ -0,0 +1,460 @@
+use std::fmt;
+use ahash::{AHashMap, AHashSet};
+use concilium_core::{amount::Amount, checkpoint::Checkpoint, epoch::EpochSnapshot, transaction::{BatchTemp, BroadcastTransactionTemp, Transaction}};
+use concilium_core_ext::{amount::AmountSupport, checkpoint::CheckpointSupport, epoch::EpochSnapshotSupport, transaction::{batch_temp::BatchTempSupport, broadcast_transaction_temp::BroadcastTransactionTempSupport, transaction::TransactionSupport}};
+use concilium_error::Error;
+use concilium_shared::{binary, chacha20::generate_weighted_random_number_by_seed, epoch::timestamp_to_epoch_number, sha::sha256, transaction::{calculating_nnr, BASE_UNITS_PER_COIN, LEADER_CANDIDATES, QUORUM_DENOMINATOR, QUORUM_NUMERATOR}};
+
+// why a committee could not be selected
+#[derive(Debug)]
+pub enum SelectionError {
+    InsufficientCommittee { found: usize, required: usize },
+    Failed(Error)
+}
+
+impl SelectionError {
+    pub fn get_message(&self) -> String {
+        match self {
+            SelectionError::InsufficientCommittee { found, required } => format!("insufficient committee: found {} of {} required members", found, required),
+            SelectionError::Failed(error) => error.get_message().to_string()
+        }
+    }
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_message())
+    }
+}
+
+impl From<Error> for SelectionError {
+    fn from(error: Error) -> Self {
+        SelectionError::Failed(error)
+    }
+}
+
+impl From<SelectionError> for Error {
+    fn from(error: SelectionError) -> Self {
+        match error {
+            SelectionError::Failed(error) => error,
+            error => Error::new(&error.get_message())
+        }
+    }
+}
+
+/*
+    Number of members of the Accreditation Council
+
+    returns (
+        all nodes count,
+        before cycle nodes count,
+        current cycle nodes count,
+        nodes count by transaction
+    )
+*/
+pub fn calculate_nmac(transaction: &Transaction, node_count: u32) -> (u32, u32, u32, u32) {
+    let sum_vouts = Amount::checked_sum(transaction.get_vout().iter().map(|v| &v.value))
+    .map_or(u64::MAX, |sum| sum.get_base_units());
+    let sum_vouts = (sum_vouts as f64 / BASE_UNITS_PER_COIN as f64) as f32;
+
+    let mut nnr = calculating_nnr(sum_vouts);
+    if nnr < 128 {
+        nnr = 128;
+    }
+
+    if nnr > node_count {
+        nnr = node_count;
+    }
+
+    let node_count_per_section = nnr / 3;
+    let node_count_per_before_cycle = node_count_per_section;
+    let node_count_per_current_cycle = node_count_per_section;
+    let node_count_per_trx = nnr - (node_count_per_before_cycle + node_count_per_current_cycle);
+
+    (nnr, node_count_per_before_cycle, node_count_per_current_cycle, node_count_per_trx)
+}
+
+// smallest number of signers that satisfies QUORUM_NUMERATOR / QUORUM_DENOMINATOR of a committee
+pub fn calculate_quorum(committee_size: usize) -> usize {
+    let quorum = (committee_size * QUORUM_NUMERATOR as usize).div_ceil(QUORUM_DENOMINATOR as usize);
+
+    quorum.clamp(committee_size.min(1), committee_size)
+}
+
+pub fn select_leader_id(transaction: &Transaction, epoch_snapshot: &EpochSnapshot) -> Result<u32, Error> {
+    let leader_ids = select_leader_ids(transaction, epoch_snapshot)?;
+
+    match leader_ids.first() {
+        Some(id) => Ok(*id),
+        None => Err(Error::new("node_id not found"))
+    }
+}
+
+/*
+    The selected leader followed by its backups, in the order they take over.
+    The seed is the same as the leader's, so the first candidate is always the leader.
+    Candidates are drawn by stake among the members, which leaves out the nodes excluded for the epoch.
+    There are fewer candidates when the epoch has fewer members, and none at all is an error, not a fallback.
+*/
+pub fn select_leader_ids(transaction: &Transaction, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, Error> {
+    check_epoch(transaction, epoch_snapshot)?;
+
+    let temporary_node_ids = epoch_snapshot.get_temporary_node_ids();
+    let hash = sha256(transaction.get_from());
+    let ids = generate_weighted_random_number_by_seed(hash, &get_weighted_temporary_ids(temporary_node_ids, epoch_snapshot), LEADER_CANDIDATES);
+
+    let mut leader_ids = Vec::with_capacity(ids.len());
+    for id in ids {
+        match temporary_node_ids.get(&id) {
+            Some(data) => leader_ids.push(data.clone()),
+            None => return Err(Error::new("node_id not found"))
+        }
+    }
+
+    if leader_ids.is_empty() {
+        return Err(Error::new("epoch has no member to lead"));
+    }
+
+    Ok(leader_ids)
+}
+
+/*
+    Ids of every member of the Accreditation Council, the local node included.
+    Every node computes the same set, so it can be used to check signer sets.
+*/
+pub fn select_accreditation_council_node_ids(transaction: &Transaction, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, SelectionError> {
+    let (before_cycle_node_ids, current_cycle_node_ids, transaction_node_ids) = select_accreditation_council_segments(transaction, epoch_snapshot)?;
+
+    let mut node_ids: Vec<u32> = before_cycle_node_ids.into_iter()
+    .chain(current_cycle_node_ids)
+    .chain(transaction_node_ids)
+    .collect();
+    node_ids.sort();
+
+    Ok(node_ids)
+}
+
+/*
+    The Accreditation Council by the section its members were drawn for.
+    The current cycle is drawn first, the before cycle and then the transaction fill up with nodes not drawn yet.
+
+    returns (
+        before cycle node ids,
+        current cycle node ids,
+        node ids by transaction
+    )
+*/
+pub fn select_accreditation_council_segments(transaction: &Transaction, epoch_snapshot: &EpochSnapshot) -> Result<(Vec<u32>, Vec<u32>, Vec<u32>), SelectionError> {
+    check_epoch(transaction, epoch_snapshot)?;
+
+    let present_node_ids = epoch_snapshot.get_node_public_keys();
+    let (_, node_count_per_before_cycle, node_count_per_current_cycle, node_count_per_trx) = calculate_nmac(transaction, present_node_ids.len() as u32);
+
+    let public_key_hash = sha256(&binary::encode(transaction.get_from())?);
+    let transaction_hash = sha256(&binary::encode(&transaction)?);
+
+    let before_temporary_node_ids = epoch_snapshot.get_before_temporary_node_ids();
+    let current_temporary_node_ids = epoch_snapshot.get_temporary_node_ids();
+
+    if before_temporary_node_ids.is_empty() || current_temporary_node_ids.is_empty() {
+        return Err(Error::new("temporary node ids not found").into());
+    }
+
+    let before_weights = get_weighted_temporary_ids(before_temporary_node_ids, epoch_snapshot);
+    let current_weights = get_weighted_temporary_ids(current_temporary_node_ids, epoch_snapshot);
+
+    let nodes_by_current_cycle = draw_node_ids(public_key_hash, &current_weights, current_temporary_node_ids, present_node_ids, &AHashSet::new(), node_count_per_current_cycle as usize)?;
+    let mut blacklist = nodes_by_current_cycle.clone();
+
+    let nodes_by_before_cycle = draw_node_ids(public_key_hash, &before_weights, before_temporary_node_ids, present_node_ids, &blacklist, node_count_per_before_cycle as usize)?;
+    blacklist.extend(nodes_by_before_cycle.iter().copied());
+
+    let nodes_by_transaction = draw_node_ids(transaction_hash, &current_weights, current_temporary_node_ids, present_node_ids, &blacklist, node_count_per_trx as usize)?;
+
+    Ok((sorted_node_ids(nodes_by_before_cycle), sorted_node_ids(nodes_by_current_cycle), sorted_node_ids(nodes_by_transaction)))
+}
+
+pub fn select_broadcast_node_ids(broadcast_transaction_temp: &BroadcastTransactionTemp, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, SelectionError> {
+    check_epoch(broadcast_transaction_temp.get_transaction(), epoch_snapshot)?;
+
+    let present_node_ids = epoch_snapshot.get_node_public_keys();
+    let nnr = calculate_broadcast_node_count(broadcast_transaction_temp.get_transaction(), present_node_ids.len() as u32);
+
+    let broadcast_transaction_temp_hash = sha256(&binary::encode(broadcast_transaction_temp)?);
+
+    select_node_ids_by_seed(broadcast_transaction_temp_hash, nnr, epoch_snapshot)
+}
+
+/*
+    The broadcast set of a whole batch. Its size is carried by the batch temp,
+    every transaction of the batch checks that it is not below its own broadcast size.
+*/
+pub fn select_batch_broadcast_node_ids(batch_temp: &BatchTemp, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, SelectionError> {
+    if batch_temp.get_epoch_number() != epoch_snapshot.get_epoch_number() {
+        return Err(Error::new("batch does not belong to the epoch").into());
+    }
+
+    let present_node_ids = epoch_snapshot.get_node_public_keys();
+    if batch_temp.get_broadcast_node_count() as usize > present_node_ids.len() {
+        return Err(Error::new("broadcast node count exceeds the present nodes").into());
+    }
+
+    let batch_temp_hash = sha256(&binary::encode(batch_temp)?);
+
+    select_node_ids_by_seed(batch_temp_hash, batch_temp.get_broadcast_node_count(), epoch_snapshot)
+}
+
+/*
+    The committee that signs a checkpoint, selected from the epoch the checkpoint is built in.
+    The seed is the checkpointed epoch and the previous hash, so the proposer can not pick its signers.
+*/
+pub fn select_checkpoint_committee_node_ids(checkpoint: &Checkpoint, committee_size: u32, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, SelectionError> {
+    if checkpoint.get_committee_epoch_number() != epoch_snapshot.get_epoch_number() {
+        return Err(Error::new("checkpoint does not belong to the epoch").into());
+    }
+
+    let present_node_ids = epoch_snapshot.get_node_public_keys();
+    let nnr = committee_size.min(present_node_ids.len() as u32);
+
+    let seed = sha256(&binary::encode(&(checkpoint.get_epoch_number(), checkpoint.get_previous_hash()))?);
+
+    select_node_ids_by_seed(seed, nnr, epoch_snapshot)
+}
+
+// the member that collects the signatures, every node agrees on it without asking
+pub fn select_checkpoint_proposer_id(checkpoint: &Checkpoint, committee_node_ids: &[u32]) -> Result<u32, Error> {
+    if committee_node_ids.is_empty() {
+        return Err(Error::new("checkpoint committee is empty"));
+    }
+
+    Ok(committee_node_ids[(checkpoint.get_epoch_number() % committee_node_ids.len() as u64) as usize])
+}
+
+// 10% of the Accreditation Council size of the transaction, at most 128
+pub fn calculate_broadcast_node_count(transaction: &Transaction, node_count: u32) -> u32 {
+    let (nnr, _, _, _) = calculate_nmac(transaction, node_count);
+    let nnr = (nnr as f32) * 0.10;
+    let nnr = nnr.ceil() as u32;
+
+    nnr.min(128)
+}
+
+fn select_node_ids_by_seed(seed: [u8; 32], nnr: u32, epoch_snapshot: &EpochSnapshot) -> Result<Vec<u32>, SelectionError> {
+    let present_node_ids = epoch_snapshot.get_node_public_keys();
+    let current_temporary_node_ids = epoch_snapshot.get_temporary_node_ids();
+    let current_weights = get_weighted_temporary_ids(current_temporary_node_ids, epoch_snapshot);
+
+    let nodes = draw_node_ids(seed, &current_weights, current_temporary_node_ids, present_node_ids, &AHashSet::new(), nnr as usize)?;
+
+    Ok(sorted_node_ids(nodes))
+}
+
+/*
+    Draws ids until the needed number of present nodes outside of the blacklist is found.
+    A larger draw starts with the ids of a smaller one, so every missing node costs at least one more id,
+    and once every weighted id is drawn the committee can not grow anymore.
+*/
+fn draw_node_ids(seed: [u8; 32], weights: &[(u32, u64)], temporary_node_ids: &AHashMap<u32, u32>, present_node_ids: &AHashMap<u32, [u8; 48]>, blacklist: &AHashSet<u32>, nodes_needed: usize) -> Result<AHashSet<u32>, SelectionError> {
+    let mut how_many = nodes_needed;
+    loop {
+        let ids = generate_weighted_random_number_by_seed(seed, weights, how_many as u32);
+        let mut nodes = AHashSet::with_capacity(nodes_needed);
+
+        for item in &ids {
+            let node_id = match temporary_node_ids.get(item) {
+                Some(data) => data.clone(),
+                None => return Err(Error::new("node_id not found").into())
+            };
+
+            if present_node_ids.contains_key(&node_id) && !blacklist.contains(&node_id) {
+                nodes.insert(node_id);
+            }
+        }
+
+        if nodes.len() >= nodes_needed {
+            return Ok(nodes);
+        }
+
+        if ids.len() < how_many {
+            return Err(SelectionError::InsufficientCommittee { found: nodes.len(), required: nodes_needed });
+        }
+
+        how_many += nodes_needed - nodes.len();
+    }
+}
+
+// temporary ids of the members with the stake of their node, sorted so every node draws from the same list
+fn get_weighted_temporary_ids(temporary_node_ids: &AHashMap<u32, u32>, epoch_snapshot: &EpochSnapshot) -> Vec<(u32, u64)> {
+    let node_stakes = epoch_snapshot.get_node_stakes();
+
+    let mut weights: Vec<(u32, u64)> = temporary_node_ids.iter()
+    .filter_map(|(temporary_id, node_id)| node_stakes.get(node_id).map(|stake| (*temporary_id, *stake)))
+    .collect();
+    weights.sort();
+
+    weights
+}
+
+fn sorted_node_ids(nodes: AHashSet<u32>) -> Vec<u32> {
+    let mut node_ids: Vec<u32> = nodes.into_iter().collect();
+    node_ids.sort();
+
+    node_ids
+}
+
+fn check_epoch(transaction: &Transaction, epoch_snapshot: &EpochSnapshot) -> Result<(), Error> {
+    let transaction_epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
+
+    if transaction_epoch_number != epoch_snapshot.get_epoch_number() {
+        return Err(Error::new("transaction does not belong to the epoch"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use concilium_core::transaction::TransactionKind;
+    use super::*;
+
+    const CREATED_AT: i64 = 1_700_000_000;
+
+    // node ids 1..=count behind temporary ids spread far apart
+    fn sparse_temporary_node_ids(count: u32) -> AHashMap<u32, u32> {
+        (1..=count).map(|node_id| (node_id * 65_537 + 3, node_id)).collect()
+    }
+
+    fn snapshot(temporary_node_ids: AHashMap<u32, u32>, before_temporary_node_ids: AHashMap<u32, u32>, member_node_ids: &[u32]) -> EpochSnapshot {
+        let member_stakes: Vec<(u32, u64)> = member_node_ids.iter().map(|node_id| (*node_id, 1)).collect();
+
+        snapshot_with_stakes(temporary_node_ids, before_temporary_node_ids, &member_stakes)
+    }
+
+    fn snapshot_with_stakes(temporary_node_ids: AHashMap<u32, u32>, before_temporary_node_ids: AHashMap<u32, u32>, member_stakes: &[(u32, u64)]) -> EpochSnapshot {
+        let last_node_id = temporary_node_ids.values().copied().max().unwrap_or(0);
+        let node_public_keys = member_stakes.iter().map(|(node_id, _)| (*node_id, [0; 48])).collect();
+        let node_stakes = member_stakes.iter().copied().collect();
+
+        EpochSnapshot::new(
+            timestamp_to_epoch_number(CREATED_AT) as u64, 
+            last_node_id, 
+            Arc::new(temporary_node_ids), 
+            Arc::new(before_temporary_node_ids), 
+            node_public_keys, 
+            node_stakes, 
+            Vec::new()
+        )
+    }
+
+    fn transaction() -> Transaction {
+        Transaction::new([0; 32], [1; 32], [0; 64], 0, CREATED_AT, Vec::new(), Vec::new(), TransactionKind::Transfer)
+    }
+
+    #[test]
+    fn draw_fills_committee_from_sparse_ids() {
+        let member_node_ids: Vec<u32> = (1..=10).collect();
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(10), AHashMap::new(), &member_node_ids);
+        let weights = get_weighted_temporary_ids(epoch_snapshot.get_temporary_node_ids(), &epoch_snapshot);
+
+        let nodes = draw_node_ids([5; 32], &weights, epoch_snapshot.get_temporary_node_ids(), epoch_snapshot.get_node_public_keys(), &AHashSet::new(), 10).unwrap();
+
+        assert_eq!(nodes.len(), 10);
+    }
+
+    #[test]
+    fn draw_skips_offline_ids() {
+        let temporary_node_ids = sparse_temporary_node_ids(40);
+        let epoch_snapshot = snapshot(temporary_node_ids.clone(), AHashMap::new(), &[5, 12, 33]);
+
+        let mut weights: Vec<(u32, u64)> = temporary_node_ids.keys().map(|temporary_id| (*temporary_id, 1)).collect();
+        weights.sort();
+
+        let nodes = draw_node_ids([5; 32], &weights, &temporary_node_ids, epoch_snapshot.get_node_public_keys(), &AHashSet::new(), 3).unwrap();
+        assert_eq!(nodes, AHashSet::from([5, 12, 33]));
+
+        match draw_node_ids([5; 32], &weights, &temporary_node_ids, epoch_snapshot.get_node_public_keys(), &AHashSet::new(), 4) {
+            Err(SelectionError::InsufficientCommittee { found, required }) => assert_eq!((found, required), (3, 4)),
+            result => panic!("unexpected result: {:?}", result)
+        }
+    }
+
+    #[test]
+    fn draw_does_not_count_blacklisted_nodes() {
+        let member_node_ids: Vec<u32> = (1..=3).collect();
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(3), AHashMap::new(), &member_node_ids);
+        let weights = get_weighted_temporary_ids(epoch_snapshot.get_temporary_node_ids(), &epoch_snapshot);
+
+        match draw_node_ids([5; 32], &weights, epoch_snapshot.get_temporary_node_ids(), epoch_snapshot.get_node_public_keys(), &AHashSet::from([1, 2]), 2) {
+            Err(SelectionError::InsufficientCommittee { found, required }) => assert_eq!((found, required), (1, 2)),
+            result => panic!("unexpected result: {:?}", result)
+        }
+    }
+
+    #[test]
+    fn committee_larger_than_members_is_insufficient() {
+        let member_node_ids: Vec<u32> = (1..=30).collect();
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(30), AHashMap::new(), &member_node_ids);
+
+        let node_ids = select_node_ids_by_seed([5; 32], 30, &epoch_snapshot).unwrap();
+        assert_eq!(node_ids, member_node_ids);
+
+        match select_node_ids_by_seed([5; 32], 31, &epoch_snapshot) {
+            Err(SelectionError::InsufficientCommittee { found, required }) => assert_eq!((found, required), (30, 31)),
+            result => panic!("unexpected result: {:?}", result)
+        }
+    }
+
+    #[test]
+    fn skewed_stakes_report_insufficient_committee() {
+        // a single base unit next to a million coins
+        let mut member_stakes: Vec<(u32, u64)> = (2..=30).map(|node_id| (node_id, 1)).collect();
+        member_stakes.push((1, 1_000_000 * BASE_UNITS_PER_COIN));
+        let epoch_snapshot = snapshot_with_stakes(sparse_temporary_node_ids(30), AHashMap::new(), &member_stakes);
+
+        let node_ids = select_node_ids_by_seed([5; 32], 30, &epoch_snapshot).unwrap();
+        assert_eq!(node_ids, (1..=30).collect::<Vec<u32>>());
+
+        match select_node_ids_by_seed([5; 32], 31, &epoch_snapshot) {
+            Err(SelectionError::InsufficientCommittee { found, required }) => assert_eq!((found, required), (30, 31)),
+            result => panic!("unexpected result: {:?}", result)
+        }
+    }
+
+    #[test]
+    fn leader_candidates_are_members() {
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(40), AHashMap::new(), &[5, 12, 33]);
+
+        let leader_ids = select_leader_ids(&transaction(), &epoch_snapshot).unwrap();
+        assert_eq!(sorted_node_ids(leader_ids.into_iter().collect()), vec![5, 12, 33]);
+
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(40), AHashMap::new(), &[]);
+        assert!(select_leader_ids(&transaction(), &epoch_snapshot).is_err());
+    }
+
+    #[test]
+    fn council_selects_every_member_of_sparse_ids() {
+        let member_node_ids: Vec<u32> = (1..=12).collect();
+        let before_temporary_node_ids = (1..=12).map(|node_id| (node_id * 1_000_003, node_id)).collect();
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(12), before_temporary_node_ids, &member_node_ids);
+
+        let node_ids = select_accreditation_council_node_ids(&transaction(), &epoch_snapshot).unwrap();
+
+        assert_eq!(node_ids, member_node_ids);
+    }
+
+    #[test]
+    fn council_with_offline_before_cycle_is_insufficient() {
+        let member_node_ids: Vec<u32> = (1..=9).collect();
+        let before_temporary_node_ids = AHashMap::from([(4_000_000, 1), (4_100_000, 200), (4_200_000, 300)]);
+        let epoch_snapshot = snapshot(sparse_temporary_node_ids(9), before_temporary_node_ids, &member_node_ids);
+
+        match select_accreditation_council_node_ids(&transaction(), &epoch_snapshot) {
+            Err(SelectionError::InsufficientCommittee { found, required }) => {
+                assert_eq!(required, 3);
+                assert!(found <= 1);
+            },
+            result => panic!("unexpected result: {:?}", result)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,115 @@
+use ahash::AHashMap;
+use blst::min_pk::PublicKey;
+use concilium_core::{chain_state::ChainState, stake::Stake, transaction::{Transaction, TransactionKind}};
+use concilium_core_ext::{
+    amount::AmountSupport, chain_state::ChainStateSupport, stake::StakeSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}
+};
+use concilium_shared::{epoch::timestamp_to_epoch_number, transaction::STAKED_PER_NODE};
+use crate::validation::Rejection;
+
+/*
+    The staking rules of a transaction against the ledger: a stake bonds its first output, which pays the sender,
+    an unstake names a stake of the sender that is not unbonding yet, and a bonded output is only spent
+    once its unbonding delay passed. The delay is counted from the epoch of the transaction, so every node agrees.
+*/
+pub fn check_stake(transaction: &Transaction, chain_state: &ChainState) -> Result<(), Rejection> {
+    let epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
+    let stakes = chain_state.get_stakes();
+
+    match transaction.get_kind() {
+        TransactionKind::Transfer => {},
+        TransactionKind::Stake { node_public_key } => {
+            if PublicKey::key_validate(node_public_key).is_err() {
+                return Err(Rejection::InvalidStake);
+            }
+
+            match transaction.get_vout().first() {
+                Some(output) if output.get_public_key() == transaction.get_from() => {},
+                _ => return Err(Rejection::InvalidStake)
+            }
+        },
+        TransactionKind::Unstake { txid, vout } => {
+            let stake = match stakes.get(&(*txid, *vout)) {
+                Some(data) => data,
+                None => return Err(Rejection::StakeNotFound)
+            };
+
+            if stake.get_owner() != transaction.get_from() {
+                return Err(Rejection::StakeNotOwned);
+            }
+
+            if stake.get_unbonding_epoch().is_some() {
+                return Err(Rejection::StakeAlreadyUnbonding);
+            }
+        }
+    }
+
+    for input in transaction.get_vin() {
+        if let Some(stake) = stakes.get(&(*input.get_txid(), input.get_vout())) {
+            if !stake.is_spendable_at(epoch_number) {
+                return Err(Rejection::StakeLocked);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// runs beside the utxo changes of a commit, the transaction already passed check_stake
+pub fn apply_stake(transaction: &Transaction, chain_state: &mut ChainState) {
+    let epoch_number = timestamp_to_epoch_number(transaction.get_created_at()) as u64;
+    let stakes = chain_state.get_mut_stakes();
+
+    for input in transaction.get_vin() {
+        stakes.remove(&(*input.get_txid(), input.get_vout()));
+    }
+
+    match transaction.get_kind() {
+        TransactionKind::Transfer => {},
+        TransactionKind::Stake { node_public_key } => {
+            if let Some(output) = transaction.get_vout().first() {
+                stakes.insert(
+                    (*transaction.get_txid(), 0), 
+                    Stake::new(*node_public_key, *transaction.get_from(), output.get_value(), epoch_number, None)
+                );
+            }
+        },
+        TransactionKind::Unstake { txid, vout } => {
+            if let Some(stake) = stakes.get_mut(&(*txid, *vout)) {
+                stake.set_unbonding_epoch(epoch_number);
+            }
+        }
+    }
+}
+
+// the stake that counts for every node in an epoch, in base units by BLS public key
+pub fn get_node_stakes(epoch_number: u64, chain_state: &ChainState) -> AHashMap<[u8; 48], u64> {
+    let mut node_stakes = AHashMap::new();
+
+    for stake in chain_state.get_stakes().values() {
+        if stake.is_active_at(epoch_number) {
+            let node_stake = node_stakes.entry(*stake.get_node_public_key()).or_insert(0u64);
+            *node_stake = node_stake.saturating_add(stake.get_value().get_base_units());
+        }
+    }
+
+    node_stakes
+}
+
+/*
+    Members of an epoch with their selection weight: the present nodes with at least STAKED_PER_NODE bonded.
+    Until any node of the ledger has, every present node is a member with the same weight,
+    so a new network can commit its first stakes.
+*/
+pub fn get_member_stakes(node_public_keys: &AHashMap<u32, [u8; 48]>, node_stakes: &AHashMap<[u8; 48], u64>) -> AHashMap<u32, u64> {
+    if !node_stakes.values().any(|stake| *stake >= STAKED_PER_NODE) {
+        return node_public_keys.keys().map(|id| (*id, 1)).collect();
+    }
+
+    node_public_keys.iter()
+    .filter_map(|(id, public_key)| match node_stakes.get(public_key) {
+        Some(stake) if *stake >= STAKED_PER_NODE => Some((*id, *stake)),
+        _ => None
+    })
+    .collect()
+}
\ No newline at end of file
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use ahash::AHashSet;
+use concilium_core::{amount::Amount, chain_state::{ChainState, StateCommitment}, db::DB, mempool::Mempool, stake::Stake, transaction::{Transaction, TransactionKind}};
+use concilium_core_ext::{
+    amount::AmountSupport, chain_state::{ChainStateSupport, StateCommitmentSupport}, db::DBSupport, mempool::MempoolSupport, stake::StakeSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}
+};
+use concilium_error::Error;
+use concilium_shared::{binary, epoch::timestamp_to_epoch_number, merkle::merkle_root, sha::sha256};
+
+/*
+    A deterministic root over the ledger state after the transactions of the given epoch and before:
+    the utxos and the stakes sorted by outpoint and the non-zero balances sorted by public key are merkled
+    separately and hashed together. The later transactions this node already committed are taken back out of
+    a copy of the state, so nodes that committed the same transactions up to the epoch end up with the same root
+    whenever they compute it. The utxos lock is taken before the chain state one, like a commit does.
+*/
+pub async fn compute_state_root(epoch_number: u64, mempool: Arc<Mempool>, db: &DB) -> Result<[u8; 32], Error> {
+    let utxos_lock = mempool.get_utxos();
+    let live_utxos = utxos_lock.read().await;
+
+    let chain_state_lock = mempool.get_chain_state();
+    let chain_state = chain_state_lock.read().await;
+
+    // a checkpoint only names transactions of its epoch or before, so every later one is still uncheckpointed
+    let later_txids: AHashSet<[u8; 32]> = chain_state.get_uncheckpointed_txids().iter()
+    .filter(|(_, transaction_epoch_number)| **transaction_epoch_number > epoch_number)
+    .map(|(txid, _)| *txid)
+    .collect();
+
+    let mut later_transactions = Vec::with_capacity(later_txids.len());
+    for txid in &later_txids {
+        later_transactions.push(get_committed_transaction(txid, db)?);
+    }
+
+    let mut utxos = live_utxos.clone();
+    let mut balances = chain_state.get_balances().clone();
+    let mut stakes = chain_state.get_stakes().clone();
+
+    for transaction in &later_transactions {
+        for index in 0..transaction.get_vout().len() {
+            utxos.remove(&(*transaction.get_txid(), index));
+        }
+
+        match transaction.get_kind() {
+            TransactionKind::Transfer => {},
+            TransactionKind::Stake { .. } => {
+                stakes.remove(&(*transaction.get_txid(), 0));
+            },
+            TransactionKind::Unstake { txid, vout } => {
+                if let Some(stake) = stakes.get(&(*txid, *vout)) {
+                    let stake = Stake::new(*stake.get_node_public_key(), *stake.get_owner(), stake.get_value(), stake.get_bonded_epoch(), None);
+                    stakes.insert((*txid, *vout), stake);
+                }
+            }
+        }
+    }
+
+    // the sender is credited back with its inputs before the outputs are debited, so no balance runs below zero on the way
+    for transaction in &later_transactions {
+        for input in transaction.get_vin() {
+            let previous_transaction = get_committed_transaction(input.get_txid(), db)?;
+            let output = previous_transaction.get_vout().get(input.get_vout()).ok_or(Error::new("spent output not found"))?;
+
+            let balance = balances.get(transaction.get_from()).copied().unwrap_or(Amount::zero());
+            balances.insert(*transaction.get_from(), balance.checked_add(output.get_value()).ok_or(Error::new("amount overflow"))?);
+
+            if later_txids.contains(input.get_txid()) {
+                continue;
+            }
+            utxos.insert((*input.get_txid(), input.get_vout()), output.clone());
+
+            if let TransactionKind::Stake { node_public_key } = previous_transaction.get_kind() {
+                if input.get_vout() == 0 {
+                    let bonded_epoch = timestamp_to_epoch_number(previous_transaction.get_created_at()) as u64;
+                    let unbonding_epoch = get_unbonding_epoch(input.get_txid(), input.get_vout(), transaction.get_from(), &later_txids, &chain_state, db)?;
+
+                    stakes.insert((*input.get_txid(), 0), Stake::new(*node_public_key, *transaction.get_from(), output.get_value(), bonded_epoch, unbonding_epoch));
+                }
+            }
+        }
+    }
+
+    for transaction in &later_transactions {
+        for output in transaction.get_vout() {
+            let balance = balances.get(output.get_public_key()).copied().unwrap_or(Amount::zero());
+            balances.insert(*output.get_public_key(), balance.checked_sub(output.get_value()).ok_or(Error::new("balance underflow"))?);
+        }
+    }
+
+    let mut outpoints: Vec<&([u8; 32], usize)> = utxos.keys().collect();
+    outpoints.sort();
+
+    let mut utxo_leaves = Vec::with_capacity(outpoints.len());
+    for outpoint in outpoints {
+        let output = &utxos[outpoint];
+        utxo_leaves.push(sha256(&binary::encode(&(outpoint.0, outpoint.1 as u64, output))?));
+    }
+
+    // an account the later transactions opened is left at zero, like one that spent everything
+    let mut public_keys: Vec<&[u8; 32]> = balances.iter()
+    .filter(|(_, balance)| **balance != Amount::zero())
+    .map(|(public_key, _)| public_key)
+    .collect();
+    public_keys.sort();
+
+    let mut balance_leaves = Vec::with_capacity(public_keys.len());
+    for public_key in public_keys {
+        let balance = &balances[public_key];
+        balance_leaves.push(sha256(&binary::encode(&(public_key, balance))?));
+    }
+
+    let mut stake_outpoints: Vec<&([u8; 32], usize)> = stakes.keys().collect();
+    stake_outpoints.sort();
+
+    let mut stake_leaves = Vec::with_capacity(stake_outpoints.len());
+    for outpoint in stake_outpoints {
+        let stake = &stakes[outpoint];
+        stake_leaves.push(sha256(&binary::encode(&(outpoint.0, outpoint.1 as u64, stake))?));
+    }
+
+    let mut roots = merkle_root(&utxo_leaves).to_vec();
+    roots.extend_from_slice(&merkle_root(&balance_leaves));
+    roots.extend_from_slice(&merkle_root(&stake_leaves));
+
+    Ok(sha256(&roots))
+}
+
+pub fn put_state_commitment(state_commitment: &StateCommitment, db: &DB) -> Result<(), Error> {
+    db.put(format!("state_commitment.{}", state_commitment.get_epoch_number()).as_str(), &binary::encode(state_commitment)?)
+}
+
+pub fn get_state_commitment(epoch_number: u64, db: &DB) -> Result<Option<StateCommitment>, Error> {
+    match db.get(format!("state_commitment.{}", epoch_number).as_str())? {
+        Some(data) => Ok(Some(binary::decode::<StateCommitment>(&data)?)),
+        None => Ok(None)
+    }
+}
+
+fn get_committed_transaction(txid: &[u8; 32], db: &DB) -> Result<Transaction, Error> {
+    match db.get(format!("transaction.{}", hex::encode(txid)).as_str())? {
+        Some(data) => Ok(binary::decode::<Transaction>(&data)?),
+        None => Err(Error::new(format!("committed transaction {} not found", hex::encode(txid)).as_str()))
+    }
+}
+
+// a spent stake was unstaked before, by an earlier transaction of its owner unless a later one did it
+fn get_unbonding_epoch(txid: &[u8; 32], vout: usize, owner: &[u8; 32], later_txids: &AHashSet<[u8; 32]>, chain_state: &ChainState, db: &DB) -> Result<Option<u64>, Error> {
+    let Some(owner_txids) = chain_state.get_transactions().get(owner) else {
+        return Ok(None);
+    };
+
+    for owner_txid in owner_txids.iter().filter(|owner_txid| !later_txids.contains(*owner_txid)) {
+        let transaction = get_committed_transaction(owner_txid, db)?;
+
+        if let TransactionKind::Unstake { txid: unstaked_txid, vout: unstaked_vout } = transaction.get_kind() {
+            if unstaked_txid == txid && *unstaked_vout == vout {
+                return Ok(Some(timestamp_to_epoch_number(transaction.get_created_at()) as u64));
+            }
+        }
+    }
+
+    Ok(None)
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use chrono::Utc;
+use concilium_core::{db::DB, transaction::{TransactionState, TransactionStatus}};
+use concilium_core_ext::{db::DBSupport, transaction::transaction_status::{TransactionStateSupport, TransactionStatusSupport}};
+use concilium_error::Error;
+use concilium_shared::binary;
+
+/*
+    Records how far a transaction got on this node. The state only moves forward,
+    a rejected transaction can be received again and a committed one never changes.
+*/
+pub fn put_transaction_status(txid: &[u8; 32], state: TransactionState, reason: Option<&str>, db: &DB) -> Result<(), Error> {
+    if let Some(current) = get_transaction_status(txid, db)? {
+        let current_state = current.get_state();
+
+        if current_state.is_final() {
+            return Ok(());
+        }
+
+        if state != TransactionState::Rejected && state.get_rank() <= current_state.get_rank() {
+            return Ok(());
+        }
+    }
+
+    let transaction_status = TransactionStatus::new(state, reason.map(str::to_string), Utc::now().timestamp());
+
+    db.put(format!("transaction_status.{}", hex::encode(txid)).as_str(), &binary::encode(&transaction_status)?)
+}
+
+pub fn get_transaction_status(txid: &[u8; 32], db: &DB) -> Result<Option<TransactionStatus>, Error> {
+    match db.get(format!("transaction_status.{}", hex::encode(txid)).as_str())? {
+        Some(data) => Ok(Some(binary::decode::<TransactionStatus>(&data)?)),
+        None => Ok(None)
+    }
+}
\ No newline at end of file
@@ -1,11 +1,135 @@
-use std::sync::Arc;
-use concilium_core::{mempool::Mempool, transaction::Transaction};
-use concilium_core_ext::{mempool::MempoolSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}};
-use concilium_shared::{binary, sha::sha256};
+use std::{fmt, sync::Arc};
+use ahash::AHashSet;
+use concilium_core::{amount::Amount, mempool::Mempool, transaction::Transaction};
+use concilium_core_ext::{amount::AmountSupport, chain_state::ChainStateSupport, config::ConfigSupport, mempool::MempoolSupport, transaction::{transaction::TransactionSupport, txinput::TXInputSupport, txoutput::TXOutputSupport}};
+use crate::{fee::calculate_fee, stake::check_stake};
+use concilium_error::Error;
+use concilium_shared::{binary, epoch::{current_epoch_number, timestamp_to_epoch_number}, sha::sha256, transaction::{MAX_TRANSACTION_INPUTS, MAX_TRANSACTION_OUTPUTS, TRANSACTION_VALIDITY_WINDOW}};
 use ed25519_dalek::{Signature, VerifyingKey, Verifier};
-use rust_decimal::prelude::*;
 
-pub fn validate_signature_and_txid(transaction: &Transaction, check_txid: bool) -> bool {
+// the rule a transaction was rejected by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    EmptyInputs,
+    EmptyOutputs,
+    TooManyInputs,
+    TooManyOutputs,
+    DuplicateInput,
+    ZeroValueOutput,
+    ValueOverflow,
+    InvalidEncoding,
+    InvalidTxid,
+    InvalidPublicKey,
+    InvalidSignature,
+    FutureDated,
+    OutsideValidityWindow,
+    NonceAlreadyUsed,
+    InputNotFound,
+    InputNotOwned,
+    OutputsExceedInputs,
+    FeeTooLow,
+    InvalidStake,
+    StakeNotFound,
+    StakeNotOwned,
+    StakeAlreadyUnbonding,
+    StakeLocked,
+}
+
+impl Rejection {
+    pub fn get_message(&self) -> &'static str {
+        match self {
+            Rejection::EmptyInputs => "transaction has no inputs",
+            Rejection::EmptyOutputs => "transaction has no outputs",
+            Rejection::TooManyInputs => "transaction has too many inputs",
+            Rejection::TooManyOutputs => "transaction has too many outputs",
+            Rejection::DuplicateInput => "transaction spends an input twice",
+            Rejection::ZeroValueOutput => "transaction has an output without value",
+            Rejection::ValueOverflow => "transaction value overflows",
+            Rejection::InvalidEncoding => "transaction can not be encoded",
+            Rejection::InvalidTxid => "invalid txid",
+            Rejection::InvalidPublicKey => "invalid public key",
+            Rejection::InvalidSignature => "invalid signature",
+            Rejection::FutureDated => "transaction is future-dated",
+            Rejection::OutsideValidityWindow => "transaction is outside of the validity window",
+            Rejection::NonceAlreadyUsed => "nonce is already used",
+            Rejection::InputNotFound => "utxo not found",
+            Rejection::InputNotOwned => "utxo does not belong to the sender",
+            Rejection::OutputsExceedInputs => "sum of outputs exceeds sum of inputs",
+            Rejection::FeeTooLow => "fee is below the minimum transaction fee",
+            Rejection::InvalidStake => "stake has to bond its first output, paid to the sender, to a valid node public key",
+            Rejection::StakeNotFound => "stake not found",
+            Rejection::StakeNotOwned => "stake does not belong to the sender",
+            Rejection::StakeAlreadyUnbonding => "stake is already unbonding",
+            Rejection::StakeLocked => "utxo is bonded until its unbonding delay passed",
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_message())
+    }
+}
+
+impl From<Rejection> for Error {
+    fn from(rejection: Rejection) -> Self {
+        Error::new(rejection.get_message())
+    }
+}
+
+/*
+    Runs every rule in order and stops at the first one that fails.
+    The txid is only checked when it was set by the sender, not computed locally.
+*/
+pub async fn validate_transaction(transaction: &Transaction, check_txid: bool, mempool: Arc<Mempool>) -> Result<(), Rejection> {
+    validate_structure(transaction)?;
+    validate_signature_and_txid(transaction, check_txid)?;
+    validate_timestamp(transaction)?;
+    validate_nonce(transaction, Arc::clone(&mempool)).await?;
+    validate_utxo_exist_and_values(transaction, Arc::clone(&mempool)).await?;
+    validate_stake(transaction, Arc::clone(&mempool)).await?;
+    validate_fee(transaction, mempool).await
+}
+
+pub fn validate_structure(transaction: &Transaction) -> Result<(), Rejection> {
+    let vin = transaction.get_vin();
+    let vout = transaction.get_vout();
+
+    if vin.is_empty() {
+        return Err(Rejection::EmptyInputs);
+    }
+
+    if vout.is_empty() {
+        return Err(Rejection::EmptyOutputs);
+    }
+
+    if vin.len() > MAX_TRANSACTION_INPUTS {
+        return Err(Rejection::TooManyInputs);
+    }
+
+    if vout.len() > MAX_TRANSACTION_OUTPUTS {
+        return Err(Rejection::TooManyOutputs);
+    }
+
+    let mut inputs = AHashSet::with_capacity(vin.len());
+    for input in vin {
+        if !inputs.insert((input.get_txid().clone(), input.get_vout())) {
+            return Err(Rejection::DuplicateInput);
+        }
+    }
+
+    if vout.iter().any(|output| output.get_value() == Amount::zero()) {
+        return Err(Rejection::ZeroValueOutput);
+    }
+
+    if Amount::checked_sum(vout.iter().map(|output| &output.value)).is_none() {
+        return Err(Rejection::ValueOverflow);
+    }
+
+    Ok(())
+}
+
+pub fn validate_signature_and_txid(transaction: &Transaction, check_txid: bool) -> Result<(), Rejection> {
     let preimage = Transaction::new(
         [0; 32], 
         *transaction.get_from(), 
@@ -13,71 +137,87 @@ pub fn validate_signature_and_txid(transaction: &Transaction, check_txid: bool)
         transaction.nonce, 
         transaction.created_at, 
         transaction.get_vin().to_vec(),
-        transaction.get_vout().to_vec()
+        transaction.get_vout().to_vec(),
+        *transaction.get_kind()
     );
 
     let binary_preimage = match binary::encode(&preimage) {
         Ok(data) => data,
-        Err(_) => return false
+        Err(_) => return Err(Rejection::InvalidEncoding)
     };
     let hash = sha256(&binary_preimage);
 
     if check_txid == true && hash != transaction.get_txid().clone() {
-        return false;
+        return Err(Rejection::InvalidTxid);
     }
 
     let verifying_key = match VerifyingKey::from_bytes(transaction.get_from()) {
         Ok(data) => data,
-        Err(_) => return false
+        Err(_) => return Err(Rejection::InvalidPublicKey)
     };
     let signature = Signature::from_bytes(transaction.get_signature());
 
-    if let Ok(_) = verifying_key.verify(&hash, &signature) {
-        true
-    } else {
-        false
+    match verifying_key.verify(&hash, &signature) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Rejection::InvalidSignature)
+    }
+}
+
+/*
+    A transaction is handled by the committee of the epoch it was created in,
+    so it is only accepted within TRANSACTION_VALIDITY_WINDOW epochs after it.
+*/
+pub fn validate_timestamp(transaction: &Transaction) -> Result<(), Rejection> {
+    let current_epoch_number = current_epoch_number();
+    let transaction_epoch_number = timestamp_to_epoch_number(transaction.get_created_at());
+
+    if transaction_epoch_number > current_epoch_number {
+        return Err(Rejection::FutureDated);
+    }
+
+    if current_epoch_number - transaction_epoch_number > TRANSACTION_VALIDITY_WINDOW {
+        return Err(Rejection::OutsideValidityWindow);
+    }
+
+    Ok(())
+}
+
+pub async fn validate_nonce(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<(), Rejection> {
+    let lock = mempool.get_chain_state();
+    let chain_state = lock.read().await;
+
+    match chain_state.get_nonces().get(transaction.get_from()) {
+        Some(last_nonce) if transaction.get_nonce() <= *last_nonce => Err(Rejection::NonceAlreadyUsed),
+        _ => Ok(())
     }
 }
 
-// pub fn validate_timestamp(transaction: &Transaction) -> bool {
-//     current_epoch_number() == timestamp_to_epoch_number(transaction.get_created_at())
-// }
+pub async fn validate_utxo_exist_and_values(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<(), Rejection> {
+    let lock = mempool.get_utxos();
+    let utxos = lock.read().await;
+
+    calculate_fee(transaction, &utxos).map(|_| ())
+}
+
+pub async fn validate_stake(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<(), Rejection> {
+    let lock = mempool.get_chain_state();
+    let chain_state = lock.read().await;
 
-pub async fn validate_utxo_exist_and_values(transaction: &Transaction, mempool: Arc<Mempool>) -> bool {
-    let sum_vin_values = {
+    check_stake(transaction, &chain_state)
+}
+
+// the minimum fee is a policy of this node, committed transactions are not checked against it
+pub async fn validate_fee(transaction: &Transaction, mempool: Arc<Mempool>) -> Result<(), Rejection> {
+    let fee = {
         let lock = mempool.get_utxos();
         let utxos = lock.read().await;
-        
-        let mut sum_vin_values: f64 = 0.0;
-
-        for txinput in transaction.get_vin() {
-            match utxos.get(&(txinput.get_txid().clone(), txinput.get_vout())) {
-                Some(utxo) => {
-                    sum_vin_values += utxo.get_value() as f64;
-                    if utxo.get_public_key() != transaction.get_from() {
-                        return false;
-                    }
-                },
-                None => {
-                    return false;
-                }
-            }
-        }
 
-        sum_vin_values
+        calculate_fee(transaction, &utxos)?
     };
 
-    let sum_vout_values: f64 = transaction.get_vout()
-    .iter()
-    .map(|v| { v.value as f64 })
-    .sum();
-
-    let sum_vout_values = Decimal::from_f64(sum_vout_values)
-        .unwrap()
-        .round_dp(2);
-    let sum_vin_values = Decimal::from_f64(sum_vin_values)
-        .unwrap()
-        .round_dp(2);
+    if fee < mempool.get_config().get_min_transaction_fee() {
+        return Err(Rejection::FeeTooLow);
+    }
 
-    sum_vout_values == sum_vin_values
-}
\ No newline at end of file
+    Ok(())
+}